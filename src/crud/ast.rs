@@ -1,16 +1,21 @@
 use proc_macro2::Span;
 use syn::parse::{Parse, ParseStream};
-use syn::{Error, Ident, Result, Token, braced, token};
+use syn::{Error, Ident, LitInt, Result, Token, braced, token};
+
+use crate::helpers::parse_flag_options;
 
 #[derive(Debug)]
 pub struct ConfigAst {
+    pub options: RepositoryOptions,
     pub repository_name: Ident,
     pub objects: Vec<ObjectDef>,
 }
 
 impl Parse for ConfigAst {
     fn parse(input: ParseStream<'_>) -> Result<Self> {
-        // Expect a repository name first, followed by a semicolon.
+        // Optional repository-level options (e.g., `#[events]`), followed by
+        // the repository name and a semicolon.
+        let options: RepositoryOptions = input.parse()?;
         let repository_name: Ident = input.parse()?;
         if !input.peek(Token![;]) {
             if ObjectKind::is_object_leading_ident(&repository_name) {
@@ -39,12 +44,47 @@ impl Parse for ConfigAst {
             // by syn).
         }
         Ok(Self {
+            options,
             repository_name,
             objects,
         })
     }
 }
 
+/// Options given as outer attributes on the repository name (e.g.,
+/// `#[events] MyRepo;`).
+#[derive(Debug, Default)]
+pub struct RepositoryOptions {
+    pub events: bool,
+    pub schema: bool,
+    pub diagram: bool,
+    pub client: bool,
+    pub paginated: bool,
+    pub transactions: bool,
+}
+
+impl Parse for RepositoryOptions {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let mut options = Self::default();
+        // `fragments(...)` is split off before parsing (see `crud::fragment`);
+        // it only gets here when the rest of the header is malformed, which
+        // the caller then reports.
+        parse_flag_options(
+            input,
+            &mut [
+                ("events", &mut options.events),
+                ("schema", &mut options.schema),
+                ("diagram", &mut options.diagram),
+                ("client", &mut options.client),
+                ("paginated", &mut options.paginated),
+                ("transactions", &mut options.transactions),
+            ],
+            &["fragments"],
+        )?;
+        Ok(options)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ObjectKind {
     Phantom,
@@ -77,7 +117,8 @@ impl ObjectKind {
 
     fn is_object_leading_ident(ident: &Ident) -> bool {
         let ident_str = ident.to_string();
-        ident_str == "archive" || Self::from_str(ident_str.as_str()).is_some()
+        ObjectModifiers::is_modifier(ident_str.as_str())
+            || Self::from_str(ident_str.as_str()).is_some()
     }
 }
 
@@ -102,6 +143,10 @@ impl Parse for ObjectKind {
 #[derive(Debug)]
 pub struct ObjectDef {
     pub is_archive: bool,
+    pub is_soft_delete: bool,
+    pub is_movable_archive: bool,
+    pub is_versioned: bool,
+    pub is_validated: bool,
     pub kind: ObjectKind,
     pub name: Ident,
     pub props: ObjectPropsRaw,
@@ -109,7 +154,13 @@ pub struct ObjectDef {
 
 impl Parse for ObjectDef {
     fn parse(input: ParseStream<'_>) -> Result<Self> {
-        let is_archive = parse_archive_prefix(input)?;
+        let ObjectModifiers {
+            is_archive,
+            is_soft_delete,
+            is_movable_archive,
+            is_versioned,
+            is_validated,
+        } = input.parse()?;
         let kind: ObjectKind = input.parse()?;
         let name: Ident = input.parse()?;
         let content;
@@ -121,6 +172,10 @@ impl Parse for ObjectDef {
         let mut batch_children: Option<Vec<Ident>> = None;
         let mut singleton_children: Option<Vec<Ident>> = None;
         let mut indexed_singleton_children: Option<Vec<Ident>> = None;
+        let mut filterable: Option<Vec<Ident>> = None;
+        let mut table: Option<Ident> = None;
+        let mut hooks: Option<Ident> = None;
+        let mut cache_ttl: Option<LitInt> = None;
 
         while !content.is_empty() {
             let key: Ident = content.parse()?;
@@ -177,13 +232,38 @@ impl Parse for ObjectDef {
                     }
                     indexed_singleton_children = Some(parse_ident_list(&content)?);
                 }
+                "filterable" => {
+                    if filterable.is_some() {
+                        return Err(Error::new(key.span(), "duplicate `filterable` property"));
+                    }
+                    filterable = Some(parse_ident_list(&content)?);
+                }
+                "table" => {
+                    if table.is_some() {
+                        return Err(Error::new(key.span(), "duplicate `table` property"));
+                    }
+                    table = Some(content.parse()?);
+                }
+                "hooks" => {
+                    if hooks.is_some() {
+                        return Err(Error::new(key.span(), "duplicate `hooks` property"));
+                    }
+                    hooks = Some(content.parse()?);
+                }
+                "cache_ttl" => {
+                    if cache_ttl.is_some() {
+                        return Err(Error::new(key.span(), "duplicate `cache_ttl` property"));
+                    }
+                    cache_ttl = Some(content.parse()?);
+                }
                 _ => {
                     return Err(Error::new(
                         key.span(),
                         format!(
                             "unknown property `{}`; expected one of: `parent`, \
                              `ordered_children`, `unordered_children`, `batch_children`, \
-                             `singleton_children`, `indexed_singleton_children`",
+                             `singleton_children`, `indexed_singleton_children`, `filterable`, \
+                             `table`, `hooks`, `cache_ttl`",
                             key
                         ),
                     ));
@@ -198,6 +278,10 @@ impl Parse for ObjectDef {
 
         Ok(Self {
             is_archive,
+            is_soft_delete,
+            is_movable_archive,
+            is_versioned,
+            is_validated,
             kind,
             name,
             props: ObjectPropsRaw {
@@ -207,20 +291,62 @@ impl Parse for ObjectDef {
                 batch_children: batch_children.unwrap_or_default(),
                 singleton_children: singleton_children.unwrap_or_default(),
                 indexed_singleton_children: indexed_singleton_children.unwrap_or_default(),
+                filterable: filterable.unwrap_or_default(),
+                table,
+                hooks,
+                cache_ttl,
             },
         })
     }
 }
 
-fn parse_archive_prefix(input: ParseStream<'_>) -> Result<bool> {
-    let fork = input.fork();
-    if let Ok(ident) = fork.parse::<Ident>() {
-        if ident.to_string() == "archive" {
+/// Optional keywords preceding the object kind (e.g., `archive ordered Foo`).
+/// Modifiers may appear in any order, but each at most once.
+#[derive(Debug, Default)]
+pub(crate) struct ObjectModifiers {
+    is_archive: bool,
+    is_soft_delete: bool,
+    is_movable_archive: bool,
+    is_versioned: bool,
+    is_validated: bool,
+}
+
+impl ObjectModifiers {
+    pub(crate) fn is_modifier(s: &str) -> bool {
+        matches!(
+            s,
+            "archive" | "soft_delete" | "movable_archive" | "versioned" | "validate"
+        )
+    }
+}
+
+impl Parse for ObjectModifiers {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let mut modifiers = Self::default();
+        loop {
+            let fork = input.fork();
+            let Ok(ident) = fork.parse::<Ident>() else {
+                break;
+            };
+            let flag = match ident.to_string().as_str() {
+                "archive" => &mut modifiers.is_archive,
+                "soft_delete" => &mut modifiers.is_soft_delete,
+                "movable_archive" => &mut modifiers.is_movable_archive,
+                "versioned" => &mut modifiers.is_versioned,
+                "validate" => &mut modifiers.is_validated,
+                _ => break,
+            };
+            if *flag {
+                return Err(Error::new(
+                    ident.span(),
+                    format!("duplicate `{}` modifier", ident),
+                ));
+            }
+            *flag = true;
             let _: Ident = input.parse()?;
-            return Ok(true);
         }
+        Ok(modifiers)
     }
-    Ok(false)
 }
 
 #[derive(Debug, Default)]
@@ -231,6 +357,10 @@ pub struct ObjectPropsRaw {
     pub batch_children: Vec<Ident>,
     pub singleton_children: Vec<Ident>,
     pub indexed_singleton_children: Vec<Ident>,
+    pub filterable: Vec<Ident>,
+    pub table: Option<Ident>,
+    pub hooks: Option<Ident>,
+    pub cache_ttl: Option<LitInt>,
 }
 
 fn parse_ident_list(input: ParseStream<'_>) -> Result<Vec<Ident>> {
@@ -291,4 +421,74 @@ mod tests {
                 .contains("expected repository name before object definitions")
         );
     }
+
+    #[test]
+    fn parses_soft_delete_modifier_in_any_order() {
+        let ast: ConfigAst = syn::parse_str(
+            r#"
+            MyRepo;
+            soft_delete archive ordered PersonaPrinciple {
+                parent: Persona
+            }
+            archive soft_delete unordered PersonaSample {
+                parent: Persona
+            }
+            "#,
+        )
+        .unwrap();
+
+        assert!(ast.objects.iter().all(|o| o.is_archive && o.is_soft_delete));
+
+        let err = syn::parse_str::<ConfigAst>(
+            r#"
+            MyRepo;
+            soft_delete soft_delete ordered PersonaPrinciple {}
+            "#,
+        )
+        .unwrap_err();
+
+        assert!(err.to_string().contains("duplicate `soft_delete` modifier"));
+    }
+
+    #[test]
+    fn parses_repository_options_before_repository_name() {
+        let ast: ConfigAst = syn::parse_str(
+            r#"
+            #[events]
+            #[schema]
+            MyRepo;
+            root Persona {}
+            "#,
+        )
+        .unwrap();
+
+        assert!(ast.options.events);
+        assert!(ast.options.schema);
+        assert_eq!(ast.repository_name.to_string(), "MyRepo");
+
+        let err = syn::parse_str::<ConfigAst>(
+            r#"
+            #[audited]
+            MyRepo;
+            "#,
+        )
+        .unwrap_err();
+
+        assert!(
+            err.to_string()
+                .contains("unknown repository option `audited`")
+        );
+    }
+
+    #[test]
+    fn reports_the_header_rather_than_the_fragments_option() {
+        // `#[fragments(...)]` is split off before parsing unless the header
+        // is malformed, as here.
+        let err = syn::parse_str::<ConfigAst>("#[fragments(billing)] root Persona {}").unwrap_err();
+
+        assert!(
+            err.to_string()
+                .contains("expected repository name before object definitions")
+        );
+    }
 }
@@ -0,0 +1,312 @@
+//! Attribute form of the crud DSL: `#[crud_object(...)]` on each object type,
+//! collected by `crud_repository!`.
+//!
+//! Proc macro invocations cannot share state, so each `#[crud_object]` emits a
+//! hidden `macro_rules!` holding the object's definition in DSL form. The
+//! collector then walks the listed objects by invoking the first of these
+//! macros with the list of the others; each appends its definition and
+//! invokes the next, and the last hands the assembled DSL to
+//! `__crud_repository_step!`, which runs the same parser, model, and codegen
+//! as `crud_scaffolding!`.
+//!
+//! Every object (and fragment) adds one level of macro expansion, so
+//! repositories with more than about 120 of them exceed rustc's default
+//! `recursion_limit` of 128. The crate invoking `crud_repository!` (or merging
+//! the fragments) then needs a higher limit, e.g. `#![recursion_limit = "256"]`.
+
+use proc_macro2::TokenStream;
+use quote::{ToTokens as _, quote};
+use syn::{
+    Attribute, DeriveInput, Error, Ident, LitInt, Path, Result, Token, bracketed,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+};
+
+use crate::crud::{
+    ast::{ObjectDef, ObjectModifiers},
+    fragment::split_fragments,
+};
+
+/// Arguments of `#[crud_object(...)]`, in any order: modifiers and the object
+/// kind as bare keywords, and properties as `key = value` (with lists written
+/// as `[a, b]`).
+pub struct ObjectArgs {
+    modifiers: Vec<Ident>,
+    kinds: Vec<Ident>,
+    props: Vec<(Ident, PropValue)>,
+}
+
+enum PropValue {
+    Ident(Ident),
+    Int(LitInt),
+    List(Vec<Ident>),
+}
+
+impl Parse for ObjectArgs {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let mut args = Self {
+            modifiers: Vec::new(),
+            kinds: Vec::new(),
+            props: Vec::new(),
+        };
+        while !input.is_empty() {
+            let key: Ident = input.parse()?;
+            if input.peek(Token![=]) {
+                input.parse::<Token![=]>()?;
+                let value = if input.peek(syn::token::Bracket) {
+                    let content;
+                    bracketed!(content in input);
+                    let items = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?;
+                    if items.is_empty() {
+                        return Err(Error::new(
+                            key.span(),
+                            format!("`{}` requires at least one identifier", key),
+                        ));
+                    }
+                    PropValue::List(items.into_iter().collect())
+                } else if input.peek(LitInt) {
+                    PropValue::Int(input.parse()?)
+                } else {
+                    PropValue::Ident(input.parse()?)
+                };
+                args.props.push((key, value));
+            } else if ObjectModifiers::is_modifier(&key.to_string()) {
+                args.modifiers.push(key);
+            } else {
+                args.kinds.push(key);
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(args)
+    }
+}
+
+impl ObjectArgs {
+    /// The object definition as it would be written in `crud_scaffolding!`.
+    fn to_dsl(&self, name: &Ident) -> TokenStream {
+        let Self {
+            modifiers,
+            kinds,
+            props,
+        } = self;
+        let props = props.iter().map(|(key, value)| {
+            let value = match value {
+                PropValue::Ident(ident) => ident.to_token_stream(),
+                PropValue::Int(lit) => lit.to_token_stream(),
+                PropValue::List(items) => quote! { #(#items),* },
+            };
+            quote! { #key: #value }
+        });
+        quote! {
+            #(#modifiers)* #(#kinds)* #name {
+                #(#props)*
+            }
+        }
+    }
+}
+
+pub(crate) fn object_macro_ident_for(ty_ident: &Ident) -> Ident {
+    Ident::new(&format!("__crud_object_{}", ty_ident), ty_ident.span())
+}
+
+/// Expansion of `#[crud_object(...)]`: the item itself, unchanged, followed by
+/// the hidden macro contributing its definition to `crud_repository!`.
+pub fn expand_object(args: ObjectArgs, item: TokenStream) -> Result<TokenStream> {
+    let input: DeriveInput = syn::parse2(item.clone())?;
+    let dsl = args.to_dsl(&input.ident);
+    // Report mistakes here, rather than where the repository is collected.
+    syn::parse2::<ObjectDef>(dsl.clone())?;
+
+    let contributor = contributor_macro(&object_macro_ident_for(&input.ident), &dsl);
+    Ok(quote! {
+        #item
+        #contributor
+    })
+}
+
+/// Hidden macro appending `dsl` to the definitions collected so far, then
+/// invoking the next macro in the list (see `step`).
+pub(crate) fn contributor_macro(macro_ident: &Ident, dsl: &TokenStream) -> TokenStream {
+    quote! {
+        #[doc(hidden)]
+        #[allow(unused_macros)]
+        macro_rules! #macro_ident {
+            ([$($head:tt)*] [[$($next:tt)*] $($rest:tt)*] $($defs:tt)*) => {
+                $($next)*! { [$($head)*] [$($rest)*] $($defs)* #dsl }
+            };
+        }
+
+        #[doc(hidden)]
+        #[allow(unused_imports)]
+        pub(crate) use #macro_ident;
+    }
+}
+
+/// Input of `crud_repository!`: optional repository options, the repository
+/// name, and the paths of the object types (ex. `#[events] MyRepo: [A, b::B]`).
+pub struct RepositoryArgs {
+    options: Vec<Attribute>,
+    repository_name: Ident,
+    objects: Vec<Path>,
+}
+
+impl Parse for RepositoryArgs {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let options = input.call(Attribute::parse_outer)?;
+        let repository_name: Ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let content;
+        bracketed!(content in input);
+        let objects = Punctuated::<Path, Token![,]>::parse_terminated(&content)?;
+        if objects.is_empty() {
+            return Err(Error::new(
+                repository_name.span(),
+                "expected at least one object type (e.g., `MyRepo: [Persona]`)",
+            ));
+        }
+        Ok(Self {
+            options,
+            repository_name,
+            objects: objects.into_iter().collect(),
+        })
+    }
+}
+
+/// Expansion of `crud_repository!`: start the walk at the first object (or
+/// fragment, if the repository also merges `#[fragments(...)]`).
+pub fn expand_repository(args: RepositoryArgs) -> Result<TokenStream> {
+    let RepositoryArgs {
+        options,
+        repository_name,
+        objects,
+    } = args;
+    let (options, fragments) = split_fragments(options, &repository_name)?;
+    let contributors = objects
+        .into_iter()
+        .map(|mut path| {
+            if let Some(last) = path.segments.last_mut() {
+                last.ident = object_macro_ident_for(&last.ident);
+            }
+            path
+        })
+        .chain(fragments)
+        .collect();
+    Ok(step(
+        quote! { #(#options)* #repository_name },
+        contributors,
+        TokenStream::new(),
+    ))
+}
+
+/// Input of `__crud_repository_step!`, invoked by the last contributing
+/// macro: the repository header and the collected definitions.
+pub struct StepArgs {
+    head: TokenStream,
+    defs: TokenStream,
+}
+
+impl Parse for StepArgs {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let head;
+        bracketed!(head in input);
+        let rest;
+        bracketed!(rest in input);
+        if !rest.is_empty() {
+            return Err(rest.error("unexpected contributing macros after the last one"));
+        }
+        Ok(Self {
+            head: head.parse()?,
+            defs: input.parse()?,
+        })
+    }
+}
+
+/// Expansion of `__crud_repository_step!`: the complete `crud_scaffolding!`
+/// input.
+pub fn expand_step(args: StepArgs) -> TokenStream {
+    let StepArgs { head, defs } = args;
+    quote! { #head; #defs }
+}
+
+/// Invoke the first of the contributing macros in `contributors`, passing the
+/// others (followed by `__crud_repository_step!`) for each to invoke in turn.
+/// Each step therefore costs a single level of macro expansion.
+pub(crate) fn step(head: TokenStream, contributors: Vec<Path>, defs: TokenStream) -> TokenStream {
+    let mut chain = contributors
+        .iter()
+        .map(|path| quote! { #path })
+        .chain(std::iter::once(
+            quote! { ::fractic_crate_scaffolding::__crud_repository_step },
+        ));
+    let first = chain.next().expect("the step macro is always in the chain");
+    quote! {
+        #first! {
+            [#head]
+            [#([#chain])*]
+            #defs
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+
+    use super::{ObjectArgs, RepositoryArgs, StepArgs, expand_repository, expand_step};
+    use crate::crud::{ConfigAst, ConfigModel};
+
+    #[test]
+    fn converts_attribute_arguments_to_object_definitions() {
+        let args: ObjectArgs = syn::parse2(quote! {
+            ordered, archive, parent = Persona, filterable = [name, rank], cache_ttl = 30
+        })
+        .unwrap();
+        let name = syn::parse_str("PersonaPrinciple").unwrap();
+        let defs = args.to_dsl(&name);
+
+        let ast: ConfigAst = syn::parse2(quote! {
+            MyRepo;
+            root Persona {
+                ordered_children: PersonaPrinciple
+            }
+            #defs
+        })
+        .unwrap();
+        let model = ConfigModel::try_from(ast).unwrap();
+
+        let object = &model.ordered_objects[0];
+        assert_eq!(object.name.to_string(), "PersonaPrinciple");
+        assert!(object.is_archive);
+        assert_eq!(object.parents.as_ref().unwrap()[0].to_string(), "Persona");
+        assert_eq!(object.filterable.len(), 2);
+        assert_eq!(object.cache_ttl, Some(30));
+    }
+
+    #[test]
+    fn chains_contributors_one_expansion_level_each() {
+        let args: RepositoryArgs =
+            syn::parse2(quote! { #[events] MyRepo: [Persona, models::Task] }).unwrap();
+        let code = expand_repository(args).unwrap().to_string();
+
+        // The first contributor receives the others, ending in the step macro.
+        let expected = quote! {
+            __crud_object_Persona! {
+                [#[events] MyRepo]
+                [[models::__crud_object_Task] [::fractic_crate_scaffolding::__crud_repository_step]]
+            }
+        };
+        assert_eq!(code, expected.to_string());
+    }
+
+    #[test]
+    fn completes_the_dsl_at_the_step_macro() {
+        let args: StepArgs = syn::parse2(quote! { [MyRepo] [] root Persona {} }).unwrap();
+        assert_eq!(
+            expand_step(args).to_string(),
+            quote! { MyRepo; root Persona {} }.to_string()
+        );
+        assert!(syn::parse2::<StepArgs>(quote! { [MyRepo] [[__crud_object_Task]] }).is_err());
+    }
+}
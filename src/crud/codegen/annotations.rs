@@ -6,12 +6,20 @@ use proc_macro2::TokenStream;
 use quote::quote;
 use syn::Ident;
 
+use super::{
+    filters::filter_ident_for,
+    hooks::manager_ident_for,
+    repository::{archived_manage_ident_for, version_conflict_ident_for},
+    transaction,
+    validation::gen_data_validation,
+};
 use crate::{
     crud::model::{
         BatchDef, ConfigModel, HasParents, IndexedSingletonDef, PhantomDef, SingletonDef,
         StandardDef,
     },
     helpers::to_snake_case,
+    instrument::{SelfRole, instrument_methods},
 };
 
 pub fn generate(model: &ConfigModel) -> TokenStream {
@@ -25,41 +33,41 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
     let root_items: Vec<TokenStream> = model
         .phantom_objects
         .iter()
-        .map(gen_phantom_item)
+        .map(|phantom| gen_phantom_item(phantom, model))
         .chain(
             model
                 .ordered_objects
                 .iter()
                 .filter(|root| root.parents.is_none())
-                .map(|root| gen_root_standard_item(root, true)),
+                .map(|root| gen_root_standard_item(root, true, model)),
         )
         .chain(
             model
                 .unordered_objects
                 .iter()
                 .filter(|root| root.parents.is_none())
-                .map(|root| gen_root_standard_item(root, false)),
+                .map(|root| gen_root_standard_item(root, false, model)),
         )
         .chain(
             model
                 .batch_objects
                 .iter()
                 .filter(|batch| batch.parents.is_none())
-                .map(gen_root_batch_item),
+                .map(|batch| gen_root_batch_item(batch, model)),
         )
         .chain(
             model
                 .singleton_objects
                 .iter()
                 .filter(|singleton| singleton.parents.is_none())
-                .map(gen_root_singleton_item),
+                .map(|singleton| gen_root_singleton_item(singleton, model)),
         )
         .chain(
             model
                 .indexed_singleton_objects
                 .iter()
                 .filter(|indexed_singleton| indexed_singleton.parents.is_none())
-                .map(gen_root_indexed_singleton_item),
+                .map(|indexed_singleton| gen_root_indexed_singleton_item(indexed_singleton, model)),
         )
         .collect();
 
@@ -77,38 +85,40 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
             .ordered_objects
             .iter()
             .filter(|child| child.parents.is_some())
-            .map(|child| gen_child_standard_item(child, parent_of(child), true))
+            .map(|child| gen_child_standard_item(child, parent_of(child), true, model))
             .chain(
                 model
                     .unordered_objects
                     .iter()
                     .filter(|child| child.parents.is_some())
-                    .map(|child| gen_child_standard_item(child, parent_of(child), false)),
+                    .map(|child| gen_child_standard_item(child, parent_of(child), false, model)),
             )
             .chain(
                 model
                     .batch_objects
                     .iter()
                     .filter(|child| child.parents.is_some())
-                    .map(|child| gen_child_batch_item(child, parent_of(child))),
+                    .map(|child| gen_child_batch_item(child, parent_of(child), model)),
             )
             .chain(
                 model
                     .singleton_objects
                     .iter()
                     .filter(|child| child.parents.is_some())
-                    .map(|child| gen_child_singleton_item(child, parent_of(child))),
+                    .map(|child| gen_child_singleton_item(child, parent_of(child), model)),
             )
             .chain(
                 model
                     .indexed_singleton_objects
                     .iter()
                     .filter(|child| child.parents.is_some())
-                    .map(|child| gen_child_indexed_singleton_item(child, parent_of(child))),
+                    .map(|child| gen_child_indexed_singleton_item(child, parent_of(child), model)),
             )
             .collect()
     };
 
+    let transaction = transaction::generate(model);
+
     let root_items_clone = root_items.clone();
     let child_items_clone = child_items.clone();
 
@@ -123,6 +133,8 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
                 #(#root_items)*
                 // Children:
                 #(#child_items)*
+                // Transactions:
+                #transaction
             };
             ($ctx:ty => $ctx_repo_accessor:ident) => {
                 // Local helper for the context type when a concrete type is
@@ -132,6 +144,8 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
                 #(#root_items_clone)*
                 // Children:
                 #(#child_items_clone)*
+                // Transactions:
+                #transaction
             };
         }
 
@@ -140,7 +154,7 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
     }
 }
 
-fn gen_phantom_item(phantom: &PhantomDef) -> TokenStream {
+fn gen_phantom_item(phantom: &PhantomDef, model: &ConfigModel) -> TokenStream {
     let ty_ident = &phantom.name;
 
     let (ordered_child_methods, ordered_child_impls) = phantom
@@ -149,7 +163,9 @@ fn gen_phantom_item(phantom: &PhantomDef) -> TokenStream {
         .map(|child_name| {
             let child_ident = child_name;
             let child_data_ident = dynamo_data_type(child_ident);
-            let child_manager_ident = method_ident_for("manage", child_ident);
+            let child_data_check = gen_data_validation(model, child_ident, quote! { &data }, false);
+            let child_data_checks = gen_data_validation(model, child_ident, quote! { data.iter() }, true);
+            let child_manager_ident = manager_ident_for(child_ident, model.is_intercepted(child_ident));
             let base_pascal = stripped_pascal(ty_ident, child_ident);
             let child_singular_snake = to_snake_case(&base_pascal);
             let child_plural_snake = to_snake_case(&pluralize_pascal(&base_pascal));
@@ -161,22 +177,36 @@ fn gen_phantom_item(phantom: &PhantomDef) -> TokenStream {
             );
             let list_children_fn =
                 Ident::new(&format!("list_{}", child_plural_snake), child_ident.span());
+            let (list_children_page_methods, list_children_page_impls) =
+                gen_child_page_methods(model, child_ident, &child_plural_snake, &child_manager_ident);
+            let (filter_methods, filter_impls) =
+                gen_child_filter_methods(model, child_ident, &child_plural_snake, &child_manager_ident);
+            let (deleted_methods, deleted_impls) =
+                gen_child_soft_delete_methods(model, child_ident, &child_plural_snake, &child_manager_ident);
             (
                 quote! {
                     async fn #add_child_fn(&self, ctx: __ctx!(), data: #child_data_ident, after: ::std::option::Option<& #child_ident>) -> ::std::result::Result<#child_ident, ::fractic_server_error::ServerError>;
                     async fn #batch_add_children_fn(&self, ctx: __ctx!(), data: ::std::vec::Vec<#child_data_ident>, after: ::std::option::Option<& #child_ident>) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError>;
                     async fn #list_children_fn(&self, ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError>;
+                    #list_children_page_methods
+                    #filter_methods
+                    #deleted_methods
                 },
                 quote! {
                     async fn #add_child_fn(&self, ctx: __ctx!(), data: #child_data_ident, after: ::std::option::Option<& #child_ident>) -> ::std::result::Result<#child_ident, ::fractic_server_error::ServerError> {
+                        #child_data_check
                         ctx.$ctx_repo_accessor().await?.#child_manager_ident().add(self, data, after).await
                     }
                     async fn #batch_add_children_fn(&self, ctx: __ctx!(), data: ::std::vec::Vec<#child_data_ident>, after: ::std::option::Option<& #child_ident>) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError> {
+                        #child_data_checks
                         ctx.$ctx_repo_accessor().await?.#child_manager_ident().batch_add(self, data, after).await
                     }
                     async fn #list_children_fn(&self, ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError> {
                         ctx.$ctx_repo_accessor().await?.#child_manager_ident().query_all(self).await
                     }
+                    #list_children_page_impls
+                    #filter_impls
+                    #deleted_impls
                 },
             )
         })
@@ -188,7 +218,9 @@ fn gen_phantom_item(phantom: &PhantomDef) -> TokenStream {
         .map(|child_name| {
             let child_ident = child_name;
             let child_data_ident = dynamo_data_type(child_ident);
-            let child_manager_ident = method_ident_for("manage", child_ident);
+            let child_data_check = gen_data_validation(model, child_ident, quote! { &data }, false);
+            let child_data_checks = gen_data_validation(model, child_ident, quote! { data.iter() }, true);
+            let child_manager_ident = manager_ident_for(child_ident, model.is_intercepted(child_ident));
             let base_pascal = stripped_pascal(ty_ident, child_ident);
             let child_singular_snake = to_snake_case(&base_pascal);
             let child_plural_snake = to_snake_case(&pluralize_pascal(&base_pascal));
@@ -200,22 +232,36 @@ fn gen_phantom_item(phantom: &PhantomDef) -> TokenStream {
             );
             let list_children_fn =
                 Ident::new(&format!("list_{}", child_plural_snake), child_ident.span());
+            let (list_children_page_methods, list_children_page_impls) =
+                gen_child_page_methods(model, child_ident, &child_plural_snake, &child_manager_ident);
+            let (filter_methods, filter_impls) =
+                gen_child_filter_methods(model, child_ident, &child_plural_snake, &child_manager_ident);
+            let (deleted_methods, deleted_impls) =
+                gen_child_soft_delete_methods(model, child_ident, &child_plural_snake, &child_manager_ident);
             (
                 quote! {
                     async fn #add_child_fn(&self, ctx: __ctx!(), data: #child_data_ident) -> ::std::result::Result<#child_ident, ::fractic_server_error::ServerError>;
                     async fn #batch_add_children_fn(&self, ctx: __ctx!(), data: ::std::vec::Vec<#child_data_ident>) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError>;
                     async fn #list_children_fn(&self, ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError>;
+                    #list_children_page_methods
+                    #filter_methods
+                    #deleted_methods
                 },
                 quote! {
                     async fn #add_child_fn(&self, ctx: __ctx!(), data: #child_data_ident) -> ::std::result::Result<#child_ident, ::fractic_server_error::ServerError> {
+                        #child_data_check
                         ctx.$ctx_repo_accessor().await?.#child_manager_ident().add(self, data).await
                     }
                     async fn #batch_add_children_fn(&self, ctx: __ctx!(), data: ::std::vec::Vec<#child_data_ident>) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError> {
+                        #child_data_checks
                         ctx.$ctx_repo_accessor().await?.#child_manager_ident().batch_add(self, data).await
                     }
                     async fn #list_children_fn(&self, ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError> {
                         ctx.$ctx_repo_accessor().await?.#child_manager_ident().query_all(self).await
                     }
+                    #list_children_page_impls
+                    #filter_impls
+                    #deleted_impls
                 },
             )
         })
@@ -227,10 +273,15 @@ fn gen_phantom_item(phantom: &PhantomDef) -> TokenStream {
         .map(|batch_name| {
             let batch_ident = batch_name;
             let batch_data_ident = dynamo_data_type(batch_ident);
-            let batch_manager_ident = method_ident_for("manage", batch_ident);
+            let batch_data_checks = gen_data_validation(model, batch_ident, quote! { data.iter() }, true);
+            let batch_manager_ident = manager_ident_for(batch_ident, model.is_intercepted(batch_ident));
             let base_pascal = stripped_pascal(ty_ident, batch_ident);
             let plural_snake = to_snake_case(&pluralize_pascal(&base_pascal));
             let list_fn = Ident::new(&format!("list_{}", plural_snake), batch_ident.span());
+            let (list_page_methods, list_page_impls) =
+                gen_child_page_methods(model, batch_ident, &plural_snake, &batch_manager_ident);
+            let (filter_methods, filter_impls) =
+                gen_child_filter_methods(model, batch_ident, &plural_snake, &batch_manager_ident);
             let del_all_fn = Ident::new(
                 &format!("batch_delete_all_{}", plural_snake),
                 batch_ident.span(),
@@ -242,6 +293,8 @@ fn gen_phantom_item(phantom: &PhantomDef) -> TokenStream {
             (
                 quote! {
                     async fn #list_fn(&self, ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#batch_ident>, ::fractic_server_error::ServerError>;
+                    #list_page_methods
+                    #filter_methods
                     async fn #del_all_fn(&self, ctx: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
                     async fn #replace_all_fn(&self, ctx: __ctx!(), data: ::std::vec::Vec<#batch_data_ident>) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
                 },
@@ -249,10 +302,13 @@ fn gen_phantom_item(phantom: &PhantomDef) -> TokenStream {
                     async fn #list_fn(&self, ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#batch_ident>, ::fractic_server_error::ServerError> {
                         ctx.$ctx_repo_accessor().await?.#batch_manager_ident().query_all(self).await
                     }
+                    #list_page_impls
+                    #filter_impls
                     async fn #del_all_fn(&self, ctx: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
                         ctx.$ctx_repo_accessor().await?.#batch_manager_ident().batch_delete_all(self).await
                     }
                     async fn #replace_all_fn(&self, ctx: __ctx!(), data: ::std::vec::Vec<#batch_data_ident>) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+                        #batch_data_checks
                         ctx.$ctx_repo_accessor().await?.#batch_manager_ident().batch_replace_all_ordered(self, data).await
                     }
                 },
@@ -266,7 +322,8 @@ fn gen_phantom_item(phantom: &PhantomDef) -> TokenStream {
         .map(|child_name| {
             let child_ident = child_name;
             let child_data_ident = dynamo_data_type(child_ident);
-            let child_manager_ident = method_ident_for("manage", child_ident);
+            let child_data_check = gen_data_validation(model, child_ident, quote! { &data }, false);
+            let child_manager_ident = manager_ident_for(child_ident, model.is_intercepted(child_ident));
             let base_pascal = stripped_pascal(ty_ident, child_ident);
             let singular_snake = to_snake_case(&base_pascal);
             let get_fn = Ident::new(&format!("get_{}", singular_snake), child_ident.span());
@@ -283,6 +340,7 @@ fn gen_phantom_item(phantom: &PhantomDef) -> TokenStream {
                         ctx.$ctx_repo_accessor().await?.#child_manager_ident().find(self).await
                     }
                     async fn #set_fn(&self, ctx: __ctx!(), data: #child_data_ident) -> ::std::result::Result<#child_ident, ::fractic_server_error::ServerError> {
+                        #child_data_check
                         ctx.$ctx_repo_accessor().await?.#child_manager_ident().set(self, data).await
                     }
                     async fn #delete_fn(&self, ctx: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
@@ -299,7 +357,9 @@ fn gen_phantom_item(phantom: &PhantomDef) -> TokenStream {
         .map(|child_name| {
             let child_ident = child_name;
             let child_data_ident = dynamo_data_type(child_ident);
-            let child_manager_ident = method_ident_for("manage", child_ident);
+            let child_data_check = gen_data_validation(model, child_ident, quote! { &data }, false);
+            let child_data_checks = gen_data_validation(model, child_ident, quote! { data.iter() }, true);
+            let child_manager_ident = manager_ident_for(child_ident, model.is_intercepted(child_ident));
             let base_pascal = stripped_pascal(ty_ident, child_ident);
             let singular_snake = to_snake_case(&base_pascal);
             let plural_snake = to_snake_case(&pluralize_pascal(&base_pascal));
@@ -312,6 +372,10 @@ fn gen_phantom_item(phantom: &PhantomDef) -> TokenStream {
             let batch_delete_fn =
                 Ident::new(&format!("batch_delete_{}", plural_snake), child_ident.span());
             let list_fn = Ident::new(&format!("list_{}", plural_snake), child_ident.span());
+            let (list_page_methods, list_page_impls) =
+                gen_child_page_methods(model, child_ident, &plural_snake, &child_manager_ident);
+            let (filter_methods, filter_impls) =
+                gen_child_filter_methods(model, child_ident, &plural_snake, &child_manager_ident);
             let batch_delete_all_fn =
                 Ident::new(&format!("batch_delete_all_{}", plural_snake), child_ident.span());
 
@@ -323,6 +387,8 @@ fn gen_phantom_item(phantom: &PhantomDef) -> TokenStream {
                     async fn #delete_fn(&self, ctx: __ctx!(), key: &str) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
                     async fn #batch_delete_fn(&self, ctx: __ctx!(), keys: ::std::vec::Vec<&str>) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
                     async fn #list_fn(&self, ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError>;
+                    #list_page_methods
+                    #filter_methods
                     async fn #batch_delete_all_fn(&self, ctx: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
                 },
                 quote! {
@@ -330,9 +396,11 @@ fn gen_phantom_item(phantom: &PhantomDef) -> TokenStream {
                         ctx.$ctx_repo_accessor().await?.#child_manager_ident().find(self, key).await
                     }
                     async fn #set_fn(&self, ctx: __ctx!(), data: #child_data_ident) -> ::std::result::Result<#child_ident, ::fractic_server_error::ServerError> {
+                        #child_data_check
                         ctx.$ctx_repo_accessor().await?.#child_manager_ident().set(self, data).await
                     }
                     async fn #batch_set_fn(&self, ctx: __ctx!(), data: ::std::vec::Vec<#child_data_ident>) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError> {
+                        #child_data_checks
                         ctx.$ctx_repo_accessor().await?.#child_manager_ident().batch_set(self, data).await
                     }
                     async fn #delete_fn(&self, ctx: __ctx!(), key: &str) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
@@ -344,6 +412,8 @@ fn gen_phantom_item(phantom: &PhantomDef) -> TokenStream {
                     async fn #list_fn(&self, ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError> {
                         ctx.$ctx_repo_accessor().await?.#child_manager_ident().query_all(self).await
                     }
+                    #list_page_impls
+                    #filter_impls
                     async fn #batch_delete_all_fn(&self, ctx: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
                         ctx.$ctx_repo_accessor().await?.#child_manager_ident().batch_delete_all(self).await
                     }
@@ -354,6 +424,17 @@ fn gen_phantom_item(phantom: &PhantomDef) -> TokenStream {
 
     let trait_ident = Ident::new(&format!("{}Crud", ty_ident), ty_ident.span());
 
+    let child_impls = instrument_methods(
+        ty_ident,
+        SelfRole::Parent,
+        quote! {
+            #(#ordered_child_impls)*
+            #(#unordered_child_impls)*
+            #(#batch_impls)*
+            #(#singleton_child_impls)*
+            #(#indexed_singleton_child_impls)*
+        },
+    );
     quote! {
         pub trait #trait_ident {
             #(#ordered_child_methods)*
@@ -363,19 +444,24 @@ fn gen_phantom_item(phantom: &PhantomDef) -> TokenStream {
             #(#indexed_singleton_child_methods)*
         }
         impl #trait_ident for #ty_ident {
-            #(#ordered_child_impls)*
-            #(#unordered_child_impls)*
-            #(#batch_impls)*
-            #(#singleton_child_impls)*
-            #(#indexed_singleton_child_impls)*
+            #child_impls
         }
     }
 }
 
-fn gen_root_standard_item(root: &StandardDef, is_ordered: bool) -> TokenStream {
+fn gen_root_standard_item(
+    root: &StandardDef,
+    is_ordered: bool,
+    model: &ConfigModel,
+) -> TokenStream {
     let ty_ident = &root.name;
     let ty_data_ident = dynamo_data_type(ty_ident);
-    let manager_ident = method_ident_for("manage", &root.name);
+    let ty_data_check = gen_data_validation(model, ty_ident, quote! { &data }, false);
+    let ty_data_checks = gen_data_validation(model, ty_ident, quote! { data.iter() }, true);
+    let manager_ident = manager_ident_for(&root.name, model.is_intercepted(&root.name));
+    let update_impl = gen_update_impl(model, root, &manager_ident);
+    let (filter_methods, filter_impls) = gen_root_filter_methods(model, ty_ident, &manager_ident);
+    let (page_methods, page_impls) = gen_root_page_methods(model, ty_ident, &manager_ident);
 
     let (basic_methods, basic_impls) = (
         quote! {
@@ -383,6 +469,8 @@ fn gen_root_standard_item(root: &StandardDef, is_ordered: bool) -> TokenStream {
             async fn get(ctx: __ctx!(), id: ::fractic_aws_dynamo::schema::PkSk) -> ::std::result::Result<#ty_ident, ::fractic_server_error::ServerError>;
             async fn update(&self, ctx: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
             async fn list(ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError>;
+            #page_methods
+            #filter_methods
         },
         quote! {
             async fn find(ctx: __ctx!(), id: ::fractic_aws_dynamo::schema::PkSk) -> ::std::result::Result<::std::option::Option<#ty_ident>, ::fractic_server_error::ServerError> {
@@ -391,12 +479,12 @@ fn gen_root_standard_item(root: &StandardDef, is_ordered: bool) -> TokenStream {
             async fn get(ctx: __ctx!(), id: ::fractic_aws_dynamo::schema::PkSk) -> ::std::result::Result<#ty_ident, ::fractic_server_error::ServerError> {
                 ctx.$ctx_repo_accessor().await?.#manager_ident().get(id).await
             }
-            async fn update(&self, ctx: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
-                ctx.$ctx_repo_accessor().await?.#manager_ident().update(self).await
-            }
+            #update_impl
             async fn list(ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError> {
                 ctx.$ctx_repo_accessor().await?.#manager_ident().query_all().await
             }
+            #page_impls
+            #filter_impls
         },
     );
 
@@ -408,9 +496,11 @@ fn gen_root_standard_item(root: &StandardDef, is_ordered: bool) -> TokenStream {
             },
             quote! {
                 async fn add(ctx: __ctx!(), data: #ty_data_ident, after: ::std::option::Option<& #ty_ident>) -> ::std::result::Result<#ty_ident, ::fractic_server_error::ServerError> {
+                    #ty_data_check
                     ctx.$ctx_repo_accessor().await?.#manager_ident().add(data, after).await
                 }
                 async fn batch_add(ctx: __ctx!(), data: ::std::vec::Vec<#ty_data_ident>, after: ::std::option::Option<& #ty_ident>) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError> {
+                    #ty_data_checks
                     ctx.$ctx_repo_accessor().await?.#manager_ident().batch_add(data, after).await
                 }
             },
@@ -423,15 +513,56 @@ fn gen_root_standard_item(root: &StandardDef, is_ordered: bool) -> TokenStream {
             },
             quote! {
                 async fn add(ctx: __ctx!(), data: #ty_data_ident) -> ::std::result::Result<#ty_ident, ::fractic_server_error::ServerError> {
+                    #ty_data_check
                     ctx.$ctx_repo_accessor().await?.#manager_ident().add(data).await
                 }
                 async fn batch_add(ctx: __ctx!(), data: ::std::vec::Vec<#ty_data_ident>) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError> {
+                    #ty_data_checks
                     ctx.$ctx_repo_accessor().await?.#manager_ident().batch_add(data).await
                 }
             },
         )
     };
 
+    // Soft-deleted objects are tombstoned rather than removed, so the regular
+    // delete methods are routed to the manager's soft delete operations (which
+    // also tombstone any descendants), and restore / list-deleted methods are
+    // added. The `_DANGEROUS` non-recursive variants remain permanent deletes.
+    let (delete_recursive_call, delete_call, batch_delete_call, batch_delete_all_call) =
+        if root.is_soft_delete {
+            (
+                quote! { soft_delete },
+                quote! { soft_delete },
+                quote! { batch_soft_delete },
+                quote! { batch_soft_delete_all },
+            )
+        } else {
+            (
+                quote! { delete_recursive },
+                quote! { delete },
+                quote! { batch_delete },
+                quote! { batch_delete_all },
+            )
+        };
+    let (soft_delete_methods, soft_delete_impls) = if root.is_soft_delete {
+        (
+            quote! {
+                async fn restore(self, ctx: __ctx!()) -> ::std::result::Result<#ty_ident, ::fractic_server_error::ServerError>;
+                async fn list_deleted(ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError>;
+            },
+            quote! {
+                async fn restore(self, ctx: __ctx!()) -> ::std::result::Result<#ty_ident, ::fractic_server_error::ServerError> {
+                    ctx.$ctx_repo_accessor().await?.#manager_ident().restore(self).await
+                }
+                async fn list_deleted(ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError> {
+                    ctx.$ctx_repo_accessor().await?.#manager_ident().query_deleted().await
+                }
+            },
+        )
+    } else {
+        (TokenStream::new(), TokenStream::new())
+    };
+
     let (delete_methods, delete_impls) = if root.has_children() {
         (
             quote! {
@@ -445,7 +576,7 @@ fn gen_root_standard_item(root: &StandardDef, is_ordered: bool) -> TokenStream {
             },
             quote! {
                 async fn delete_recursive(self, ctx: __ctx!()) -> ::std::result::Result<#ty_data_ident, ::fractic_server_error::ServerError> {
-                    ctx.$ctx_repo_accessor().await?.#manager_ident().delete_recursive(self).await
+                    ctx.$ctx_repo_accessor().await?.#manager_ident().#delete_recursive_call(self).await
                 }
                 #[allow(non_snake_case)]
                 async fn delete_non_recursive_DANGEROUS(self, ctx: __ctx!()) -> ::std::result::Result<#ty_data_ident, ::fractic_server_error::ServerError> {
@@ -470,23 +601,31 @@ fn gen_root_standard_item(root: &StandardDef, is_ordered: bool) -> TokenStream {
             },
             quote! {
                 async fn delete(self, ctx: __ctx!()) -> ::std::result::Result<#ty_data_ident, ::fractic_server_error::ServerError> {
-                    ctx.$ctx_repo_accessor().await?.#manager_ident().delete(self).await
+                    ctx.$ctx_repo_accessor().await?.#manager_ident().#delete_call(self).await
                 }
                 async fn batch_delete(ctx: __ctx!(), items: ::std::vec::Vec<#ty_ident>) -> ::std::result::Result<::std::vec::Vec<#ty_data_ident>, ::fractic_server_error::ServerError> {
-                    ctx.$ctx_repo_accessor().await?.#manager_ident().batch_delete(items).await
+                    ctx.$ctx_repo_accessor().await?.#manager_ident().#batch_delete_call(items).await
                 }
                 async fn batch_delete_all(ctx: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
-                    ctx.$ctx_repo_accessor().await?.#manager_ident().batch_delete_all().await
+                    ctx.$ctx_repo_accessor().await?.#manager_ident().#batch_delete_all_call().await
                 }
             },
         )
     };
 
+    let (archive_methods, archive_impls) = if root.is_movable_archive {
+        gen_movable_archive_methods(model, ty_ident)
+    } else {
+        (TokenStream::new(), TokenStream::new())
+    };
+
     let (ordered_child_methods, ordered_child_impls) =
         root.ordered_children.iter().map(|child_name| {
             let child_ident = child_name;
             let child_data_ident = dynamo_data_type(child_ident);
-            let child_manager_ident = method_ident_for("manage", child_ident);
+            let child_data_check = gen_data_validation(model, child_ident, quote! { &data }, false);
+            let child_data_checks = gen_data_validation(model, child_ident, quote! { data.iter() }, true);
+            let child_manager_ident = manager_ident_for(child_ident, model.is_intercepted(child_ident));
             let base_pascal = stripped_pascal(ty_ident, child_ident);
             let child_singular_snake = to_snake_case(&base_pascal);
             let child_plural_snake = to_snake_case(&pluralize_pascal(&base_pascal));
@@ -495,22 +634,36 @@ fn gen_root_standard_item(root: &StandardDef, is_ordered: bool) -> TokenStream {
                 Ident::new(&format!("batch_add_{}", child_plural_snake), child_ident.span());
             let list_children_fn =
                 Ident::new(&format!("list_{}", child_plural_snake), child_ident.span());
+            let (list_children_page_methods, list_children_page_impls) =
+                gen_child_page_methods(model, child_ident, &child_plural_snake, &child_manager_ident);
+            let (filter_methods, filter_impls) =
+                gen_child_filter_methods(model, child_ident, &child_plural_snake, &child_manager_ident);
+            let (deleted_methods, deleted_impls) =
+                gen_child_soft_delete_methods(model, child_ident, &child_plural_snake, &child_manager_ident);
             (
                 quote! {
                     async fn #add_child_fn(&self, ctx: __ctx!(), data: #child_data_ident, after: ::std::option::Option<& #child_ident>) -> ::std::result::Result<#child_ident, ::fractic_server_error::ServerError>;
                     async fn #batch_add_children_fn(&self, ctx: __ctx!(), data: ::std::vec::Vec<#child_data_ident>, after: ::std::option::Option<& #child_ident>) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError>;
                     async fn #list_children_fn(&self, ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError>;
+                    #list_children_page_methods
+                    #filter_methods
+                    #deleted_methods
                 },
                 quote! {
                     async fn #add_child_fn(&self, ctx: __ctx!(), data: #child_data_ident, after: ::std::option::Option<& #child_ident>) -> ::std::result::Result<#child_ident, ::fractic_server_error::ServerError> {
+                        #child_data_check
                         ctx.$ctx_repo_accessor().await?.#child_manager_ident().add(self, data, after).await
                     }
                     async fn #batch_add_children_fn(&self, ctx: __ctx!(), data: ::std::vec::Vec<#child_data_ident>, after: ::std::option::Option<& #child_ident>) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError> {
+                        #child_data_checks
                         ctx.$ctx_repo_accessor().await?.#child_manager_ident().batch_add(self, data, after).await
                     }
                     async fn #list_children_fn(&self, ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError> {
                         ctx.$ctx_repo_accessor().await?.#child_manager_ident().query_all(self).await
                     }
+                    #list_children_page_impls
+                    #filter_impls
+                    #deleted_impls
                 },
             )
         }).unzip::<TokenStream, TokenStream, Vec<_>, Vec<_>>();
@@ -519,7 +672,9 @@ fn gen_root_standard_item(root: &StandardDef, is_ordered: bool) -> TokenStream {
         root.unordered_children.iter().map(|child_name| {
             let child_ident = child_name;
             let child_data_ident = dynamo_data_type(child_ident);
-            let child_manager_ident = method_ident_for("manage", child_ident);
+            let child_data_check = gen_data_validation(model, child_ident, quote! { &data }, false);
+            let child_data_checks = gen_data_validation(model, child_ident, quote! { data.iter() }, true);
+            let child_manager_ident = manager_ident_for(child_ident, model.is_intercepted(child_ident));
             let base_pascal = stripped_pascal(ty_ident, child_ident);
             let child_singular_snake = to_snake_case(&base_pascal);
             let child_plural_snake = to_snake_case(&pluralize_pascal(&base_pascal));
@@ -528,22 +683,36 @@ fn gen_root_standard_item(root: &StandardDef, is_ordered: bool) -> TokenStream {
                 Ident::new(&format!("batch_add_{}", child_plural_snake), child_ident.span());
             let list_children_fn =
                 Ident::new(&format!("list_{}", child_plural_snake), child_ident.span());
+            let (list_children_page_methods, list_children_page_impls) =
+                gen_child_page_methods(model, child_ident, &child_plural_snake, &child_manager_ident);
+            let (filter_methods, filter_impls) =
+                gen_child_filter_methods(model, child_ident, &child_plural_snake, &child_manager_ident);
+            let (deleted_methods, deleted_impls) =
+                gen_child_soft_delete_methods(model, child_ident, &child_plural_snake, &child_manager_ident);
             (
                 quote! {
                     async fn #add_child_fn(&self, ctx: __ctx!(), data: #child_data_ident) -> ::std::result::Result<#child_ident, ::fractic_server_error::ServerError>;
                     async fn #batch_add_children_fn(&self, ctx: __ctx!(), data: ::std::vec::Vec<#child_data_ident>) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError>;
                     async fn #list_children_fn(&self, ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError>;
+                    #list_children_page_methods
+                    #filter_methods
+                    #deleted_methods
                 },
                 quote! {
                     async fn #add_child_fn(&self, ctx: __ctx!(), data: #child_data_ident) -> ::std::result::Result<#child_ident, ::fractic_server_error::ServerError> {
+                        #child_data_check
                         ctx.$ctx_repo_accessor().await?.#child_manager_ident().add(self, data).await
                     }
                     async fn #batch_add_children_fn(&self, ctx: __ctx!(), data: ::std::vec::Vec<#child_data_ident>) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError> {
+                        #child_data_checks
                         ctx.$ctx_repo_accessor().await?.#child_manager_ident().batch_add(self, data).await
                     }
                     async fn #list_children_fn(&self, ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError> {
                         ctx.$ctx_repo_accessor().await?.#child_manager_ident().query_all(self).await
                     }
+                    #list_children_page_impls
+                    #filter_impls
+                    #deleted_impls
                 },
             )
         }).unzip::<TokenStream, TokenStream, Vec<_>, Vec<_>>();
@@ -551,16 +720,23 @@ fn gen_root_standard_item(root: &StandardDef, is_ordered: bool) -> TokenStream {
     let (batch_methods, batch_impls) = root.batch_children.iter().map(|batch_name| {
         let batch_ident = batch_name;
         let batch_data_ident = dynamo_data_type(batch_ident);
-        let batch_manager_ident = method_ident_for("manage", batch_ident);
+        let batch_data_checks = gen_data_validation(model, batch_ident, quote! { data.iter() }, true);
+        let batch_manager_ident = manager_ident_for(batch_ident, model.is_intercepted(batch_ident));
         let base_pascal = stripped_pascal(ty_ident, batch_ident);
         let plural_snake = to_snake_case(&pluralize_pascal(&base_pascal));
         let list_fn = Ident::new(&format!("list_{}", plural_snake), batch_ident.span());
+        let (list_page_methods, list_page_impls) =
+                gen_child_page_methods(model, batch_ident, &plural_snake, &batch_manager_ident);
+        let (filter_methods, filter_impls) =
+            gen_child_filter_methods(model, batch_ident, &plural_snake, &batch_manager_ident);
         let del_all_fn = Ident::new(&format!("batch_delete_all_{}", plural_snake), batch_ident.span());
         let replace_all_fn =
             Ident::new(&format!("batch_replace_all_{}", plural_snake), batch_ident.span());
         (
             quote! {
                 async fn #list_fn(&self, ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#batch_ident>, ::fractic_server_error::ServerError>;
+                #list_page_methods
+                #filter_methods
                 async fn #del_all_fn(&self, ctx: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
                 async fn #replace_all_fn(&self, ctx: __ctx!(), data: ::std::vec::Vec<#batch_data_ident>) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
             },
@@ -568,10 +744,13 @@ fn gen_root_standard_item(root: &StandardDef, is_ordered: bool) -> TokenStream {
                 async fn #list_fn(&self, ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#batch_ident>, ::fractic_server_error::ServerError> {
                     ctx.$ctx_repo_accessor().await?.#batch_manager_ident().query_all(self).await
                 }
+                #list_page_impls
+                #filter_impls
                 async fn #del_all_fn(&self, ctx: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
                     ctx.$ctx_repo_accessor().await?.#batch_manager_ident().batch_delete_all(self).await
                 }
                 async fn #replace_all_fn(&self, ctx: __ctx!(), data: ::std::vec::Vec<#batch_data_ident>) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+                    #batch_data_checks
                     ctx.$ctx_repo_accessor().await?.#batch_manager_ident().batch_replace_all_ordered(self, data).await
                 }
             },
@@ -584,7 +763,8 @@ fn gen_root_standard_item(root: &StandardDef, is_ordered: bool) -> TokenStream {
         .map(|child_name| {
             let child_ident = child_name;
             let child_data_ident = dynamo_data_type(child_ident);
-            let child_manager_ident = method_ident_for("manage", child_ident);
+            let child_data_check = gen_data_validation(model, child_ident, quote! { &data }, false);
+            let child_manager_ident = manager_ident_for(child_ident, model.is_intercepted(child_ident));
             let base_pascal = stripped_pascal(ty_ident, child_ident);
             let singular_snake = to_snake_case(&base_pascal);
             let get_fn = Ident::new(&format!("get_{}", singular_snake), child_ident.span());
@@ -601,6 +781,7 @@ fn gen_root_standard_item(root: &StandardDef, is_ordered: bool) -> TokenStream {
                         ctx.$ctx_repo_accessor().await?.#child_manager_ident().find(self).await
                     }
                     async fn #set_fn(&self, ctx: __ctx!(), data: #child_data_ident) -> ::std::result::Result<#child_ident, ::fractic_server_error::ServerError> {
+                        #child_data_check
                         ctx.$ctx_repo_accessor().await?.#child_manager_ident().set(self, data).await
                     }
                     async fn #delete_fn(&self, ctx: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
@@ -617,7 +798,9 @@ fn gen_root_standard_item(root: &StandardDef, is_ordered: bool) -> TokenStream {
         .map(|child_name| {
             let child_ident = child_name;
             let child_data_ident = dynamo_data_type(child_ident);
-            let child_manager_ident = method_ident_for("manage", child_ident);
+            let child_data_check = gen_data_validation(model, child_ident, quote! { &data }, false);
+            let child_data_checks = gen_data_validation(model, child_ident, quote! { data.iter() }, true);
+            let child_manager_ident = manager_ident_for(child_ident, model.is_intercepted(child_ident));
             let base_pascal = stripped_pascal(ty_ident, child_ident);
             let singular_snake = to_snake_case(&base_pascal);
             let plural_snake = to_snake_case(&pluralize_pascal(&base_pascal));
@@ -629,6 +812,10 @@ fn gen_root_standard_item(root: &StandardDef, is_ordered: bool) -> TokenStream {
             let batch_delete_fn =
                 Ident::new(&format!("batch_delete_{}", plural_snake), child_ident.span());
             let list_fn = Ident::new(&format!("list_{}", plural_snake), child_ident.span());
+            let (list_page_methods, list_page_impls) =
+                gen_child_page_methods(model, child_ident, &plural_snake, &child_manager_ident);
+            let (filter_methods, filter_impls) =
+                gen_child_filter_methods(model, child_ident, &plural_snake, &child_manager_ident);
             let batch_delete_all_fn =
                 Ident::new(&format!("batch_delete_all_{}", plural_snake), child_ident.span());
 
@@ -640,6 +827,8 @@ fn gen_root_standard_item(root: &StandardDef, is_ordered: bool) -> TokenStream {
                     async fn #delete_fn(&self, ctx: __ctx!(), key: &str) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
                     async fn #batch_delete_fn(&self, ctx: __ctx!(), keys: ::std::vec::Vec<&str>) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
                     async fn #list_fn(&self, ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError>;
+                    #list_page_methods
+                    #filter_methods
                     async fn #batch_delete_all_fn(&self, ctx: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
                 },
                 quote! {
@@ -647,9 +836,11 @@ fn gen_root_standard_item(root: &StandardDef, is_ordered: bool) -> TokenStream {
                         ctx.$ctx_repo_accessor().await?.#child_manager_ident().find(self, key).await
                     }
                     async fn #set_fn(&self, ctx: __ctx!(), data: #child_data_ident) -> ::std::result::Result<#child_ident, ::fractic_server_error::ServerError> {
+                        #child_data_check
                         ctx.$ctx_repo_accessor().await?.#child_manager_ident().set(self, data).await
                     }
                     async fn #batch_set_fn(&self, ctx: __ctx!(), data: ::std::vec::Vec<#child_data_ident>) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError> {
+                        #child_data_checks
                         ctx.$ctx_repo_accessor().await?.#child_manager_ident().batch_set(self, data).await
                     }
                     async fn #delete_fn(&self, ctx: __ctx!(), key: &str) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
@@ -661,6 +852,8 @@ fn gen_root_standard_item(root: &StandardDef, is_ordered: bool) -> TokenStream {
                     async fn #list_fn(&self, ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError> {
                         ctx.$ctx_repo_accessor().await?.#child_manager_ident().query_all(self).await
                     }
+                    #list_page_impls
+                    #filter_impls
                     async fn #batch_delete_all_fn(&self, ctx: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
                         ctx.$ctx_repo_accessor().await?.#child_manager_ident().batch_delete_all(self).await
                     }
@@ -671,11 +864,35 @@ fn gen_root_standard_item(root: &StandardDef, is_ordered: bool) -> TokenStream {
 
     let trait_ident = Ident::new(&format!("{}Crud", ty_ident), ty_ident.span());
 
+    let item_impls = instrument_methods(
+        ty_ident,
+        SelfRole::Item,
+        quote! {
+            #basic_impls
+            #add_impls
+            #delete_impls
+            #soft_delete_impls
+            #archive_impls
+        },
+    );
+    let child_impls = instrument_methods(
+        ty_ident,
+        SelfRole::Parent,
+        quote! {
+            #(#ordered_child_impls)*
+            #(#unordered_child_impls)*
+            #(#batch_impls)*
+            #(#singleton_child_impls)*
+            #(#indexed_singleton_child_impls)*
+        },
+    );
     quote! {
         pub trait #trait_ident {
             #basic_methods
             #add_methods
             #delete_methods
+            #soft_delete_methods
+            #archive_methods
             #(#ordered_child_methods)*
             #(#unordered_child_methods)*
             #(#batch_methods)*
@@ -683,26 +900,25 @@ fn gen_root_standard_item(root: &StandardDef, is_ordered: bool) -> TokenStream {
             #(#indexed_singleton_child_methods)*
         }
         impl #trait_ident for #ty_ident {
-            #basic_impls
-            #add_impls
-            #delete_impls
-            #(#ordered_child_impls)*
-            #(#unordered_child_impls)*
-            #(#batch_impls)*
-            #(#singleton_child_impls)*
-            #(#indexed_singleton_child_impls)*
+            #item_impls
+            #child_impls
         }
     }
 }
 
-fn gen_root_batch_item(batch: &BatchDef) -> TokenStream {
+fn gen_root_batch_item(batch: &BatchDef, model: &ConfigModel) -> TokenStream {
     let ty_ident = &batch.name;
     let ty_data_ident = dynamo_data_type(ty_ident);
-    let manager_ident = method_ident_for("manage", ty_ident);
+    let ty_data_checks = gen_data_validation(model, ty_ident, quote! { data.iter() }, true);
+    let manager_ident = manager_ident_for(ty_ident, model.is_intercepted(ty_ident));
     let trait_ident = Ident::new(&format!("{}Crud", ty_ident), ty_ident.span());
+    let (filter_methods, filter_impls) = gen_root_filter_methods(model, ty_ident, &manager_ident);
+    let (page_methods, page_impls) = gen_root_page_methods(model, ty_ident, &manager_ident);
 
     let methods = quote! {
         async fn list(ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError>;
+        #page_methods
+        #filter_methods
         async fn batch_delete_all(ctx: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
         async fn batch_replace_all(ctx: __ctx!(), data: ::std::vec::Vec<#ty_data_ident>) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
     };
@@ -710,14 +926,18 @@ fn gen_root_batch_item(batch: &BatchDef) -> TokenStream {
         async fn list(ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError> {
             ctx.$ctx_repo_accessor().await?.#manager_ident().query_all().await
         }
+        #page_impls
+        #filter_impls
         async fn batch_delete_all(ctx: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
             ctx.$ctx_repo_accessor().await?.#manager_ident().batch_delete_all().await
         }
         async fn batch_replace_all(ctx: __ctx!(), data: ::std::vec::Vec<#ty_data_ident>) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+            #ty_data_checks
             ctx.$ctx_repo_accessor().await?.#manager_ident().batch_replace_all_ordered(data).await
         }
     };
 
+    let impls = instrument_methods(ty_ident, SelfRole::Item, impls);
     quote! {
         pub trait #trait_ident {
             #methods
@@ -728,15 +948,23 @@ fn gen_root_batch_item(batch: &BatchDef) -> TokenStream {
     }
 }
 
-fn gen_child_batch_item(batch: &BatchDef, parent_ident: &Ident) -> TokenStream {
+fn gen_child_batch_item(
+    batch: &BatchDef,
+    parent_ident: &Ident,
+    model: &ConfigModel,
+) -> TokenStream {
     let ty_ident = &batch.name;
     let ty_data_ident = dynamo_data_type(ty_ident);
+    let ty_data_checks = gen_data_validation(model, ty_ident, quote! { data.iter() }, true);
     let parent_data_ident = dynamo_data_type(parent_ident);
-    let manager_ident = method_ident_for("manage", ty_ident);
+    let manager_ident = manager_ident_for(ty_ident, model.is_intercepted(ty_ident));
     let trait_ident = Ident::new(&format!("{}Crud", ty_ident), ty_ident.span());
+    let (page_methods, page_impls) =
+        gen_unchecked_page_methods(model, ty_ident, parent_ident, &manager_ident);
 
     let methods = quote! {
         async fn unchecked_list(ctx: __ctx!(), parent_id: ::fractic_aws_dynamo::schema::PkSk) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError>;
+        #page_methods
         async fn unchecked_batch_delete_all(ctx: __ctx!(), parent_id: ::fractic_aws_dynamo::schema::PkSk) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
         async fn unchecked_batch_replace_all(ctx: __ctx!(), parent_id: ::fractic_aws_dynamo::schema::PkSk, data: ::std::vec::Vec<#ty_data_ident>) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
     };
@@ -749,6 +977,7 @@ fn gen_child_batch_item(batch: &BatchDef, parent_ident: &Ident) -> TokenStream {
             };
             ctx.$ctx_repo_accessor().await?.#manager_ident().query_all(&tmp_dummy).await
         }
+        #page_impls
         async fn unchecked_batch_delete_all(ctx: __ctx!(), parent_id: ::fractic_aws_dynamo::schema::PkSk) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
             let tmp_dummy = #parent_ident {
                 id: parent_id,
@@ -758,6 +987,7 @@ fn gen_child_batch_item(batch: &BatchDef, parent_ident: &Ident) -> TokenStream {
             ctx.$ctx_repo_accessor().await?.#manager_ident().batch_delete_all(&tmp_dummy).await
         }
         async fn unchecked_batch_replace_all(ctx: __ctx!(), parent_id: ::fractic_aws_dynamo::schema::PkSk, data: ::std::vec::Vec<#ty_data_ident>) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+            #ty_data_checks
             let tmp_dummy = #parent_ident {
                 id: parent_id,
                 data: #parent_data_ident::default(),
@@ -767,6 +997,7 @@ fn gen_child_batch_item(batch: &BatchDef, parent_ident: &Ident) -> TokenStream {
         }
     };
 
+    let impls = instrument_methods(ty_ident, SelfRole::Item, impls);
     quote! {
         pub trait #trait_ident {
             #methods
@@ -777,10 +1008,11 @@ fn gen_child_batch_item(batch: &BatchDef, parent_ident: &Ident) -> TokenStream {
     }
 }
 
-fn gen_root_singleton_item(singleton: &SingletonDef) -> TokenStream {
+fn gen_root_singleton_item(singleton: &SingletonDef, model: &ConfigModel) -> TokenStream {
     let ty_ident = &singleton.name;
     let ty_data_ident = dynamo_data_type(ty_ident);
-    let manager_ident = method_ident_for("manage", ty_ident);
+    let ty_data_check = gen_data_validation(model, ty_ident, quote! { &data }, false);
+    let manager_ident = manager_ident_for(ty_ident, model.is_intercepted(ty_ident));
     let trait_ident = Ident::new(&format!("{}Crud", ty_ident), ty_ident.span());
 
     let methods = quote! {
@@ -793,6 +1025,7 @@ fn gen_root_singleton_item(singleton: &SingletonDef) -> TokenStream {
             ctx.$ctx_repo_accessor().await?.#manager_ident().find().await
         }
         async fn set(ctx: __ctx!(), data: #ty_data_ident) -> ::std::result::Result<#ty_ident, ::fractic_server_error::ServerError> {
+            #ty_data_check
             ctx.$ctx_repo_accessor().await?.#manager_ident().set(data).await
         }
         async fn delete(ctx: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
@@ -800,6 +1033,7 @@ fn gen_root_singleton_item(singleton: &SingletonDef) -> TokenStream {
         }
     };
 
+    let impls = instrument_methods(ty_ident, SelfRole::Item, impls);
     quote! {
         pub trait #trait_ident {
             #methods
@@ -810,11 +1044,18 @@ fn gen_root_singleton_item(singleton: &SingletonDef) -> TokenStream {
     }
 }
 
-fn gen_root_indexed_singleton_item(indexed_singleton: &IndexedSingletonDef) -> TokenStream {
+fn gen_root_indexed_singleton_item(
+    indexed_singleton: &IndexedSingletonDef,
+    model: &ConfigModel,
+) -> TokenStream {
     let ty_ident = &indexed_singleton.name;
     let ty_data_ident = dynamo_data_type(ty_ident);
-    let manager_ident = method_ident_for("manage", ty_ident);
+    let ty_data_check = gen_data_validation(model, ty_ident, quote! { &data }, false);
+    let ty_data_checks = gen_data_validation(model, ty_ident, quote! { data.iter() }, true);
+    let manager_ident = manager_ident_for(ty_ident, model.is_intercepted(ty_ident));
     let trait_ident = Ident::new(&format!("{}Crud", ty_ident), ty_ident.span());
+    let (filter_methods, filter_impls) = gen_root_filter_methods(model, ty_ident, &manager_ident);
+    let (page_methods, page_impls) = gen_root_page_methods(model, ty_ident, &manager_ident);
 
     let methods = quote! {
         async fn get(ctx: __ctx!(), key: &str) -> ::std::result::Result<::std::option::Option<#ty_ident>, ::fractic_server_error::ServerError>;
@@ -823,6 +1064,8 @@ fn gen_root_indexed_singleton_item(indexed_singleton: &IndexedSingletonDef) -> T
         async fn delete(ctx: __ctx!(), key: &str) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
         async fn batch_delete(ctx: __ctx!(), keys: ::std::vec::Vec<&str>) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
         async fn list(ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError>;
+        #page_methods
+        #filter_methods
         async fn batch_delete_all(ctx: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
     };
     let impls = quote! {
@@ -830,9 +1073,11 @@ fn gen_root_indexed_singleton_item(indexed_singleton: &IndexedSingletonDef) -> T
             ctx.$ctx_repo_accessor().await?.#manager_ident().find(key).await
         }
         async fn set(ctx: __ctx!(), data: #ty_data_ident) -> ::std::result::Result<#ty_ident, ::fractic_server_error::ServerError> {
+            #ty_data_check
             ctx.$ctx_repo_accessor().await?.#manager_ident().set(data).await
         }
         async fn batch_set(ctx: __ctx!(), data: ::std::vec::Vec<#ty_data_ident>) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError> {
+            #ty_data_checks
             ctx.$ctx_repo_accessor().await?.#manager_ident().batch_set(data).await
         }
         async fn delete(ctx: __ctx!(), key: &str) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
@@ -844,11 +1089,14 @@ fn gen_root_indexed_singleton_item(indexed_singleton: &IndexedSingletonDef) -> T
         async fn list(ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError> {
             ctx.$ctx_repo_accessor().await?.#manager_ident().query_all().await
         }
+        #page_impls
+        #filter_impls
         async fn batch_delete_all(ctx: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
             ctx.$ctx_repo_accessor().await?.#manager_ident().batch_delete_all().await
         }
     };
 
+    let impls = instrument_methods(ty_ident, SelfRole::Item, impls);
     quote! {
         pub trait #trait_ident {
             #methods
@@ -863,11 +1111,17 @@ fn gen_child_standard_item(
     child: &StandardDef,
     parent_ident: &Ident,
     is_ordered: bool,
+    model: &ConfigModel,
 ) -> TokenStream {
     let ty_ident = &child.name;
     let ty_data_ident = dynamo_data_type(ty_ident);
+    let ty_data_check = gen_data_validation(model, ty_ident, quote! { &data }, false);
+    let ty_data_checks = gen_data_validation(model, ty_ident, quote! { data.iter() }, true);
     let parent_data_ident = dynamo_data_type(parent_ident);
-    let manager_ident = method_ident_for("manage", &child.name);
+    let manager_ident = manager_ident_for(&child.name, model.is_intercepted(&child.name));
+    let update_impl = gen_update_impl(model, child, &manager_ident);
+    let (page_methods, page_impls) =
+        gen_unchecked_page_methods(model, ty_ident, parent_ident, &manager_ident);
 
     let (basic_methods, basic_impls) = (
         quote! {
@@ -875,6 +1129,7 @@ fn gen_child_standard_item(
             async fn get(ctx: __ctx!(), id: ::fractic_aws_dynamo::schema::PkSk) -> ::std::result::Result<#ty_ident, ::fractic_server_error::ServerError>;
             async fn update(&self, ctx: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
                 async fn unchecked_list(ctx: __ctx!(), parent_id: ::fractic_aws_dynamo::schema::PkSk) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError>;
+                #page_methods
         },
         quote! {
             async fn find(ctx: __ctx!(), id: ::fractic_aws_dynamo::schema::PkSk) -> ::std::result::Result<::std::option::Option<#ty_ident>, ::fractic_server_error::ServerError> {
@@ -883,9 +1138,7 @@ fn gen_child_standard_item(
             async fn get(ctx: __ctx!(), id: ::fractic_aws_dynamo::schema::PkSk) -> ::std::result::Result<#ty_ident, ::fractic_server_error::ServerError> {
                 ctx.$ctx_repo_accessor().await?.#manager_ident().get(id).await
             }
-            async fn update(&self, ctx: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
-                ctx.$ctx_repo_accessor().await?.#manager_ident().update(self).await
-            }
+            #update_impl
                 async fn unchecked_list(ctx: __ctx!(), parent_id: ::fractic_aws_dynamo::schema::PkSk) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError> {
                     let tmp_dummy = #parent_ident {
                         id: parent_id,
@@ -894,6 +1147,7 @@ fn gen_child_standard_item(
                     };
                     ctx.$ctx_repo_accessor().await?.#manager_ident().query_all(&tmp_dummy).await
                 }
+                #page_impls
         },
     );
 
@@ -905,6 +1159,7 @@ fn gen_child_standard_item(
             },
             quote! {
                 async fn unchecked_add(ctx: __ctx!(), parent_id: ::fractic_aws_dynamo::schema::PkSk, data: #ty_data_ident, after: ::std::option::Option<& #ty_ident>) -> ::std::result::Result<#ty_ident, ::fractic_server_error::ServerError> {
+                    #ty_data_check
                     let tmp_dummy = #parent_ident {
                         id: parent_id,
                         data: #parent_data_ident::default(),
@@ -913,6 +1168,7 @@ fn gen_child_standard_item(
                     ctx.$ctx_repo_accessor().await?.#manager_ident().add(&tmp_dummy, data, after).await
                 }
                 async fn unchecked_batch_add(ctx: __ctx!(), parent_id: ::fractic_aws_dynamo::schema::PkSk, data: ::std::vec::Vec<#ty_data_ident>, after: ::std::option::Option<& #ty_ident>) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError> {
+                    #ty_data_checks
                     let tmp_dummy = #parent_ident {
                         id: parent_id,
                         data: #parent_data_ident::default(),
@@ -930,6 +1186,7 @@ fn gen_child_standard_item(
             },
             quote! {
                 async fn unchecked_add(ctx: __ctx!(), parent_id: ::fractic_aws_dynamo::schema::PkSk, data: #ty_data_ident) -> ::std::result::Result<#ty_ident, ::fractic_server_error::ServerError> {
+                    #ty_data_check
                     let tmp_dummy = #parent_ident {
                         id: parent_id,
                         data: #parent_data_ident::default(),
@@ -938,6 +1195,7 @@ fn gen_child_standard_item(
                     ctx.$ctx_repo_accessor().await?.#manager_ident().add(&tmp_dummy, data).await
                 }
                 async fn unchecked_batch_add(ctx: __ctx!(), parent_id: ::fractic_aws_dynamo::schema::PkSk, data: ::std::vec::Vec<#ty_data_ident>) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError> {
+                    #ty_data_checks
                     let tmp_dummy = #parent_ident {
                         id: parent_id,
                         data: #parent_data_ident::default(),
@@ -949,6 +1207,44 @@ fn gen_child_standard_item(
         )
     };
 
+    // Same soft delete routing as for root objects.
+    let (delete_recursive_call, delete_call, batch_delete_call) = if child.is_soft_delete {
+        (
+            quote! { soft_delete },
+            quote! { soft_delete },
+            quote! { batch_soft_delete },
+        )
+    } else {
+        (
+            quote! { delete_recursive },
+            quote! { delete },
+            quote! { batch_delete },
+        )
+    };
+    let (soft_delete_methods, soft_delete_impls) = if child.is_soft_delete {
+        (
+            quote! {
+                async fn restore(self, ctx: __ctx!()) -> ::std::result::Result<#ty_ident, ::fractic_server_error::ServerError>;
+                async fn unchecked_list_deleted(ctx: __ctx!(), parent_id: ::fractic_aws_dynamo::schema::PkSk) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError>;
+            },
+            quote! {
+                async fn restore(self, ctx: __ctx!()) -> ::std::result::Result<#ty_ident, ::fractic_server_error::ServerError> {
+                    ctx.$ctx_repo_accessor().await?.#manager_ident().restore(self).await
+                }
+                async fn unchecked_list_deleted(ctx: __ctx!(), parent_id: ::fractic_aws_dynamo::schema::PkSk) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError> {
+                    let tmp_dummy = #parent_ident {
+                        id: parent_id,
+                        data: #parent_data_ident::default(),
+                        auto_fields: ::fractic_aws_dynamo::schema::AutoFields::default(),
+                    };
+                    ctx.$ctx_repo_accessor().await?.#manager_ident().query_deleted(&tmp_dummy).await
+                }
+            },
+        )
+    } else {
+        (TokenStream::new(), TokenStream::new())
+    };
+
     let (delete_methods, delete_impls) = if child.has_children() {
         (
             quote! {
@@ -960,7 +1256,7 @@ fn gen_child_standard_item(
             },
             quote! {
                 async fn delete_recursive(self, ctx: __ctx!()) -> ::std::result::Result<#ty_data_ident, ::fractic_server_error::ServerError> {
-                    ctx.$ctx_repo_accessor().await?.#manager_ident().delete_recursive(self).await
+                    ctx.$ctx_repo_accessor().await?.#manager_ident().#delete_recursive_call(self).await
                 }
                 #[allow(non_snake_case)]
                 async fn delete_non_recursive_DANGEROUS(self, ctx: __ctx!()) -> ::std::result::Result<#ty_data_ident, ::fractic_server_error::ServerError> {
@@ -980,44 +1276,66 @@ fn gen_child_standard_item(
             },
             quote! {
                 async fn delete(self, ctx: __ctx!()) -> ::std::result::Result<#ty_data_ident, ::fractic_server_error::ServerError> {
-                    ctx.$ctx_repo_accessor().await?.#manager_ident().delete(self).await
+                    ctx.$ctx_repo_accessor().await?.#manager_ident().#delete_call(self).await
                 }
                 async fn batch_delete(ctx: __ctx!(), items: ::std::vec::Vec<#ty_ident>) -> ::std::result::Result<::std::vec::Vec<#ty_data_ident>, ::fractic_server_error::ServerError> {
-                    ctx.$ctx_repo_accessor().await?.#manager_ident().batch_delete(items).await
+                    ctx.$ctx_repo_accessor().await?.#manager_ident().#batch_delete_call(items).await
                 }
             },
         )
     };
 
+    let (archive_methods, archive_impls) = if child.is_movable_archive {
+        gen_movable_archive_methods(model, ty_ident)
+    } else {
+        (TokenStream::new(), TokenStream::new())
+    };
+
     let (ordered_grandchild_methods, ordered_grandchild_impls) = child
         .ordered_children
         .iter()
         .map(|grandchild| {
             let gc_ident = grandchild;
             let gc_data_ident = dynamo_data_type(gc_ident);
-            let gc_manager_ident = method_ident_for("manage", gc_ident);
+            let gc_data_check = gen_data_validation(model, gc_ident, quote! { &data }, false);
+            let gc_data_checks = gen_data_validation(model, gc_ident, quote! { data.iter() }, true);
+            let gc_manager_ident = manager_ident_for(gc_ident, model.is_intercepted(gc_ident));
             let base_pascal = stripped_pascal(ty_ident, gc_ident);
             let singular_snake = to_snake_case(&base_pascal);
             let plural_snake = to_snake_case(&pluralize_pascal(&base_pascal));
             let add_fn = Ident::new(&format!("add_{}", singular_snake), gc_ident.span());
             let batch_add_fn = Ident::new(&format!("batch_add_{}", plural_snake), gc_ident.span());
             let list_fn = Ident::new(&format!("list_{}", plural_snake), gc_ident.span());
+            let (list_page_methods, list_page_impls) =
+                gen_child_page_methods(model, gc_ident, &plural_snake, &gc_manager_ident);
+            let (filter_methods, filter_impls) =
+                gen_child_filter_methods(model, gc_ident, &plural_snake, &gc_manager_ident);
+            let (deleted_methods, deleted_impls) =
+                gen_child_soft_delete_methods(model, gc_ident, &plural_snake, &gc_manager_ident);
             (
                 quote! {
                     async fn #add_fn(&self, ctx: __ctx!(), data: #gc_data_ident, after: ::std::option::Option<& #gc_ident>) -> ::std::result::Result<#gc_ident, ::fractic_server_error::ServerError>;
                     async fn #batch_add_fn(&self, ctx: __ctx!(), data: ::std::vec::Vec<#gc_data_ident>, after: ::std::option::Option<& #gc_ident>) -> ::std::result::Result<::std::vec::Vec<#gc_ident>, ::fractic_server_error::ServerError>;
                     async fn #list_fn(&self, ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#gc_ident>, ::fractic_server_error::ServerError>;
+                    #list_page_methods
+                    #filter_methods
+                    #deleted_methods
                 },
                 quote! {
                     async fn #add_fn(&self, ctx: __ctx!(), data: #gc_data_ident, after: ::std::option::Option<& #gc_ident>) -> ::std::result::Result<#gc_ident, ::fractic_server_error::ServerError> {
+                        #gc_data_check
                         ctx.$ctx_repo_accessor().await?.#gc_manager_ident().add(self, data, after).await
                     }
                     async fn #batch_add_fn(&self, ctx: __ctx!(), data: ::std::vec::Vec<#gc_data_ident>, after: ::std::option::Option<& #gc_ident>) -> ::std::result::Result<::std::vec::Vec<#gc_ident>, ::fractic_server_error::ServerError> {
+                        #gc_data_checks
                         ctx.$ctx_repo_accessor().await?.#gc_manager_ident().batch_add(self, data, after).await
                     }
                     async fn #list_fn(&self, ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#gc_ident>, ::fractic_server_error::ServerError> {
                         ctx.$ctx_repo_accessor().await?.#gc_manager_ident().query_all(self).await
                     }
+                    #list_page_impls
+                    #filter_impls
+                    #deleted_impls
                 }
             )
         })
@@ -1029,29 +1347,45 @@ fn gen_child_standard_item(
         .map(|grandchild| {
             let gc_ident = grandchild;
             let gc_data_ident = dynamo_data_type(gc_ident);
-            let gc_manager_ident = method_ident_for("manage", gc_ident);
+            let gc_data_check = gen_data_validation(model, gc_ident, quote! { &data }, false);
+            let gc_data_checks = gen_data_validation(model, gc_ident, quote! { data.iter() }, true);
+            let gc_manager_ident = manager_ident_for(gc_ident, model.is_intercepted(gc_ident));
             let base_pascal = stripped_pascal(ty_ident, gc_ident);
             let singular_snake = to_snake_case(&base_pascal);
             let plural_snake = to_snake_case(&pluralize_pascal(&base_pascal));
             let add_fn = Ident::new(&format!("add_{}", singular_snake), gc_ident.span());
             let batch_add_fn = Ident::new(&format!("batch_add_{}", plural_snake), gc_ident.span());
             let list_fn = Ident::new(&format!("list_{}", plural_snake), gc_ident.span());
+            let (list_page_methods, list_page_impls) =
+                gen_child_page_methods(model, gc_ident, &plural_snake, &gc_manager_ident);
+            let (filter_methods, filter_impls) =
+                gen_child_filter_methods(model, gc_ident, &plural_snake, &gc_manager_ident);
+            let (deleted_methods, deleted_impls) =
+                gen_child_soft_delete_methods(model, gc_ident, &plural_snake, &gc_manager_ident);
             (
                 quote! {
                     async fn #add_fn(&self, ctx: __ctx!(), data: #gc_data_ident) -> ::std::result::Result<#gc_ident, ::fractic_server_error::ServerError>;
                     async fn #batch_add_fn(&self, ctx: __ctx!(), data: ::std::vec::Vec<#gc_data_ident>) -> ::std::result::Result<::std::vec::Vec<#gc_ident>, ::fractic_server_error::ServerError>;
                     async fn #list_fn(&self, ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#gc_ident>, ::fractic_server_error::ServerError>;
+                    #list_page_methods
+                    #filter_methods
+                    #deleted_methods
                 },
                 quote! {
                     async fn #add_fn(&self, ctx: __ctx!(), data: #gc_data_ident) -> ::std::result::Result<#gc_ident, ::fractic_server_error::ServerError> {
+                        #gc_data_check
                         ctx.$ctx_repo_accessor().await?.#gc_manager_ident().add(self, data).await
                     }
                     async fn #batch_add_fn(&self, ctx: __ctx!(), data: ::std::vec::Vec<#gc_data_ident>) -> ::std::result::Result<::std::vec::Vec<#gc_ident>, ::fractic_server_error::ServerError> {
+                        #gc_data_checks
                         ctx.$ctx_repo_accessor().await?.#gc_manager_ident().batch_add(self, data).await
                     }
                     async fn #list_fn(&self, ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#gc_ident>, ::fractic_server_error::ServerError> {
                         ctx.$ctx_repo_accessor().await?.#gc_manager_ident().query_all(self).await
                     }
+                    #list_page_impls
+                    #filter_impls
+                    #deleted_impls
                 }
             )
         })
@@ -1063,15 +1397,22 @@ fn gen_child_standard_item(
         .map(|batch| {
             let b_ident = batch;
             let b_data_ident = dynamo_data_type(b_ident);
-            let b_manager_ident = method_ident_for("manage", b_ident);
+            let b_data_checks = gen_data_validation(model, b_ident, quote! { data.iter() }, true);
+            let b_manager_ident = manager_ident_for(b_ident, model.is_intercepted(b_ident));
             let base_pascal = stripped_pascal(ty_ident, b_ident);
             let plural_snake = to_snake_case(&pluralize_pascal(&base_pascal));
             let list_fn = Ident::new(&format!("list_{}", plural_snake), b_ident.span());
+            let (list_page_methods, list_page_impls) =
+                gen_child_page_methods(model, b_ident, &plural_snake, &b_manager_ident);
+            let (filter_methods, filter_impls) =
+                gen_child_filter_methods(model, b_ident, &plural_snake, &b_manager_ident);
             let del_all_fn = Ident::new(&format!("batch_delete_all_{}", plural_snake), b_ident.span());
             let replace_all_fn = Ident::new(&format!("batch_replace_all_{}", plural_snake), b_ident.span());
             (
                 quote! {
                     async fn #list_fn(&self, ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#b_ident>, ::fractic_server_error::ServerError>;
+                    #list_page_methods
+                    #filter_methods
                     async fn #del_all_fn(&self, ctx: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
                     async fn #replace_all_fn(&self, ctx: __ctx!(), data: ::std::vec::Vec<#b_data_ident>) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
                 },
@@ -1079,10 +1420,13 @@ fn gen_child_standard_item(
                     async fn #list_fn(&self, ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#b_ident>, ::fractic_server_error::ServerError> {
                         ctx.$ctx_repo_accessor().await?.#b_manager_ident().query_all(self).await
                     }
+                    #list_page_impls
+                    #filter_impls
                     async fn #del_all_fn(&self, ctx: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
                         ctx.$ctx_repo_accessor().await?.#b_manager_ident().batch_delete_all(self).await
                     }
                     async fn #replace_all_fn(&self, ctx: __ctx!(), data: ::std::vec::Vec<#b_data_ident>) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+                        #b_data_checks
                         ctx.$ctx_repo_accessor().await?.#b_manager_ident().batch_replace_all_ordered(self, data).await
                     }
                 }
@@ -1096,7 +1440,8 @@ fn gen_child_standard_item(
         .map(|singleton_child| {
             let s_ident = singleton_child;
             let s_data_ident = dynamo_data_type(s_ident);
-            let s_manager_ident = method_ident_for("manage", s_ident);
+            let s_data_check = gen_data_validation(model, s_ident, quote! { &data }, false);
+            let s_manager_ident = manager_ident_for(s_ident, model.is_intercepted(s_ident));
             let base_pascal = stripped_pascal(ty_ident, s_ident);
             let singular_snake = to_snake_case(&base_pascal);
             let get_fn = Ident::new(&format!("get_{}", singular_snake), s_ident.span());
@@ -1113,6 +1458,7 @@ fn gen_child_standard_item(
                         ctx.$ctx_repo_accessor().await?.#s_manager_ident().find(self).await
                     }
                     async fn #set_fn(&self, ctx: __ctx!(), data: #s_data_ident) -> ::std::result::Result<#s_ident, ::fractic_server_error::ServerError> {
+                        #s_data_check
                         ctx.$ctx_repo_accessor().await?.#s_manager_ident().set(self, data).await
                     }
                     async fn #delete_fn(&self, ctx: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
@@ -1129,7 +1475,9 @@ fn gen_child_standard_item(
         .map(|indexed_singleton_child| {
             let s_ident = indexed_singleton_child;
             let s_data_ident = dynamo_data_type(s_ident);
-            let s_manager_ident = method_ident_for("manage", s_ident);
+            let s_data_check = gen_data_validation(model, s_ident, quote! { &data }, false);
+            let s_data_checks = gen_data_validation(model, s_ident, quote! { data.iter() }, true);
+            let s_manager_ident = manager_ident_for(s_ident, model.is_intercepted(s_ident));
             let base_pascal = stripped_pascal(ty_ident, s_ident);
             let singular_snake = to_snake_case(&base_pascal);
             let plural_snake = to_snake_case(&pluralize_pascal(&base_pascal));
@@ -1140,6 +1488,10 @@ fn gen_child_standard_item(
             let delete_fn = Ident::new(&format!("delete_{}", singular_snake), s_ident.span());
             let batch_delete_fn = Ident::new(&format!("batch_delete_{}", plural_snake), s_ident.span());
             let list_fn = Ident::new(&format!("list_{}", plural_snake), s_ident.span());
+            let (list_page_methods, list_page_impls) =
+                gen_child_page_methods(model, s_ident, &plural_snake, &s_manager_ident);
+            let (filter_methods, filter_impls) =
+                gen_child_filter_methods(model, s_ident, &plural_snake, &s_manager_ident);
             let batch_delete_all_fn = Ident::new(&format!("batch_delete_all_{}", plural_snake), s_ident.span());
 
             (
@@ -1150,6 +1502,8 @@ fn gen_child_standard_item(
                     async fn #delete_fn(&self, ctx: __ctx!(), key: &str) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
                     async fn #batch_delete_fn(&self, ctx: __ctx!(), keys: ::std::vec::Vec<&str>) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
                     async fn #list_fn(&self, ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#s_ident>, ::fractic_server_error::ServerError>;
+                    #list_page_methods
+                    #filter_methods
                     async fn #batch_delete_all_fn(&self, ctx: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
                 },
                 quote! {
@@ -1157,9 +1511,11 @@ fn gen_child_standard_item(
                         ctx.$ctx_repo_accessor().await?.#s_manager_ident().find(self, key).await
                     }
                     async fn #set_fn(&self, ctx: __ctx!(), data: #s_data_ident) -> ::std::result::Result<#s_ident, ::fractic_server_error::ServerError> {
+                        #s_data_check
                         ctx.$ctx_repo_accessor().await?.#s_manager_ident().set(self, data).await
                     }
                     async fn #batch_set_fn(&self, ctx: __ctx!(), data: ::std::vec::Vec<#s_data_ident>) -> ::std::result::Result<::std::vec::Vec<#s_ident>, ::fractic_server_error::ServerError> {
+                        #s_data_checks
                         ctx.$ctx_repo_accessor().await?.#s_manager_ident().batch_set(self, data).await
                     }
                     async fn #delete_fn(&self, ctx: __ctx!(), key: &str) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
@@ -1171,6 +1527,8 @@ fn gen_child_standard_item(
                     async fn #list_fn(&self, ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#s_ident>, ::fractic_server_error::ServerError> {
                         ctx.$ctx_repo_accessor().await?.#s_manager_ident().query_all(self).await
                     }
+                    #list_page_impls
+                    #filter_impls
                     async fn #batch_delete_all_fn(&self, ctx: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
                         ctx.$ctx_repo_accessor().await?.#s_manager_ident().batch_delete_all(self).await
                     }
@@ -1181,11 +1539,35 @@ fn gen_child_standard_item(
 
     let trait_ident = Ident::new(&format!("{}Crud", ty_ident), ty_ident.span());
 
+    let item_impls = instrument_methods(
+        ty_ident,
+        SelfRole::Item,
+        quote! {
+            #basic_impls
+            #add_impls
+            #delete_impls
+            #soft_delete_impls
+            #archive_impls
+        },
+    );
+    let child_impls = instrument_methods(
+        ty_ident,
+        SelfRole::Parent,
+        quote! {
+            #(#ordered_grandchild_impls)*
+            #(#unordered_grandchild_impls)*
+            #(#batch_impls)*
+            #(#singleton_child_impls)*
+            #(#indexed_singleton_child_impls)*
+        },
+    );
     quote! {
         pub trait #trait_ident {
             #basic_methods
             #add_methods
             #delete_methods
+            #soft_delete_methods
+            #archive_methods
             #(#ordered_grandchild_methods)*
             #(#unordered_grandchild_methods)*
             #(#batch_methods)*
@@ -1193,23 +1575,22 @@ fn gen_child_standard_item(
             #(#indexed_singleton_child_methods)*
         }
         impl #trait_ident for #ty_ident {
-            #basic_impls
-            #add_impls
-            #delete_impls
-            #(#ordered_grandchild_impls)*
-            #(#unordered_grandchild_impls)*
-            #(#batch_impls)*
-            #(#singleton_child_impls)*
-            #(#indexed_singleton_child_impls)*
+            #item_impls
+            #child_impls
         }
     }
 }
 
-fn gen_child_singleton_item(singleton: &SingletonDef, parent_ident: &Ident) -> TokenStream {
+fn gen_child_singleton_item(
+    singleton: &SingletonDef,
+    parent_ident: &Ident,
+    model: &ConfigModel,
+) -> TokenStream {
     let ty_ident = &singleton.name;
     let ty_data_ident = dynamo_data_type(ty_ident);
+    let ty_data_check = gen_data_validation(model, ty_ident, quote! { &data }, false);
     let parent_data_ident = dynamo_data_type(parent_ident);
-    let manager_ident = method_ident_for("manage", ty_ident);
+    let manager_ident = manager_ident_for(ty_ident, model.is_intercepted(ty_ident));
     let trait_ident = Ident::new(&format!("{}Crud", ty_ident), ty_ident.span());
 
     let methods = quote! {
@@ -1227,6 +1608,7 @@ fn gen_child_singleton_item(singleton: &SingletonDef, parent_ident: &Ident) -> T
             ctx.$ctx_repo_accessor().await?.#manager_ident().find(&tmp_dummy).await
         }
         async fn unchecked_set(ctx: __ctx!(), parent_id: ::fractic_aws_dynamo::schema::PkSk, data: #ty_data_ident) -> ::std::result::Result<#ty_ident, ::fractic_server_error::ServerError> {
+            #ty_data_check
             let tmp_dummy = #parent_ident {
                 id: parent_id,
                 data: #parent_data_ident::default(),
@@ -1244,6 +1626,7 @@ fn gen_child_singleton_item(singleton: &SingletonDef, parent_ident: &Ident) -> T
         }
     };
 
+    let impls = instrument_methods(ty_ident, SelfRole::Item, impls);
     quote! {
         pub trait #trait_ident {
             #methods
@@ -1257,12 +1640,17 @@ fn gen_child_singleton_item(singleton: &SingletonDef, parent_ident: &Ident) -> T
 fn gen_child_indexed_singleton_item(
     indexed_singleton: &IndexedSingletonDef,
     parent_ident: &Ident,
+    model: &ConfigModel,
 ) -> TokenStream {
     let ty_ident = &indexed_singleton.name;
     let ty_data_ident = dynamo_data_type(ty_ident);
+    let ty_data_check = gen_data_validation(model, ty_ident, quote! { &data }, false);
+    let ty_data_checks = gen_data_validation(model, ty_ident, quote! { data.iter() }, true);
     let parent_data_ident = dynamo_data_type(parent_ident);
-    let manager_ident = method_ident_for("manage", ty_ident);
+    let manager_ident = manager_ident_for(ty_ident, model.is_intercepted(ty_ident));
     let trait_ident = Ident::new(&format!("{}Crud", ty_ident), ty_ident.span());
+    let (page_methods, page_impls) =
+        gen_unchecked_page_methods(model, ty_ident, parent_ident, &manager_ident);
 
     let methods = quote! {
         async fn unchecked_get(ctx: __ctx!(), parent_id: ::fractic_aws_dynamo::schema::PkSk, key: &str) -> ::std::result::Result<::std::option::Option<#ty_ident>, ::fractic_server_error::ServerError>;
@@ -1271,6 +1659,7 @@ fn gen_child_indexed_singleton_item(
         async fn unchecked_delete(ctx: __ctx!(), parent_id: ::fractic_aws_dynamo::schema::PkSk, key: &str) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
         async fn unchecked_batch_delete(ctx: __ctx!(), parent_id: ::fractic_aws_dynamo::schema::PkSk, keys: ::std::vec::Vec<&str>) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
         async fn unchecked_list(ctx: __ctx!(), parent_id: ::fractic_aws_dynamo::schema::PkSk) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError>;
+        #page_methods
         async fn unchecked_batch_delete_all(ctx: __ctx!(), parent_id: ::fractic_aws_dynamo::schema::PkSk) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
     };
     let impls = quote! {
@@ -1283,6 +1672,7 @@ fn gen_child_indexed_singleton_item(
             ctx.$ctx_repo_accessor().await?.#manager_ident().find(&tmp_dummy, key).await
         }
         async fn unchecked_set(ctx: __ctx!(), parent_id: ::fractic_aws_dynamo::schema::PkSk, data: #ty_data_ident) -> ::std::result::Result<#ty_ident, ::fractic_server_error::ServerError> {
+            #ty_data_check
             let tmp_dummy = #parent_ident {
                 id: parent_id,
                 data: #parent_data_ident::default(),
@@ -1291,6 +1681,7 @@ fn gen_child_indexed_singleton_item(
             ctx.$ctx_repo_accessor().await?.#manager_ident().set(&tmp_dummy, data).await
         }
         async fn unchecked_batch_set(ctx: __ctx!(), parent_id: ::fractic_aws_dynamo::schema::PkSk, data: ::std::vec::Vec<#ty_data_ident>) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError> {
+            #ty_data_checks
             let tmp_dummy = #parent_ident {
                 id: parent_id,
                 data: #parent_data_ident::default(),
@@ -1322,6 +1713,7 @@ fn gen_child_indexed_singleton_item(
             };
             ctx.$ctx_repo_accessor().await?.#manager_ident().query_all(&tmp_dummy).await
         }
+        #page_impls
         async fn unchecked_batch_delete_all(ctx: __ctx!(), parent_id: ::fractic_aws_dynamo::schema::PkSk) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
             let tmp_dummy = #parent_ident {
                 id: parent_id,
@@ -1332,6 +1724,7 @@ fn gen_child_indexed_singleton_item(
         }
     };
 
+    let impls = instrument_methods(ty_ident, SelfRole::Item, impls);
     quote! {
         pub trait #trait_ident {
             #methods
@@ -1345,7 +1738,251 @@ fn gen_child_indexed_singleton_item(
 // Helpers.
 // ----------------------------------------------------------------------------
 
-fn dynamo_data_type(ident: &Ident) -> TokenStream {
+/// Methods for listing a child collection with a storage-level filter (and
+/// optional field projection). Only generated when the child object declares
+/// `filterable` fields, since the filter type does not exist otherwise.
+fn gen_child_filter_methods(
+    model: &ConfigModel,
+    child_ident: &Ident,
+    child_plural_snake: &str,
+    child_manager_ident: &Ident,
+) -> (TokenStream, TokenStream) {
+    if model.filterable_fields(child_ident).is_empty() {
+        return (TokenStream::new(), TokenStream::new());
+    }
+    let filter_ident = filter_ident_for(child_ident);
+    let where_fn = Ident::new(
+        &format!("list_{}_where", child_plural_snake),
+        child_ident.span(),
+    );
+    let where_projected_fn = Ident::new(
+        &format!("list_{}_where_projected", child_plural_snake),
+        child_ident.span(),
+    );
+    (
+        quote! {
+            async fn #where_fn(&self, ctx: __ctx!(), filter: #filter_ident) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError>;
+            async fn #where_projected_fn(&self, ctx: __ctx!(), filter: #filter_ident, fields: &[&str]) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError>;
+        },
+        quote! {
+            async fn #where_fn(&self, ctx: __ctx!(), filter: #filter_ident) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError> {
+                ctx.$ctx_repo_accessor().await?.#child_manager_ident().query_filtered(self, filter.into_conditions(), ::std::option::Option::None).await
+            }
+            async fn #where_projected_fn(&self, ctx: __ctx!(), filter: #filter_ident, fields: &[&str]) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError> {
+                ctx.$ctx_repo_accessor().await?.#child_manager_ident().query_filtered(self, filter.into_conditions(), ::std::option::Option::Some(fields)).await
+            }
+        },
+    )
+}
+
+/// Root equivalent of `gen_child_filter_methods`.
+fn gen_root_filter_methods(
+    model: &ConfigModel,
+    ty_ident: &Ident,
+    manager_ident: &Ident,
+) -> (TokenStream, TokenStream) {
+    if model.filterable_fields(ty_ident).is_empty() {
+        return (TokenStream::new(), TokenStream::new());
+    }
+    let filter_ident = filter_ident_for(ty_ident);
+    (
+        quote! {
+            async fn list_where(ctx: __ctx!(), filter: #filter_ident) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError>;
+            async fn list_where_projected(ctx: __ctx!(), filter: #filter_ident, fields: &[&str]) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError>;
+        },
+        quote! {
+            async fn list_where(ctx: __ctx!(), filter: #filter_ident) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError> {
+                ctx.$ctx_repo_accessor().await?.#manager_ident().query_filtered(filter.into_conditions(), ::std::option::Option::None).await
+            }
+            async fn list_where_projected(ctx: __ctx!(), filter: #filter_ident, fields: &[&str]) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError> {
+                ctx.$ctx_repo_accessor().await?.#manager_ident().query_filtered(filter.into_conditions(), ::std::option::Option::Some(fields)).await
+            }
+        },
+    )
+}
+
+/// Methods for listing a child collection one page at a time. Only generated
+/// with the `paginated` repository option.
+fn gen_child_page_methods(
+    model: &ConfigModel,
+    child_ident: &Ident,
+    child_plural_snake: &str,
+    child_manager_ident: &Ident,
+) -> (TokenStream, TokenStream) {
+    if !model.paginated {
+        return (TokenStream::new(), TokenStream::new());
+    }
+    let page_fn = Ident::new(
+        &format!("list_{}_page", child_plural_snake),
+        child_ident.span(),
+    );
+    (
+        quote! {
+            async fn #page_fn(&self, ctx: __ctx!(), cursor: ::std::option::Option<&str>, limit: usize) -> ::std::result::Result<(::std::vec::Vec<#child_ident>, ::std::option::Option<::std::string::String>), ::fractic_server_error::ServerError>;
+        },
+        quote! {
+            async fn #page_fn(&self, ctx: __ctx!(), cursor: ::std::option::Option<&str>, limit: usize) -> ::std::result::Result<(::std::vec::Vec<#child_ident>, ::std::option::Option<::std::string::String>), ::fractic_server_error::ServerError> {
+                ctx.$ctx_repo_accessor().await?.#child_manager_ident().query_page(self, cursor, limit).await
+            }
+        },
+    )
+}
+
+/// Root equivalent of `gen_child_page_methods`.
+fn gen_root_page_methods(
+    model: &ConfigModel,
+    ty_ident: &Ident,
+    manager_ident: &Ident,
+) -> (TokenStream, TokenStream) {
+    if !model.paginated {
+        return (TokenStream::new(), TokenStream::new());
+    }
+    (
+        quote! {
+            async fn list_page(ctx: __ctx!(), cursor: ::std::option::Option<&str>, limit: usize) -> ::std::result::Result<(::std::vec::Vec<#ty_ident>, ::std::option::Option<::std::string::String>), ::fractic_server_error::ServerError>;
+        },
+        quote! {
+            async fn list_page(ctx: __ctx!(), cursor: ::std::option::Option<&str>, limit: usize) -> ::std::result::Result<(::std::vec::Vec<#ty_ident>, ::std::option::Option<::std::string::String>), ::fractic_server_error::ServerError> {
+                ctx.$ctx_repo_accessor().await?.#manager_ident().query_page(cursor, limit).await
+            }
+        },
+    )
+}
+
+/// Equivalent of `gen_child_page_methods` taking the parent by ID, for the
+/// `unchecked_*` methods of child objects.
+fn gen_unchecked_page_methods(
+    model: &ConfigModel,
+    ty_ident: &Ident,
+    parent_ident: &Ident,
+    manager_ident: &Ident,
+) -> (TokenStream, TokenStream) {
+    if !model.paginated {
+        return (TokenStream::new(), TokenStream::new());
+    }
+    let parent_data_ident = dynamo_data_type(parent_ident);
+    (
+        quote! {
+            async fn unchecked_list_page(ctx: __ctx!(), parent_id: ::fractic_aws_dynamo::schema::PkSk, cursor: ::std::option::Option<&str>, limit: usize) -> ::std::result::Result<(::std::vec::Vec<#ty_ident>, ::std::option::Option<::std::string::String>), ::fractic_server_error::ServerError>;
+        },
+        quote! {
+            async fn unchecked_list_page(ctx: __ctx!(), parent_id: ::fractic_aws_dynamo::schema::PkSk, cursor: ::std::option::Option<&str>, limit: usize) -> ::std::result::Result<(::std::vec::Vec<#ty_ident>, ::std::option::Option<::std::string::String>), ::fractic_server_error::ServerError> {
+                let tmp_dummy = #parent_ident {
+                    id: parent_id,
+                    data: #parent_data_ident::default(),
+                    auto_fields: ::fractic_aws_dynamo::schema::AutoFields::default(),
+                };
+                ctx.$ctx_repo_accessor().await?.#manager_ident().query_page(&tmp_dummy, cursor, limit).await
+            }
+        },
+    )
+}
+
+/// Methods for listing the soft-deleted members of a child collection. Only
+/// generated when the child object uses the `soft_delete` prefix.
+fn gen_child_soft_delete_methods(
+    model: &ConfigModel,
+    child_ident: &Ident,
+    child_plural_snake: &str,
+    child_manager_ident: &Ident,
+) -> (TokenStream, TokenStream) {
+    if !model.is_soft_delete(child_ident) {
+        return (TokenStream::new(), TokenStream::new());
+    }
+    let list_deleted_fn = Ident::new(
+        &format!("list_deleted_{}", child_plural_snake),
+        child_ident.span(),
+    );
+    (
+        quote! {
+            async fn #list_deleted_fn(&self, ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError>;
+        },
+        quote! {
+            async fn #list_deleted_fn(&self, ctx: __ctx!()) -> ::std::result::Result<::std::vec::Vec<#child_ident>, ::fractic_server_error::ServerError> {
+                ctx.$ctx_repo_accessor().await?.#child_manager_ident().query_deleted(self).await
+            }
+        },
+    )
+}
+
+/// `update` annotation method body; conditional on the stored version for
+/// `versioned` objects.
+fn gen_update_impl(
+    model: &ConfigModel,
+    object: &StandardDef,
+    manager_ident: &Ident,
+) -> TokenStream {
+    let ty_ident = &object.name;
+    let data_check = gen_data_validation(model, ty_ident, quote! { &self.data }, false);
+    if object.is_versioned {
+        // Only succeeds if the stored item is still at the version this copy
+        // was read at.
+        let conflict_ident = version_conflict_ident_for(&model.repository_name);
+        quote! {
+            async fn update(&self, ctx: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+                #data_check
+                let expected_version = self.auto_fields.version;
+                if ctx.$ctx_repo_accessor().await?.#manager_ident().update_if_version(self, expected_version).await? {
+                    ::std::result::Result::Ok(())
+                } else {
+                    ::std::result::Result::Err(#conflict_ident::new(stringify!(#ty_ident)).into())
+                }
+            }
+        }
+    } else {
+        quote! {
+            async fn update(&self, ctx: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+                #data_check
+                ctx.$ctx_repo_accessor().await?.#manager_ident().update(self).await
+            }
+        }
+    }
+}
+
+/// Methods for moving a `movable_archive` object (together with its
+/// descendants) between the main and archive tables.
+fn gen_movable_archive_methods(
+    model: &ConfigModel,
+    ty_ident: &Ident,
+) -> (TokenStream, TokenStream) {
+    let manager_ident = method_ident_for("manage", ty_ident);
+    let archived_manager_ident = archived_manage_ident_for(ty_ident);
+    // Moving an item between tables bypasses the hooked managers, so a cached
+    // repository is cleared wholesale instead.
+    let invalidate = if model.cache_ttl_for(ty_ident).is_some()
+        || !model.cached_descendants(ty_ident).is_empty()
+    {
+        quote! {
+            if let ::std::option::Option::Some(cache) = repo.cache() {
+                cache.clear();
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+    (
+        quote! {
+            async fn archive(self, ctx: __ctx!()) -> ::std::result::Result<#ty_ident, ::fractic_server_error::ServerError>;
+            async fn unarchive(self, ctx: __ctx!()) -> ::std::result::Result<#ty_ident, ::fractic_server_error::ServerError>;
+        },
+        quote! {
+            async fn archive(self, ctx: __ctx!()) -> ::std::result::Result<#ty_ident, ::fractic_server_error::ServerError> {
+                let repo = ctx.$ctx_repo_accessor().await?;
+                let item = repo.#manager_ident().transfer(self, repo.#archived_manager_ident()).await?;
+                #invalidate
+                ::std::result::Result::Ok(item)
+            }
+            async fn unarchive(self, ctx: __ctx!()) -> ::std::result::Result<#ty_ident, ::fractic_server_error::ServerError> {
+                let repo = ctx.$ctx_repo_accessor().await?;
+                let item = repo.#archived_manager_ident().transfer(self, repo.#manager_ident()).await?;
+                #invalidate
+                ::std::result::Result::Ok(item)
+            }
+        },
+    )
+}
+
+pub(crate) fn dynamo_data_type(ident: &Ident) -> TokenStream {
     quote! {
         <#ident as ::fractic_aws_dynamo::schema::DynamoObject>::Data
     }
@@ -1357,7 +1994,7 @@ fn method_ident_for(prefix: &str, ident: &Ident) -> Ident {
     Ident::new(&name, ident.span())
 }
 
-fn stripped_pascal(parent: &Ident, child: &Ident) -> String {
+pub(crate) fn stripped_pascal(parent: &Ident, child: &Ident) -> String {
     let parent = parent.to_string();
     let mut child = child.to_string();
     let overlap_len = parent
@@ -1378,7 +2015,7 @@ fn stripped_pascal(parent: &Ident, child: &Ident) -> String {
 }
 
 /// Very small heuristic pluralizer.
-fn pluralize_pascal(s: &str) -> String {
+pub(crate) fn pluralize_pascal(s: &str) -> String {
     let lower = s.to_ascii_lowercase();
     if lower.ends_with('y')
         && !matches!(
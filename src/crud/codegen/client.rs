@@ -0,0 +1,470 @@
+use std::collections::HashMap;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+
+use super::annotations::{dynamo_data_type, pluralize_pascal, stripped_pascal};
+use crate::{crud::model::ConfigModel, helpers::to_snake_case};
+
+/// `{Repo}CrudClient`, if the repository declares `#[client]`: typed methods
+/// per object (named as in the annotation traits, ex. `list_principles`,
+/// `add_principle`) which build the `CrudOperation` for the object's handler
+/// and send it through a `{Repo}CrudTransport`. The loopback transport, which
+/// calls the handlers directly, is generated with the handlers.
+pub fn generate(model: &ConfigModel) -> TokenStream {
+    if !model.client {
+        return TokenStream::new();
+    }
+    let repo_name = &model.repository_name;
+    let client_ident = client_ident_for(repo_name);
+    let transport_ident = transport_trait_ident_for(repo_name);
+    let request_ident = request_enum_ident_for(repo_name);
+    let error_ident = client_error_ident_for(repo_name);
+
+    let objects = client_objects(model);
+    let variants = objects.iter().map(|object| {
+        let ty_ident = object.name;
+        quote! {
+            #ty_ident(::fractic_aws_apigateway::CrudOperation<#ty_ident>),
+        }
+    });
+    let object_arms = objects.iter().map(|object| {
+        let ty_ident = object.name;
+        let ty_name = ty_ident.to_string();
+        quote! {
+            Self::#ty_ident(_) => #ty_name,
+        }
+    });
+    let methods = objects
+        .iter()
+        .map(|object| gen_object_methods(object, &request_ident, model.paginated));
+
+    quote! {
+        ::fractic_server_error::define_internal_error!(
+            #error_ident,
+            "CRUD client error: {details}.",
+            { details: &str }
+        );
+
+        /// Operation sent by the CRUD client, tagged with the object whose
+        /// handler should process it.
+        pub enum #request_ident {
+            #(#variants)*
+        }
+
+        impl #request_ident {
+            /// Name of the object the operation applies to.
+            pub fn object(&self) -> &'static str {
+                match self {
+                    #(#object_arms)*
+                }
+            }
+        }
+
+        /// Carries the CRUD client's operations to the handlers (ex. over
+        /// HTTP), returning the handler's JSON response.
+        pub trait #transport_ident: ::std::marker::Send + ::std::marker::Sync {
+            fn send(
+                &self,
+                request: #request_ident,
+            ) -> impl ::std::future::Future<
+                Output = ::std::result::Result<::serde_json::Value, ::fractic_server_error::ServerError>,
+            > + ::std::marker::Send;
+        }
+
+        /// Typed client for the repository's CRUD handlers.
+        pub struct #client_ident<T> {
+            transport: T,
+        }
+
+        impl<T: #transport_ident> #client_ident<T> {
+            pub fn new(transport: T) -> Self {
+                Self { transport }
+            }
+
+            #[allow(dead_code)]
+            fn __decode<R: ::serde::de::DeserializeOwned>(
+                value: ::serde_json::Value,
+            ) -> ::std::result::Result<R, ::fractic_server_error::ServerError> {
+                ::serde_json::from_value(value).map_err(|e| #error_ident::new(&e.to_string()))
+            }
+
+            #[allow(dead_code)]
+            fn __decode_field<R: ::serde::de::DeserializeOwned>(
+                value: &mut ::serde_json::Value,
+                field: &str,
+            ) -> ::std::result::Result<R, ::fractic_server_error::ServerError> {
+                let __field = value
+                    .get_mut(field)
+                    .map(::serde_json::Value::take)
+                    .unwrap_or_default();
+                Self::__decode(__field)
+            }
+
+            #(#methods)*
+        }
+    }
+}
+
+pub(crate) fn client_ident_for(repo_name: &Ident) -> Ident {
+    Ident::new(&format!("{}CrudClient", repo_name), repo_name.span())
+}
+
+pub(crate) fn transport_trait_ident_for(repo_name: &Ident) -> Ident {
+    Ident::new(&format!("{}CrudTransport", repo_name), repo_name.span())
+}
+
+pub(crate) fn request_enum_ident_for(repo_name: &Ident) -> Ident {
+    Ident::new(&format!("{}CrudRequest", repo_name), repo_name.span())
+}
+
+pub(crate) fn client_error_ident_for(repo_name: &Ident) -> Ident {
+    Ident::new(&format!("{}CrudClientError", repo_name), repo_name.span())
+}
+
+pub(crate) fn loopback_ident_for(repo_name: &Ident) -> Ident {
+    Ident::new(&format!("{}CrudLoopback", repo_name), repo_name.span())
+}
+
+#[derive(Clone, Copy)]
+enum ClientKind {
+    Standard { is_ordered: bool },
+    Batch,
+    Singleton,
+    IndexedSingleton,
+}
+
+/// Object with a handler, and the names its client methods are built from.
+pub(crate) struct ClientObject<'a> {
+    pub name: &'a Ident,
+    is_child: bool,
+    kind: ClientKind,
+    singular_snake: String,
+    plural_snake: String,
+}
+
+/// Every object with a handler (i.e. all but phantom objects). Children are
+/// named relative to their (first) parent, as in the annotation traits, unless
+/// that name is shared with another object.
+pub(crate) fn client_objects(model: &ConfigModel) -> Vec<ClientObject<'_>> {
+    let objects = model
+        .ordered_objects
+        .iter()
+        .map(|o| {
+            (
+                &o.name,
+                &o.parents,
+                ClientKind::Standard { is_ordered: true },
+            )
+        })
+        .chain(model.unordered_objects.iter().map(|o| {
+            (
+                &o.name,
+                &o.parents,
+                ClientKind::Standard { is_ordered: false },
+            )
+        }))
+        .chain(
+            model
+                .batch_objects
+                .iter()
+                .map(|o| (&o.name, &o.parents, ClientKind::Batch)),
+        )
+        .chain(
+            model
+                .singleton_objects
+                .iter()
+                .map(|o| (&o.name, &o.parents, ClientKind::Singleton)),
+        )
+        .chain(
+            model
+                .indexed_singleton_objects
+                .iter()
+                .map(|o| (&o.name, &o.parents, ClientKind::IndexedSingleton)),
+        )
+        .map(|(name, parents, kind)| {
+            let parent = parents.as_ref().and_then(|parents| parents.first());
+            let stem = match parent {
+                Some(parent) => stripped_pascal(parent, name),
+                None => name.to_string(),
+            };
+            (name, parent.is_some(), kind, stem)
+        })
+        .collect::<Vec<_>>();
+
+    let mut stem_counts = HashMap::<&str, usize>::new();
+    for (_, _, _, stem) in &objects {
+        *stem_counts.entry(stem.as_str()).or_default() += 1;
+    }
+    objects
+        .iter()
+        .map(|(name, is_child, kind, stem)| {
+            let stem = if stem_counts[stem.as_str()] > 1 {
+                name.to_string()
+            } else {
+                stem.clone()
+            };
+            ClientObject {
+                name,
+                is_child: *is_child,
+                kind: *kind,
+                singular_snake: to_snake_case(&stem),
+                plural_snake: to_snake_case(&pluralize_pascal(&stem)),
+            }
+        })
+        .collect()
+}
+
+fn gen_object_methods(
+    object: &ClientObject<'_>,
+    request_ident: &Ident,
+    paginated: bool,
+) -> TokenStream {
+    let ty_ident = object.name;
+    let ty_data_ident = dynamo_data_type(ty_ident);
+    let singular = &object.singular_snake;
+    let plural = &object.plural_snake;
+    let method = |name: String| Ident::new(&name, ty_ident.span());
+
+    // Children are addressed through their parent's ID.
+    let (parent_param, parent_id) = if object.is_child {
+        (
+            quote! { parent_id: ::fractic_aws_dynamo::schema::PkSk, },
+            quote! { ::std::option::Option::Some(parent_id) },
+        )
+    } else {
+        (quote! {}, quote! { ::std::option::Option::None })
+    };
+
+    // Send an operation, evaluating to the handler's JSON response.
+    let send = |operation: TokenStream| {
+        quote! {
+            self.transport
+                .send(#request_ident::#ty_ident(::fractic_aws_apigateway::CrudOperation::#operation))
+                .await?
+        }
+    };
+
+    let list_fn = method(format!("list_{}", plural));
+    let list_methods = if paginated {
+        let list_page_fn = method(format!("list_{}_page", plural));
+        let send_list = send(quote! {
+            List {
+                parent_id: #parent_id,
+                page_token: ::std::option::Option::None,
+                limit: ::std::option::Option::None,
+            }
+        });
+        let send_list_page = send(quote! {
+            List {
+                parent_id: #parent_id,
+                page_token: cursor.map(::std::string::ToString::to_string),
+                limit: ::std::option::Option::Some(limit),
+            }
+        });
+        quote! {
+            pub async fn #list_fn(&self, #parent_param) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError> {
+                Self::__decode(#send_list)
+            }
+            pub async fn #list_page_fn(&self, #parent_param cursor: ::std::option::Option<&str>, limit: usize) -> ::std::result::Result<(::std::vec::Vec<#ty_ident>, ::std::option::Option<::std::string::String>), ::fractic_server_error::ServerError> {
+                let mut __response = #send_list_page;
+                ::std::result::Result::Ok((
+                    Self::__decode_field(&mut __response, "items")?,
+                    Self::__decode_field(&mut __response, "next_cursor")?,
+                ))
+            }
+        }
+    } else {
+        let send_list = send(quote! { List { parent_id: #parent_id } });
+        quote! {
+            pub async fn #list_fn(&self, #parent_param) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError> {
+                Self::__decode(#send_list)
+            }
+        }
+    };
+
+    match object.kind {
+        ClientKind::Standard { is_ordered } => {
+            let get_fn = method(format!("get_{}", singular));
+            let add_fn = method(format!("add_{}", singular));
+            let batch_add_fn = method(format!("batch_add_{}", plural));
+            let update_fn = method(format!("update_{}", singular));
+            let delete_fn = method(format!("delete_{}", singular));
+            let (after_param, after) = if is_ordered {
+                (
+                    quote! { after: ::std::option::Option<::fractic_aws_dynamo::schema::PkSk> },
+                    quote! { after },
+                )
+            } else {
+                (quote! {}, quote! { ::std::option::Option::None })
+            };
+            let send_get = send(quote! {
+                Read { item_ref: ::fractic_aws_apigateway::ItemRef::Id(id) }
+            });
+            let send_add = send(quote! {
+                Create { parent_id: #parent_id, after: #after, data }
+            });
+            let send_batch_add = send(quote! {
+                CreateMultiple { parent_id: #parent_id, after: #after, data }
+            });
+            let send_update = send(quote! { Update { item } });
+            let send_delete = send(quote! {
+                Delete { item_ref: ::fractic_aws_apigateway::ItemRef::Id(id), non_recursive: false }
+            });
+            quote! {
+                #list_methods
+                pub async fn #get_fn(&self, id: ::fractic_aws_dynamo::schema::PkSk) -> ::std::result::Result<#ty_ident, ::fractic_server_error::ServerError> {
+                    Self::__decode(#send_get)
+                }
+                pub async fn #add_fn(&self, #parent_param data: #ty_data_ident, #after_param) -> ::std::result::Result<::fractic_aws_dynamo::schema::PkSk, ::fractic_server_error::ServerError> {
+                    Self::__decode_field(&mut #send_add, "created_id")
+                }
+                pub async fn #batch_add_fn(&self, #parent_param data: ::std::vec::Vec<#ty_data_ident>, #after_param) -> ::std::result::Result<::std::vec::Vec<::fractic_aws_dynamo::schema::PkSk>, ::fractic_server_error::ServerError> {
+                    Self::__decode_field(&mut #send_batch_add, "created_ids")
+                }
+                pub async fn #update_fn(&self, item: #ty_ident) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+                    #send_update;
+                    ::std::result::Result::Ok(())
+                }
+                pub async fn #delete_fn(&self, id: ::fractic_aws_dynamo::schema::PkSk) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+                    #send_delete;
+                    ::std::result::Result::Ok(())
+                }
+            }
+        }
+        ClientKind::Batch => {
+            let replace_all_fn = method(format!("replace_all_{}", plural));
+            let delete_all_fn = method(format!("delete_all_{}", plural));
+            let send_replace_all = send(quote! {
+                ReplaceAll { parent_id: #parent_id, data }
+            });
+            let send_delete_all = send(quote! {
+                DeleteAll { parent_id: #parent_id, non_recursive: false }
+            });
+            quote! {
+                #list_methods
+                pub async fn #replace_all_fn(&self, #parent_param data: ::std::vec::Vec<#ty_data_ident>) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+                    #send_replace_all;
+                    ::std::result::Result::Ok(())
+                }
+                pub async fn #delete_all_fn(&self, #parent_param) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+                    #send_delete_all;
+                    ::std::result::Result::Ok(())
+                }
+            }
+        }
+        ClientKind::Singleton => {
+            let get_fn = method(format!("get_{}", singular));
+            let set_fn = method(format!("set_{}", singular));
+            let delete_fn = method(format!("delete_{}", singular));
+            let item_ref = quote! {
+                ::fractic_aws_apigateway::ItemRef::None { parent_id: #parent_id }
+            };
+            let send_get = send(quote! { Read { item_ref: #item_ref } });
+            let send_set = send(quote! {
+                Create { parent_id: #parent_id, after: ::std::option::Option::None, data }
+            });
+            let send_delete = send(quote! {
+                Delete { item_ref: #item_ref, non_recursive: false }
+            });
+            quote! {
+                pub async fn #get_fn(&self, #parent_param) -> ::std::result::Result<#ty_ident, ::fractic_server_error::ServerError> {
+                    Self::__decode(#send_get)
+                }
+                pub async fn #set_fn(&self, #parent_param data: #ty_data_ident) -> ::std::result::Result<::fractic_aws_dynamo::schema::PkSk, ::fractic_server_error::ServerError> {
+                    Self::__decode_field(&mut #send_set, "created_id")
+                }
+                pub async fn #delete_fn(&self, #parent_param) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+                    #send_delete;
+                    ::std::result::Result::Ok(())
+                }
+            }
+        }
+        ClientKind::IndexedSingleton => {
+            let get_fn = method(format!("get_{}", singular));
+            let set_fn = method(format!("set_{}", singular));
+            let delete_fn = method(format!("delete_{}", singular));
+            let item_ref = quote! {
+                ::fractic_aws_apigateway::ItemRef::Key {
+                    parent_id: #parent_id,
+                    key: key.to_string(),
+                }
+            };
+            let send_get = send(quote! { Read { item_ref: #item_ref } });
+            let send_set = send(quote! {
+                Create { parent_id: #parent_id, after: ::std::option::Option::None, data }
+            });
+            let send_delete = send(quote! {
+                Delete { item_ref: #item_ref, non_recursive: false }
+            });
+            quote! {
+                #list_methods
+                pub async fn #get_fn(&self, #parent_param key: &str) -> ::std::result::Result<#ty_ident, ::fractic_server_error::ServerError> {
+                    Self::__decode(#send_get)
+                }
+                pub async fn #set_fn(&self, #parent_param data: #ty_data_ident) -> ::std::result::Result<::fractic_aws_dynamo::schema::PkSk, ::fractic_server_error::ServerError> {
+                    Self::__decode_field(&mut #send_set, "created_id")
+                }
+                pub async fn #delete_fn(&self, #parent_param key: &str) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+                    #send_delete;
+                    ::std::result::Result::Ok(())
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::client_objects;
+    use crate::crud::test_model;
+
+    #[test]
+    fn names_children_relative_to_parent_unless_ambiguous() {
+        let model = test_model(
+            r#"
+            #[client]
+            MyRepo;
+            root Persona {}
+            ordered PersonaPrinciple {
+                parent: Persona
+            }
+            unordered PersonaNote {
+                parent: Persona
+            }
+            unordered ProjectNote {
+                parent: Project
+            }
+            indexed_singleton Note {}
+            "#,
+        )
+        .unwrap();
+
+        let names = client_objects(&model)
+            .into_iter()
+            .map(|object| {
+                (
+                    object.name.to_string(),
+                    object.singular_snake,
+                    object.plural_snake,
+                )
+            })
+            .collect::<Vec<_>>();
+        let expected = [
+            ("PersonaPrinciple", "principle", "principles"),
+            ("Persona", "persona", "personas"),
+            ("PersonaNote", "persona_note", "persona_notes"),
+            ("ProjectNote", "project_note", "project_notes"),
+            ("Note", "note", "notes"),
+        ];
+        assert_eq!(names.len(), expected.len());
+        for (name, singular, plural) in expected {
+            assert!(
+                names.contains(&(name.to_string(), singular.to_string(), plural.to_string())),
+                "missing client names for {}",
+                name
+            );
+        }
+    }
+}
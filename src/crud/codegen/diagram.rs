@@ -0,0 +1,258 @@
+//! Diagrams of the object hierarchy, for reviewing large configurations.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+
+use crate::{crud::model::ConfigModel, helpers::to_snake_case};
+
+/// Fill colour used for each object kind, in both formats.
+const KIND_COLORS: [(&str, &str); 7] = [
+    ("phantom", "#e9ecef"),
+    ("root", "#ffd8a8"),
+    ("ordered", "#a5d8ff"),
+    ("unordered", "#b2f2bb"),
+    ("batch", "#d0bfff"),
+    ("singleton", "#ffec99"),
+    ("indexed_singleton", "#fcc2d7"),
+];
+
+/// `{REPO}_MERMAID` and `{REPO}_DOT`, if the repository declares
+/// `#[diagram]`.
+pub fn generate(model: &ConfigModel) -> TokenStream {
+    if !model.diagram {
+        return TokenStream::new();
+    }
+    let repo_name = &model.repository_name;
+    let prefix = to_snake_case(&repo_name.to_string()).to_uppercase();
+    let mermaid_ident = Ident::new(&format!("{}_MERMAID", prefix), repo_name.span());
+    let dot_ident = Ident::new(&format!("{}_DOT", prefix), repo_name.span());
+    let mermaid = mermaid(model);
+    let dot = dot(model);
+    quote! {
+        /// Mermaid flowchart of the repository's object hierarchy.
+        pub const #mermaid_ident: &str = #mermaid;
+        /// Graphviz (DOT) graph of the repository's object hierarchy.
+        pub const #dot_ident: &str = #dot;
+    }
+}
+
+struct Node<'a> {
+    name: &'a Ident,
+    kind: &'static str,
+    // `archive` or `movable archive`, if either prefix was used.
+    archive: Option<&'static str>,
+}
+
+impl Node<'_> {
+    fn label(&self) -> String {
+        let kind = self.kind.replace('_', " ");
+        match self.archive {
+            Some(archive) => format!("{}, {}", kind, archive),
+            None => kind,
+        }
+    }
+}
+
+/// Parent, child, and the child relationship (ex. `ordered`).
+type Edge<'a> = (&'a Ident, &'a Ident, &'static str);
+
+fn collect(model: &ConfigModel) -> (Vec<Node<'_>>, Vec<Edge<'_>>) {
+    let mut nodes = Vec::new();
+    let mut edges = Vec::new();
+
+    for object in &model.phantom_objects {
+        nodes.push(Node {
+            name: &object.name,
+            kind: "phantom",
+            archive: None,
+        });
+        push_edges(
+            &mut edges,
+            &object.name,
+            [
+                (&object.ordered_children, "ordered"),
+                (&object.unordered_children, "unordered"),
+                (&object.batch_children, "batch"),
+                (&object.singleton_children, "singleton"),
+                (&object.indexed_singleton_children, "indexed_singleton"),
+            ],
+        );
+    }
+    let standard = model
+        .ordered_objects
+        .iter()
+        .map(|object| (object, "ordered"))
+        .chain(model.unordered_objects.iter().map(|object| {
+            // Unordered objects without a parent were declared as `root`.
+            let kind = match object.parents {
+                Some(_) => "unordered",
+                None => "root",
+            };
+            (object, kind)
+        }));
+    for (object, kind) in standard {
+        nodes.push(Node {
+            name: &object.name,
+            kind,
+            archive: archive_label(object.is_archive, object.is_movable_archive),
+        });
+        push_edges(
+            &mut edges,
+            &object.name,
+            [
+                (&object.ordered_children, "ordered"),
+                (&object.unordered_children, "unordered"),
+                (&object.batch_children, "batch"),
+                (&object.singleton_children, "singleton"),
+                (&object.indexed_singleton_children, "indexed_singleton"),
+            ],
+        );
+    }
+    for object in &model.batch_objects {
+        nodes.push(Node {
+            name: &object.name,
+            kind: "batch",
+            archive: archive_label(object.is_archive, false),
+        });
+    }
+    for object in &model.singleton_objects {
+        nodes.push(Node {
+            name: &object.name,
+            kind: "singleton",
+            archive: archive_label(object.is_archive, false),
+        });
+    }
+    for object in &model.indexed_singleton_objects {
+        nodes.push(Node {
+            name: &object.name,
+            kind: "indexed_singleton",
+            archive: archive_label(object.is_archive, false),
+        });
+    }
+    (nodes, edges)
+}
+
+fn push_edges<'a>(
+    edges: &mut Vec<Edge<'a>>,
+    parent: &'a Ident,
+    children: [(&'a Vec<Ident>, &'static str); 5],
+) {
+    for (children, relation) in children {
+        for child in children {
+            edges.push((parent, child, relation));
+        }
+    }
+}
+
+fn archive_label(is_archive: bool, is_movable_archive: bool) -> Option<&'static str> {
+    if is_movable_archive {
+        Some("movable archive")
+    } else if is_archive {
+        Some("archive")
+    } else {
+        None
+    }
+}
+
+/// Flowchart with one class per kind; archive objects additionally get a
+/// dashed border.
+fn mermaid(model: &ConfigModel) -> String {
+    let (nodes, edges) = collect(model);
+    let mut out = String::from("flowchart TD\n");
+    for node in &nodes {
+        out.push_str(&format!(
+            "    {}[\"{}<br/>{}\"]:::{}\n",
+            node.name,
+            node.name,
+            node.label(),
+            node.kind
+        ));
+    }
+    for (parent, child, relation) in &edges {
+        out.push_str(&format!("    {} -->|{}| {}\n", parent, relation, child));
+    }
+    for (kind, color) in KIND_COLORS {
+        out.push_str(&format!(
+            "    classDef {} fill:{},stroke:#495057\n",
+            kind, color
+        ));
+    }
+    out.push_str("    classDef archived stroke-dasharray:5 5,stroke-width:2px\n");
+    let archived = nodes
+        .iter()
+        .filter(|node| node.archive.is_some())
+        .map(|node| node.name.to_string())
+        .collect::<Vec<_>>();
+    if !archived.is_empty() {
+        out.push_str(&format!("    class {} archived\n", archived.join(",")));
+    }
+    out
+}
+
+/// Directed graph with nodes filled by kind; archive objects are dashed.
+fn dot(model: &ConfigModel) -> String {
+    let (nodes, edges) = collect(model);
+    let mut out = format!("digraph {} {{\n", model.repository_name);
+    out.push_str("    node [shape=box, style=\"rounded,filled\", fontname=\"Helvetica\"];\n");
+    for node in &nodes {
+        let color = KIND_COLORS
+            .iter()
+            .find(|(kind, _)| *kind == node.kind)
+            .map_or("#ffffff", |(_, color)| color);
+        let style = match node.archive {
+            Some(_) => "rounded,filled,dashed",
+            None => "rounded,filled",
+        };
+        out.push_str(&format!(
+            "    {} [label=\"{}\\n{}\", fillcolor=\"{}\", style=\"{}\"];\n",
+            node.name,
+            node.name,
+            node.label(),
+            color,
+            style
+        ));
+    }
+    for (parent, child, relation) in &edges {
+        out.push_str(&format!(
+            "    {} -> {} [label=\"{}\"];\n",
+            parent, child, relation
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dot, mermaid};
+    use crate::crud::test_model;
+
+    #[test]
+    fn renders_hierarchy_with_kinds_archive_marks_and_relations() {
+        let model = test_model(
+            r#"
+            #[diagram]
+            MyRepo;
+            archive root Project {
+                ordered_children: Task
+            }
+            ordered Task {
+                parent: Project
+            }
+            "#,
+        )
+        .unwrap();
+
+        let mermaid = mermaid(&model);
+        assert!(mermaid.starts_with("flowchart TD\n"));
+        assert!(mermaid.contains("    Project[\"Project<br/>root, archive\"]:::root\n"));
+        assert!(mermaid.contains("    Project -->|ordered| Task\n"));
+        assert!(mermaid.contains("    class Project archived\n"));
+
+        let dot = dot(&model);
+        assert!(dot.starts_with("digraph MyRepo {\n"));
+        assert!(dot.contains("style=\"rounded,filled,dashed\""));
+        assert!(dot.contains("    Project -> Task [label=\"ordered\"];\n"));
+    }
+}
@@ -0,0 +1,137 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+
+use crate::crud::model::ConfigModel;
+
+pub fn generate(model: &ConfigModel) -> TokenStream {
+    if !model.events {
+        return TokenStream::new();
+    }
+    let repo_name = &model.repository_name;
+    let event_enum = event_enum_ident_for(repo_name);
+    let sink_trait = event_sink_trait_ident_for(repo_name);
+    let channel_sink = Ident::new(&format!("{}ChannelEventSink", repo_name), repo_name.span());
+    let noop_sink = noop_event_sink_ident_for(repo_name);
+
+    // Only standard objects can be updated in place; everything else is
+    // created (or overwritten) and deleted.
+    let standard_names = model
+        .ordered_objects
+        .iter()
+        .chain(model.unordered_objects.iter())
+        .map(|object| &object.name);
+    let other_names = model
+        .batch_objects
+        .iter()
+        .map(|object| &object.name)
+        .chain(model.singleton_objects.iter().map(|object| &object.name))
+        .chain(
+            model
+                .indexed_singleton_objects
+                .iter()
+                .map(|object| &object.name),
+        );
+    let variants = standard_names
+        .map(|ty_ident| gen_variants(ty_ident, true, model.is_soft_delete(ty_ident)))
+        .chain(other_names.map(|ty_ident| gen_variants(ty_ident, false, false)));
+
+    quote! {
+        /// Change to an object in the repository. `parent_id` is the ID of the
+        /// object's parent, and `None` for root objects.
+        #[derive(Debug, Clone, PartialEq)]
+        pub enum #event_enum {
+            #(#variants)*
+        }
+
+        /// Destination for the events published by the repository's hooked
+        /// managers after each successful mutation.
+        pub trait #sink_trait: ::std::marker::Send + ::std::marker::Sync {
+            fn publish(&self, event: #event_enum) -> ::std::result::Result<(), ::fractic_server_error::ServerError>;
+        }
+
+        /// Sink used until one is provided with `with_event_sink`.
+        pub struct #noop_sink;
+        impl #sink_trait for #noop_sink {
+            fn publish(&self, _event: #event_enum) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+                ::std::result::Result::Ok(())
+            }
+        }
+
+        /// In-process sink forwarding events to a channel, mainly for tests.
+        pub struct #channel_sink {
+            sender: ::std::sync::mpsc::Sender<#event_enum>,
+        }
+        impl #channel_sink {
+            pub fn new() -> (Self, ::std::sync::mpsc::Receiver<#event_enum>) {
+                let (sender, receiver) = ::std::sync::mpsc::channel();
+                (Self { sender }, receiver)
+            }
+        }
+        impl #sink_trait for #channel_sink {
+            fn publish(&self, event: #event_enum) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+                // A dropped receiver just means nobody is listening anymore;
+                // that should not fail the mutation.
+                let _ = self.sender.send(event);
+                ::std::result::Result::Ok(())
+            }
+        }
+    }
+}
+
+fn gen_variants(ty_ident: &Ident, updatable: bool, restorable: bool) -> TokenStream {
+    let fields = quote! {
+        id: ::fractic_aws_dynamo::schema::PkSk,
+        parent_id: ::std::option::Option<::fractic_aws_dynamo::schema::PkSk>,
+    };
+    let created = created_variant_for(ty_ident);
+    let deleted = deleted_variant_for(ty_ident);
+    let updated = if updatable {
+        let updated = updated_variant_for(ty_ident);
+        quote! { #updated { #fields }, }
+    } else {
+        TokenStream::new()
+    };
+    // Restoring a soft-deleted object also brings back its descendants, which
+    // get no events of their own.
+    let restored = if restorable {
+        let restored = restored_variant_for(ty_ident);
+        quote! { #restored { #fields }, }
+    } else {
+        TokenStream::new()
+    };
+    quote! {
+        #created { #fields },
+        #updated
+        #deleted { #fields },
+        #restored
+    }
+}
+
+pub(crate) fn event_enum_ident_for(repo_name: &Ident) -> Ident {
+    Ident::new(&format!("{}Event", repo_name), repo_name.span())
+}
+
+pub(crate) fn event_sink_trait_ident_for(repo_name: &Ident) -> Ident {
+    Ident::new(&format!("{}EventSink", repo_name), repo_name.span())
+}
+
+pub(crate) fn noop_event_sink_ident_for(repo_name: &Ident) -> Ident {
+    Ident::new(&format!("{}NoopEventSink", repo_name), repo_name.span())
+}
+
+pub(crate) fn created_variant_for(ty_ident: &Ident) -> Ident {
+    Ident::new(&format!("{}Created", ty_ident), ty_ident.span())
+}
+
+pub(crate) fn updated_variant_for(ty_ident: &Ident) -> Ident {
+    Ident::new(&format!("{}Updated", ty_ident), ty_ident.span())
+}
+
+pub(crate) fn deleted_variant_for(ty_ident: &Ident) -> Ident {
+    Ident::new(&format!("{}Deleted", ty_ident), ty_ident.span())
+}
+
+pub(crate) fn restored_variant_for(ty_ident: &Ident) -> Ident {
+    Ident::new(&format!("{}Restored", ty_ident), ty_ident.span())
+}
@@ -0,0 +1,96 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+
+use crate::crud::model::ConfigModel;
+
+pub fn generate(model: &ConfigModel) -> TokenStream {
+    let filter_structs = model
+        .ordered_objects
+        .iter()
+        .map(|object| (&object.name, &object.filterable))
+        .chain(
+            model
+                .unordered_objects
+                .iter()
+                .map(|object| (&object.name, &object.filterable)),
+        )
+        .chain(
+            model
+                .batch_objects
+                .iter()
+                .map(|object| (&object.name, &object.filterable)),
+        )
+        .chain(
+            model
+                .indexed_singleton_objects
+                .iter()
+                .map(|object| (&object.name, &object.filterable)),
+        )
+        .filter(|(_, fields)| !fields.is_empty())
+        .map(|(ty_ident, fields)| gen_filter_struct(ty_ident, fields));
+
+    quote! {
+        #(#filter_structs)*
+    }
+}
+
+fn gen_filter_struct(ty_ident: &Ident, fields: &[Ident]) -> TokenStream {
+    let filter_ident = filter_ident_for(ty_ident);
+    let ty_data_ident = quote! {
+        <#ty_ident as ::fractic_aws_dynamo::schema::DynamoObject>::Data
+    };
+
+    let field_methods = fields.iter().map(|field| {
+        // One builder method per comparison operator supported by the storage
+        // layer's filter expressions.
+        let ops = [
+            ("eq", quote! { Eq }),
+            ("ne", quote! { Ne }),
+            ("lt", quote! { Lt }),
+            ("le", quote! { Le }),
+            ("gt", quote! { Gt }),
+            ("ge", quote! { Ge }),
+            ("begins_with", quote! { BeginsWith }),
+        ];
+        let methods = ops.iter().map(|(suffix, op)| {
+            let method_ident = Ident::new(&format!("{}_{}", field, suffix), field.span());
+            quote! {
+                pub fn #method_ident(mut self, value: impl ::serde::Serialize) -> Self {
+                    // Fails to compile if the field does not exist on the data
+                    // type, so typos are caught at the declaration site.
+                    let _ = |__data: &#ty_data_ident| {
+                        let _ = &__data.#field;
+                    };
+                    self.conditions.push(::fractic_aws_dynamo::ext::crud::FilterCondition::new(
+                        stringify!(#field),
+                        ::fractic_aws_dynamo::ext::crud::FilterOp::#op,
+                        value,
+                    ));
+                    self
+                }
+            }
+        });
+        quote! { #(#methods)* }
+    });
+
+    quote! {
+        #[derive(Debug, Clone, Default)]
+        pub struct #filter_ident {
+            conditions: ::std::vec::Vec<::fractic_aws_dynamo::ext::crud::FilterCondition>,
+        }
+        impl #filter_ident {
+            pub fn new() -> Self {
+                Self::default()
+            }
+            pub fn into_conditions(self) -> ::std::vec::Vec<::fractic_aws_dynamo::ext::crud::FilterCondition> {
+                self.conditions
+            }
+            #(#field_methods)*
+        }
+    }
+}
+
+pub(crate) fn filter_ident_for(ty_ident: &Ident) -> Ident {
+    Ident::new(&format!("{}Filter", ty_ident), ty_ident.span())
+}
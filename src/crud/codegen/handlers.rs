@@ -2,9 +2,19 @@ use proc_macro2::TokenStream;
 use quote::quote;
 use syn::Ident;
 
+use super::{
+    client::{
+        client_error_ident_for, client_objects, loopback_ident_for, request_enum_ident_for,
+        transport_trait_ident_for,
+    },
+    hooks::manager_ident_for,
+    repository::version_conflict_ident_for,
+    validation::gen_data_validation,
+};
 use crate::{
     crud::model::{BatchDef, ConfigModel, IndexedSingletonDef, SingletonDef, StandardDef},
     helpers::to_snake_case,
+    instrument::{OperationSpan, instrument},
 };
 
 pub fn generate(model: &ConfigModel) -> TokenStream {
@@ -23,6 +33,19 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
     // and OwnedCrud specs require handlers of type (CrudOperation<T>) ->
     // Result<impl serde::Serialize, ServerError>, so this enum satisfies that
     // requirement.
+    let (page_variant, page_limit) = if model.paginated {
+        (
+            quote! {
+                Page {
+                    items: ::std::vec::Vec<T>,
+                    next_cursor: ::std::option::Option<::std::string::String>,
+                },
+            },
+            gen_page_limit(),
+        )
+    } else {
+        (TokenStream::new(), TokenStream::new())
+    };
     let crud_result_enum = quote! {
         #[derive(::serde::Serialize)]
         #[serde(untagged)]
@@ -34,8 +57,11 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
             CreatedIds { created_ids: ::std::vec::Vec<::fractic_aws_dynamo::schema::PkSk> },
             Item(T),
             Items(::std::vec::Vec<T>),
+            #page_variant
             Unit,
         }
+
+        #page_limit
     };
 
     // Build handlers for root types.
@@ -43,27 +69,27 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
         .ordered_objects
         .iter()
         .filter(|root| root.parents.is_none())
-        .map(|root| gen_root_standard_handler(root, true, repo_name))
+        .map(|root| gen_root_standard_handler(root, true, model))
         .chain(
             model
                 .unordered_objects
                 .iter()
                 .filter(|root| root.parents.is_none())
-                .map(|root| gen_root_standard_handler(root, false, repo_name)),
+                .map(|root| gen_root_standard_handler(root, false, model)),
         )
         .chain(
             model
                 .batch_objects
                 .iter()
                 .filter(|batch| batch.parents.is_none())
-                .map(|batch| gen_root_batch_handler(batch, repo_name)),
+                .map(|batch| gen_root_batch_handler(batch, model)),
         )
         .chain(
             model
                 .singleton_objects
                 .iter()
                 .filter(|singleton| singleton.parents.is_none())
-                .map(|singleton| gen_root_singleton_handler(singleton, repo_name)),
+                .map(|singleton| gen_root_singleton_handler(singleton, model)),
         )
         .chain(
             model
@@ -71,7 +97,7 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
                 .iter()
                 .filter(|indexed_singleton| indexed_singleton.parents.is_none())
                 .map(|indexed_singleton| {
-                    gen_root_indexed_singleton_handler(indexed_singleton, repo_name)
+                    gen_root_indexed_singleton_handler(indexed_singleton, model)
                 }),
         )
         .collect::<Vec<_>>();
@@ -81,27 +107,27 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
         .ordered_objects
         .iter()
         .filter(|child| child.parents.is_some())
-        .map(|child| gen_child_standard_handler(child, true, repo_name))
+        .map(|child| gen_child_standard_handler(child, true, model))
         .chain(
             model
                 .unordered_objects
                 .iter()
                 .filter(|child| child.parents.is_some())
-                .map(|child| gen_child_standard_handler(child, false, repo_name)),
+                .map(|child| gen_child_standard_handler(child, false, model)),
         )
         .chain(
             model
                 .batch_objects
                 .iter()
                 .filter(|batch| batch.parents.is_some())
-                .map(|batch| gen_child_batch_handler(batch, repo_name)),
+                .map(|batch| gen_child_batch_handler(batch, model)),
         )
         .chain(
             model
                 .singleton_objects
                 .iter()
                 .filter(|singleton| singleton.parents.is_some())
-                .map(|singleton| gen_child_singleton_handler(singleton, repo_name)),
+                .map(|singleton| gen_child_singleton_handler(singleton, model)),
         )
         .chain(
             model
@@ -109,11 +135,13 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
                 .iter()
                 .filter(|indexed_singleton| indexed_singleton.parents.is_some())
                 .map(|indexed_singleton| {
-                    gen_child_indexed_singleton_handler(indexed_singleton, repo_name)
+                    gen_child_indexed_singleton_handler(indexed_singleton, model)
                 }),
         )
         .collect::<Vec<_>>();
 
+    let loopback = gen_loopback_transport(model);
+
     // Compose generation macro.
     let root_handlers_iter = root_handlers.into_iter();
     let child_handlers_iter = child_handlers.into_iter();
@@ -147,6 +175,7 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
                 #crud_result_enum
                 #(#root_handlers_iter)*
                 #(#child_handlers_iter)*
+                #loopback
             };
         }
 
@@ -155,18 +184,56 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
     }
 }
 
+/// With the `client` option, a CRUD client transport which passes operations
+/// straight to the handlers above, for testing clients without a server.
+fn gen_loopback_transport(model: &ConfigModel) -> TokenStream {
+    if !model.client {
+        return TokenStream::new();
+    }
+    let repo_name = &model.repository_name;
+    let loopback_ident = loopback_ident_for(repo_name);
+    let transport_ident = transport_trait_ident_for(repo_name);
+    let request_ident = request_enum_ident_for(repo_name);
+    let error_ident = client_error_ident_for(repo_name);
+    let arms = client_objects(model).into_iter().map(|object| {
+        let ty_ident = object.name;
+        let handler_ident = method_ident_for_with_suffix("manage", ty_ident, "_handler");
+        quote! {
+            #request_ident::#ty_ident(operation) => ::serde_json::to_value(#handler_ident(operation).await?),
+        }
+    });
+    quote! {
+        /// CRUD client transport calling the handlers in-process.
+        pub struct #loopback_ident;
+
+        impl #transport_ident for #loopback_ident {
+            async fn send(
+                &self,
+                request: #request_ident,
+            ) -> ::std::result::Result<::serde_json::Value, ::fractic_server_error::ServerError> {
+                let __response = match request {
+                    #(#arms)*
+                };
+                __response.map_err(|e| #error_ident::new(&e.to_string()))
+            }
+        }
+    }
+}
+
 fn gen_root_standard_handler(
     root: &StandardDef,
     is_ordered: bool,
-    repo_name: &Ident,
+    model: &ConfigModel,
 ) -> TokenStream {
     let ty_ident = &root.name;
-    let manager_ident = method_ident_for("manage", ty_ident);
+    let manager_ident = manager_ident_for(ty_ident, model.is_intercepted(ty_ident));
     let handler_ident = method_ident_for_with_suffix("manage", ty_ident, "_handler");
     let has_children = root.has_children();
 
+    let list_pattern = list_pattern(model);
+    let list_query = list_query(model, &manager_ident, false);
     let list_arm = quote! {
-        List { parent_id } => {
+        #list_pattern => {
             if parent_id.is_some() {
                 return ::std::result::Result::Err(
                     ::fractic_aws_apigateway::InvalidCrudRequestParameters::new(
@@ -174,8 +241,7 @@ fn gen_root_standard_handler(
                     ).into()
                 );
             }
-            let __items = __repo.#manager_ident().query_all().await?;
-            ::std::result::Result::Ok(__CrudOperationResult::Items(__items))
+            #list_query
         },
     };
     let create_arm = if is_ordered {
@@ -282,18 +348,49 @@ fn gen_root_standard_handler(
                     ).into()
                 );
             };
-            let __futs = ids.into_iter().map(|id| __repo.#manager_ident().get(id));
+            let __manager = __repo.#manager_ident();
+            let __futs = ids.into_iter().map(|id| __manager.get(id));
             let __items = ::futures_util::future::try_join_all(__futs).await?;
             ::std::result::Result::Ok(__CrudOperationResult::Items(__items))
         },
     };
-    let update_arm = quote! {
-        Update { item } => {
-            __repo.#manager_ident().update(&item).await?;
-            ::std::result::Result::Ok(__CrudOperationResult::Unit)
-        },
+    let update_arm = gen_update_arm(model, root, &manager_ident);
+    // Soft-deleted objects are only ever tombstoned through the API. The
+    // storage layer tombstones descendants together with the item, so a
+    // non-recursive request cannot be honored.
+    let reject_non_recursive = if has_children {
+        quote! {
+            if non_recursive {
+                return ::std::result::Result::Err(
+                    ::fractic_aws_apigateway::InvalidCrudRequestParameters::new(
+                        &format!("{} uses soft delete, which does not support non_recursive=true", stringify!(#ty_ident))
+                    ).into()
+                );
+            }
+        }
+    } else {
+        quote! { let _ = non_recursive; }
     };
-    let delete_arm = if has_children {
+    let delete_arm = if root.is_soft_delete {
+        quote! {
+            Delete { item_ref, non_recursive } => {
+                let ::fractic_aws_apigateway::ItemRef::Id(id) = item_ref else {
+                    return ::std::result::Result::Err(
+                        ::fractic_aws_apigateway::InvalidCrudRequestParameters::new(
+                            &format!(
+                                "delete operations on {} require ItemRef::Id",
+                                stringify!(#ty_ident)
+                            )
+                        ).into()
+                    );
+                };
+                #reject_non_recursive
+                let __item = __placeholder_item!(#ty_ident, id);
+                __repo.#manager_ident().soft_delete(__item).await?;
+                ::std::result::Result::Ok(__CrudOperationResult::Unit)
+            },
+        }
+    } else if has_children {
         quote! {
             Delete { item_ref, non_recursive } => {
                 let ::fractic_aws_apigateway::ItemRef::Id(id) = item_ref else {
@@ -334,7 +431,29 @@ fn gen_root_standard_handler(
             },
         }
     };
-    let delete_multiple_arm = if has_children {
+    let delete_multiple_arm = if root.is_soft_delete {
+        quote! {
+            DeleteMultiple { item_refs, non_recursive } => {
+                let ::fractic_aws_apigateway::ItemRefs::Id(ids) = item_refs else {
+                    return ::std::result::Result::Err(
+                        ::fractic_aws_apigateway::InvalidCrudRequestParameters::new(
+                            &format!(
+                                "batch delete operations on {} require ItemRefs::Id",
+                                stringify!(#ty_ident)
+                            )
+                        ).into()
+                    );
+                };
+                #reject_non_recursive
+                let __items = ids
+                    .into_iter()
+                    .map(|id| __placeholder_item!(#ty_ident, id))
+                    .collect::<::std::vec::Vec<_>>();
+                __repo.#manager_ident().batch_soft_delete(__items).await?;
+                ::std::result::Result::Ok(__CrudOperationResult::Unit)
+            },
+        }
+    } else if has_children {
         quote! {
             DeleteMultiple { item_refs, non_recursive } => {
                 let ::fractic_aws_apigateway::ItemRefs::Id(ids) = item_refs else {
@@ -384,7 +503,22 @@ fn gen_root_standard_handler(
             },
         }
     };
-    let delete_all_arm = if has_children {
+    let delete_all_arm = if root.is_soft_delete {
+        quote! {
+            DeleteAll { parent_id, non_recursive } => {
+                #reject_non_recursive
+                if parent_id.is_some() {
+                    return ::std::result::Result::Err(
+                        ::fractic_aws_apigateway::InvalidCrudRequestParameters::new(
+                            &format!("delete-all operations on {} do not allow a parent ID", stringify!(#ty_ident))
+                        ).into()
+                    );
+                }
+                __repo.#manager_ident().batch_soft_delete_all().await?;
+                ::std::result::Result::Ok(__CrudOperationResult::Unit)
+            },
+        }
+    } else if has_children {
         quote! {
             DeleteAll { parent_id, non_recursive } => {
                 if parent_id.is_some() {
@@ -430,35 +564,34 @@ fn gen_root_standard_handler(
         },
     };
 
-    quote! {
-        pub async fn #handler_ident(
-            operation: ::fractic_aws_apigateway::CrudOperation<#ty_ident>
-        ) -> ::std::result::Result<__CrudOperationResult<#ty_ident>, ::fractic_server_error::ServerError> {
-            use ::fractic_aws_apigateway::CrudOperation::*;
-            let __repo: ::std::sync::Arc<dyn #repo_name> = { __repo_init!() };
-            match operation {
-                #list_arm
-                #create_arm
-                #create_multiple_arm
-                #read_arm
-                #read_multiple_arm
-                #update_arm
-                #delete_arm
-                #delete_multiple_arm
-                #delete_all_arm
-                #replace_all_arm
-            }
-        }
-    }
+    gen_standard_handler_fn(
+        root,
+        model,
+        &handler_ident,
+        quote! {
+            #list_arm
+            #create_arm
+            #create_multiple_arm
+            #read_arm
+            #read_multiple_arm
+            #update_arm
+            #delete_arm
+            #delete_multiple_arm
+            #delete_all_arm
+            #replace_all_arm
+        },
+    )
 }
 
-fn gen_root_batch_handler(batch: &BatchDef, repo_name: &Ident) -> TokenStream {
+fn gen_root_batch_handler(batch: &BatchDef, model: &ConfigModel) -> TokenStream {
     let ty_ident = &batch.name;
-    let manager_ident = method_ident_for("manage", ty_ident);
+    let manager_ident = manager_ident_for(ty_ident, model.is_intercepted(ty_ident));
     let handler_ident = method_ident_for_with_suffix("manage", ty_ident, "_handler");
 
+    let list_pattern = list_pattern(model);
+    let list_query = list_query(model, &manager_ident, false);
     let list_arm = quote! {
-        List { parent_id } => {
+        #list_pattern => {
             if parent_id.is_some() {
                 return ::std::result::Result::Err(
                     ::fractic_aws_apigateway::InvalidCrudRequestParameters::new(
@@ -466,8 +599,7 @@ fn gen_root_batch_handler(batch: &BatchDef, repo_name: &Ident) -> TokenStream {
                     ).into()
                 );
             }
-            let __items = __repo.#manager_ident().query_all().await?;
-            ::std::result::Result::Ok(__CrudOperationResult::Items(__items))
+            #list_query
         },
     };
     let delete_all_arm = quote! {
@@ -512,25 +644,25 @@ fn gen_root_batch_handler(batch: &BatchDef, repo_name: &Ident) -> TokenStream {
         },
     };
 
+    let arms = quote! {
+        #list_arm
+        #delete_all_arm
+        #replace_all_arm
+        #unsupported_arm
+    };
+    let body = gen_handler_body(model, ty_ident, &handler_ident, arms);
     quote! {
         pub async fn #handler_ident(
             operation: ::fractic_aws_apigateway::CrudOperation<#ty_ident>
         ) -> ::std::result::Result<__CrudOperationResult<#ty_ident>, ::fractic_server_error::ServerError> {
-            use ::fractic_aws_apigateway::CrudOperation::*;
-            let __repo: ::std::sync::Arc<dyn #repo_name> = { __repo_init!() };
-            match operation {
-                #list_arm
-                #delete_all_arm
-                #replace_all_arm
-                #unsupported_arm
-            }
+            #body
         }
     }
 }
 
-fn gen_root_singleton_handler(singleton: &SingletonDef, repo_name: &Ident) -> TokenStream {
+fn gen_root_singleton_handler(singleton: &SingletonDef, model: &ConfigModel) -> TokenStream {
     let ty_ident = &singleton.name;
-    let manager_ident = method_ident_for("manage", ty_ident);
+    let manager_ident = manager_ident_for(ty_ident, model.is_intercepted(ty_ident));
     let handler_ident = method_ident_for_with_suffix("manage", ty_ident, "_handler");
 
     let read_arm = quote! {
@@ -634,32 +766,34 @@ fn gen_root_singleton_handler(singleton: &SingletonDef, repo_name: &Ident) -> To
         },
     };
 
+    let arms = quote! {
+        #read_arm
+        #create_arm
+        #delete_arm
+        #unsupported_arm
+    };
+    let body = gen_handler_body(model, ty_ident, &handler_ident, arms);
     quote! {
         pub async fn #handler_ident(
             operation: ::fractic_aws_apigateway::CrudOperation<#ty_ident>
         ) -> ::std::result::Result<__CrudOperationResult<#ty_ident>, ::fractic_server_error::ServerError> {
-            use ::fractic_aws_apigateway::CrudOperation::*;
-            let __repo: ::std::sync::Arc<dyn #repo_name> = { __repo_init!() };
-            match operation {
-                #read_arm
-                #create_arm
-                #delete_arm
-                #unsupported_arm
-            }
+            #body
         }
     }
 }
 
 fn gen_root_indexed_singleton_handler(
     indexed_singleton: &IndexedSingletonDef,
-    repo_name: &Ident,
+    model: &ConfigModel,
 ) -> TokenStream {
     let ty_ident = &indexed_singleton.name;
-    let manager_ident = method_ident_for("manage", ty_ident);
+    let manager_ident = manager_ident_for(ty_ident, model.is_intercepted(ty_ident));
     let handler_ident = method_ident_for_with_suffix("manage", ty_ident, "_handler");
 
+    let list_pattern = list_pattern(model);
+    let list_query = list_query(model, &manager_ident, false);
     let list_arm = quote! {
-        List { parent_id } => {
+        #list_pattern => {
             if parent_id.is_some() {
                 return ::std::result::Result::Err(
                     ::fractic_aws_apigateway::InvalidCrudRequestParameters::new(
@@ -670,8 +804,7 @@ fn gen_root_indexed_singleton_handler(
                     ).into()
                 );
             }
-            let __items = __repo.#manager_ident().query_all().await?;
-            ::std::result::Result::Ok(__CrudOperationResult::Items(__items))
+            #list_query
         },
     };
 
@@ -725,7 +858,8 @@ fn gen_root_indexed_singleton_handler(
                     ).into()
                 );
             }
-            let __futs = keys.iter().map(|key| __repo.#manager_ident().get(key));
+            let __manager = __repo.#manager_ident();
+            let __futs = keys.iter().map(|key| __manager.get(key));
             let __items = ::futures_util::future::try_join_all(__futs).await?;
             let __items = __items
                 .into_iter()
@@ -875,23 +1009,23 @@ fn gen_root_indexed_singleton_handler(
         },
     };
 
+    let arms = quote! {
+        #list_arm
+        #read_arm
+        #read_multiple_arm
+        #create_arm
+        #create_multiple_arm
+        #delete_arm
+        #delete_multiple_arm
+        #delete_all_arm
+        #unsupported_arm
+    };
+    let body = gen_handler_body(model, ty_ident, &handler_ident, arms);
     quote! {
         pub async fn #handler_ident(
             operation: ::fractic_aws_apigateway::CrudOperation<#ty_ident>
         ) -> ::std::result::Result<__CrudOperationResult<#ty_ident>, ::fractic_server_error::ServerError> {
-            use ::fractic_aws_apigateway::CrudOperation::*;
-            let __repo: ::std::sync::Arc<dyn #repo_name> = { __repo_init!() };
-            match operation {
-                #list_arm
-                #read_arm
-                #read_multiple_arm
-                #create_arm
-                #create_multiple_arm
-                #delete_arm
-                #delete_multiple_arm
-                #delete_all_arm
-                #unsupported_arm
-            }
+            #body
         }
     }
 }
@@ -899,7 +1033,7 @@ fn gen_root_indexed_singleton_handler(
 fn gen_child_standard_handler(
     child: &StandardDef,
     is_ordered: bool,
-    repo_name: &Ident,
+    model: &ConfigModel,
 ) -> TokenStream {
     let ty_ident = &child.name;
     let parent_ident = {
@@ -911,12 +1045,14 @@ fn gen_child_standard_handler(
             .expect("children must declare at least one parent");
         &parents[0]
     };
-    let manager_ident = method_ident_for("manage", ty_ident);
+    let manager_ident = manager_ident_for(ty_ident, model.is_intercepted(ty_ident));
     let handler_ident = method_ident_for_with_suffix("manage", ty_ident, "_handler");
     let has_children = child.has_children();
 
+    let list_pattern = list_pattern(model);
+    let list_query = list_query(model, &manager_ident, true);
     let list_arm = quote! {
-        List { parent_id } => {
+        #list_pattern => {
             let Some(parent_id) = parent_id else {
                 return ::std::result::Result::Err(
                     ::fractic_aws_apigateway::InvalidCrudRequestParameters::new(
@@ -925,8 +1061,7 @@ fn gen_child_standard_handler(
                 );
             };
             let __tmp_parent = __placeholder_item!(#parent_ident, parent_id);
-            let __items = __repo.#manager_ident().query_all(&__tmp_parent).await?;
-            ::std::result::Result::Ok(__CrudOperationResult::Items(__items))
+            #list_query
         },
     };
     let create_arm = if is_ordered {
@@ -1037,18 +1172,47 @@ fn gen_child_standard_handler(
                     ).into()
                 );
             };
-            let __futs = ids.into_iter().map(|id| __repo.#manager_ident().get(id));
+            let __manager = __repo.#manager_ident();
+            let __futs = ids.into_iter().map(|id| __manager.get(id));
             let __items = ::futures_util::future::try_join_all(__futs).await?;
             ::std::result::Result::Ok(__CrudOperationResult::Items(__items))
         },
     };
-    let update_arm = quote! {
-        Update { item } => {
-            __repo.#manager_ident().update(&item).await?;
-            ::std::result::Result::Ok(__CrudOperationResult::Unit)
-        },
+    let update_arm = gen_update_arm(model, child, &manager_ident);
+    // Same soft delete handling as for root objects.
+    let reject_non_recursive = if has_children {
+        quote! {
+            if non_recursive {
+                return ::std::result::Result::Err(
+                    ::fractic_aws_apigateway::InvalidCrudRequestParameters::new(
+                        &format!("{} uses soft delete, which does not support non_recursive=true", stringify!(#ty_ident))
+                    ).into()
+                );
+            }
+        }
+    } else {
+        quote! { let _ = non_recursive; }
     };
-    let delete_arm = if has_children {
+    let delete_arm = if child.is_soft_delete {
+        quote! {
+            Delete { item_ref, non_recursive } => {
+                let ::fractic_aws_apigateway::ItemRef::Id(id) = item_ref else {
+                    return ::std::result::Result::Err(
+                        ::fractic_aws_apigateway::InvalidCrudRequestParameters::new(
+                            &format!(
+                                "delete operations on {} require ItemRef::Id",
+                                stringify!(#ty_ident)
+                            )
+                        ).into()
+                    );
+                };
+                #reject_non_recursive
+                let __item = __placeholder_item!(#ty_ident, id);
+                __repo.#manager_ident().soft_delete(__item).await?;
+                ::std::result::Result::Ok(__CrudOperationResult::Unit)
+            },
+        }
+    } else if has_children {
         quote! {
             Delete { item_ref, non_recursive } => {
                 let ::fractic_aws_apigateway::ItemRef::Id(id) = item_ref else {
@@ -1089,7 +1253,29 @@ fn gen_child_standard_handler(
             },
         }
     };
-    let delete_multiple_arm = if has_children {
+    let delete_multiple_arm = if child.is_soft_delete {
+        quote! {
+            DeleteMultiple { item_refs, non_recursive } => {
+                let ::fractic_aws_apigateway::ItemRefs::Id(ids) = item_refs else {
+                    return ::std::result::Result::Err(
+                        ::fractic_aws_apigateway::InvalidCrudRequestParameters::new(
+                            &format!(
+                                "batch delete operations on {} require ItemRefs::Id",
+                                stringify!(#ty_ident)
+                            )
+                        ).into()
+                    );
+                };
+                #reject_non_recursive
+                let __items = ids
+                    .into_iter()
+                    .map(|id| __placeholder_item!(#ty_ident, id))
+                    .collect::<::std::vec::Vec<_>>();
+                __repo.#manager_ident().batch_soft_delete(__items).await?;
+                ::std::result::Result::Ok(__CrudOperationResult::Unit)
+            },
+        }
+    } else if has_children {
         quote! {
             DeleteMultiple { item_refs, non_recursive } => {
                 let ::fractic_aws_apigateway::ItemRefs::Id(ids) = item_refs else {
@@ -1139,7 +1325,23 @@ fn gen_child_standard_handler(
             },
         }
     };
-    let delete_all_arm = if has_children {
+    let delete_all_arm = if child.is_soft_delete {
+        quote! {
+            DeleteAll { parent_id, non_recursive } => {
+                #reject_non_recursive
+                let Some(parent_id) = parent_id else {
+                    return ::std::result::Result::Err(
+                        ::fractic_aws_apigateway::InvalidCrudRequestParameters::new(
+                            &format!("delete-all operations on {} require a valid parent ID", stringify!(#ty_ident))
+                        ).into()
+                    );
+                };
+                let __tmp_parent = __placeholder_item!(#parent_ident, parent_id);
+                __repo.#manager_ident().batch_soft_delete_all(&__tmp_parent).await?;
+                ::std::result::Result::Ok(__CrudOperationResult::Unit)
+            },
+        }
+    } else if has_children {
         quote! {
             DeleteAll { parent_id, non_recursive } => {
                 let Some(parent_id) = parent_id else {
@@ -1187,29 +1389,26 @@ fn gen_child_standard_handler(
         },
     };
 
-    quote! {
-        pub async fn #handler_ident(
-            operation: ::fractic_aws_apigateway::CrudOperation<#ty_ident>
-        ) -> ::std::result::Result<__CrudOperationResult<#ty_ident>, ::fractic_server_error::ServerError> {
-            use ::fractic_aws_apigateway::CrudOperation::*;
-            let __repo: ::std::sync::Arc<dyn #repo_name> = { __repo_init!() };
-            match operation {
-                #list_arm
-                #create_arm
-                #create_multiple_arm
-                #read_arm
-                #read_multiple_arm
-                #update_arm
-                #delete_arm
-                #delete_multiple_arm
-                #delete_all_arm
-                #replace_all_arm
-            }
-        }
-    }
+    gen_standard_handler_fn(
+        child,
+        model,
+        &handler_ident,
+        quote! {
+            #list_arm
+            #create_arm
+            #create_multiple_arm
+            #read_arm
+            #read_multiple_arm
+            #update_arm
+            #delete_arm
+            #delete_multiple_arm
+            #delete_all_arm
+            #replace_all_arm
+        },
+    )
 }
 
-fn gen_child_batch_handler(batch: &BatchDef, repo_name: &Ident) -> TokenStream {
+fn gen_child_batch_handler(batch: &BatchDef, model: &ConfigModel) -> TokenStream {
     let ty_ident = &batch.name;
     let parent_ident = {
         // These idents are used only to create placeholder objects, so we can
@@ -1220,11 +1419,13 @@ fn gen_child_batch_handler(batch: &BatchDef, repo_name: &Ident) -> TokenStream {
             .expect("batch children must declare at least one parent");
         &parents[0]
     };
-    let manager_ident = method_ident_for("manage", ty_ident);
+    let manager_ident = manager_ident_for(ty_ident, model.is_intercepted(ty_ident));
     let handler_ident = method_ident_for_with_suffix("manage", ty_ident, "_handler");
 
+    let list_pattern = list_pattern(model);
+    let list_query = list_query(model, &manager_ident, true);
     let list_arm = quote! {
-        List { parent_id } => {
+        #list_pattern => {
             let Some(parent_id) = parent_id else {
                 return ::std::result::Result::Err(
                     ::fractic_aws_apigateway::InvalidCrudRequestParameters::new(
@@ -1233,8 +1434,7 @@ fn gen_child_batch_handler(batch: &BatchDef, repo_name: &Ident) -> TokenStream {
                 );
             };
             let __tmp_parent = __placeholder_item!(#parent_ident, parent_id);
-            let __items = __repo.#manager_ident().query_all(&__tmp_parent).await?;
-            ::std::result::Result::Ok(__CrudOperationResult::Items(__items))
+            #list_query
         },
     };
     let delete_all_arm = quote! {
@@ -1281,23 +1481,23 @@ fn gen_child_batch_handler(batch: &BatchDef, repo_name: &Ident) -> TokenStream {
         },
     };
 
+    let arms = quote! {
+        #list_arm
+        #delete_all_arm
+        #replace_all_arm
+        #unsupported_arm
+    };
+    let body = gen_handler_body(model, ty_ident, &handler_ident, arms);
     quote! {
         pub async fn #handler_ident(
             operation: ::fractic_aws_apigateway::CrudOperation<#ty_ident>
         ) -> ::std::result::Result<__CrudOperationResult<#ty_ident>, ::fractic_server_error::ServerError> {
-            use ::fractic_aws_apigateway::CrudOperation::*;
-            let __repo: ::std::sync::Arc<dyn #repo_name> = { __repo_init!() };
-            match operation {
-                #list_arm
-                #delete_all_arm
-                #replace_all_arm
-                #unsupported_arm
-            }
+            #body
         }
     }
 }
 
-fn gen_child_singleton_handler(singleton: &SingletonDef, repo_name: &Ident) -> TokenStream {
+fn gen_child_singleton_handler(singleton: &SingletonDef, model: &ConfigModel) -> TokenStream {
     let ty_ident = &singleton.name;
     let parent_ident = {
         let parents = singleton
@@ -1306,7 +1506,7 @@ fn gen_child_singleton_handler(singleton: &SingletonDef, repo_name: &Ident) -> T
             .expect("singleton children must declare at least one parent");
         &parents[0]
     };
-    let manager_ident = method_ident_for("manage", ty_ident);
+    let manager_ident = manager_ident_for(ty_ident, model.is_intercepted(ty_ident));
     let handler_ident = method_ident_for_with_suffix("manage", ty_ident, "_handler");
 
     let read_arm = quote! {
@@ -1413,25 +1613,25 @@ fn gen_child_singleton_handler(singleton: &SingletonDef, repo_name: &Ident) -> T
         },
     };
 
+    let arms = quote! {
+        #read_arm
+        #create_arm
+        #delete_arm
+        #unsupported_arm
+    };
+    let body = gen_handler_body(model, ty_ident, &handler_ident, arms);
     quote! {
         pub async fn #handler_ident(
             operation: ::fractic_aws_apigateway::CrudOperation<#ty_ident>
         ) -> ::std::result::Result<__CrudOperationResult<#ty_ident>, ::fractic_server_error::ServerError> {
-            use ::fractic_aws_apigateway::CrudOperation::*;
-            let __repo: ::std::sync::Arc<dyn #repo_name> = { __repo_init!() };
-            match operation {
-                #read_arm
-                #create_arm
-                #delete_arm
-                #unsupported_arm
-            }
+            #body
         }
     }
 }
 
 fn gen_child_indexed_singleton_handler(
     indexed_singleton: &IndexedSingletonDef,
-    repo_name: &Ident,
+    model: &ConfigModel,
 ) -> TokenStream {
     let ty_ident = &indexed_singleton.name;
     let parent_ident = {
@@ -1441,11 +1641,13 @@ fn gen_child_indexed_singleton_handler(
             .expect("indexed singleton children must declare at least one parent");
         &parents[0]
     };
-    let manager_ident = method_ident_for("manage", ty_ident);
+    let manager_ident = manager_ident_for(ty_ident, model.is_intercepted(ty_ident));
     let handler_ident = method_ident_for_with_suffix("manage", ty_ident, "_handler");
 
+    let list_pattern = list_pattern(model);
+    let list_query = list_query(model, &manager_ident, true);
     let list_arm = quote! {
-        List { parent_id } => {
+        #list_pattern => {
             let Some(parent_id) = parent_id else {
                 return ::std::result::Result::Err(
                     ::fractic_aws_apigateway::InvalidCrudRequestParameters::new(
@@ -1457,8 +1659,7 @@ fn gen_child_indexed_singleton_handler(
                 );
             };
             let __tmp_parent = __placeholder_item!(#parent_ident, parent_id);
-            let __items = __repo.#manager_ident().query_all(&__tmp_parent).await?;
-            ::std::result::Result::Ok(__CrudOperationResult::Items(__items))
+            #list_query
         },
     };
 
@@ -1514,7 +1715,8 @@ fn gen_child_indexed_singleton_handler(
                 );
             };
             let __tmp_parent = __placeholder_item!(#parent_ident, parent_id);
-            let __futs = keys.iter().map(|key| __repo.#manager_ident().get(&__tmp_parent, key));
+            let __manager = __repo.#manager_ident();
+            let __futs = keys.iter().map(|key| __manager.get(&__tmp_parent, key));
             let __items = ::futures_util::future::try_join_all(__futs).await?;
             let __items = __items
                 .into_iter()
@@ -1669,31 +1871,241 @@ fn gen_child_indexed_singleton_handler(
         },
     };
 
+    let arms = quote! {
+        #list_arm
+        #read_arm
+        #read_multiple_arm
+        #create_arm
+        #create_multiple_arm
+        #delete_arm
+        #delete_multiple_arm
+        #delete_all_arm
+        #unsupported_arm
+    };
+    let body = gen_handler_body(model, ty_ident, &handler_ident, arms);
     quote! {
         pub async fn #handler_ident(
             operation: ::fractic_aws_apigateway::CrudOperation<#ty_ident>
         ) -> ::std::result::Result<__CrudOperationResult<#ty_ident>, ::fractic_server_error::ServerError> {
-            use ::fractic_aws_apigateway::CrudOperation::*;
-            let __repo: ::std::sync::Arc<dyn #repo_name> = { __repo_init!() };
-            match operation {
-                #list_arm
-                #read_arm
-                #read_multiple_arm
-                #create_arm
-                #create_multiple_arm
-                #delete_arm
-                #delete_multiple_arm
-                #delete_all_arm
-                #unsupported_arm
+            #body
+        }
+    }
+}
+
+/// With the `paginated` option, the page size helper shared by all list arms.
+fn gen_page_limit() -> TokenStream {
+    quote! {
+        // Page size used by paginated list operations when the caller provides
+        // a page token but no explicit limit.
+        const __DEFAULT_PAGE_LIMIT: usize = 100;
+
+        // Page size requested by a paginated list operation. An explicit limit
+        // of 0 could never make progress through the pages, so it is rejected.
+        #[allow(dead_code)]
+        fn __page_limit(
+            limit: ::std::option::Option<usize>,
+        ) -> ::std::result::Result<usize, ::fractic_server_error::ServerError> {
+            match limit {
+                ::std::option::Option::Some(0) => ::std::result::Result::Err(
+                    ::fractic_aws_apigateway::InvalidCrudRequestParameters::new(
+                        "list limit must be at least 1"
+                    ).into()
+                ),
+                ::std::option::Option::Some(limit) => ::std::result::Result::Ok(limit),
+                ::std::option::Option::None => ::std::result::Result::Ok(__DEFAULT_PAGE_LIMIT),
             }
         }
     }
 }
 
-fn method_ident_for(prefix: &str, ident: &Ident) -> Ident {
-    let snake = to_snake_case(&ident.to_string());
-    let name = format!("{}_{}", prefix, snake);
-    Ident::new(&name, ident.span())
+/// Pattern for list operations. The page fields only exist on
+/// `CrudOperation::List` in API Gateway versions supporting pagination, so
+/// they are only named with the `paginated` option.
+fn list_pattern(model: &ConfigModel) -> TokenStream {
+    if model.paginated {
+        quote! { List { parent_id, page_token, limit } }
+    } else {
+        quote! { List { parent_id, .. } }
+    }
+}
+
+/// Tail of a list arm, once the parent ID has been checked (and, for child
+/// objects, bound to `__tmp_parent`).
+fn list_query(model: &ConfigModel, manager_ident: &Ident, is_child: bool) -> TokenStream {
+    let parent_arg = is_child.then(|| quote! { &__tmp_parent });
+    let query_all = quote! {
+        let __items = __repo.#manager_ident().query_all(#parent_arg).await?;
+    };
+    if !model.paginated {
+        return quote! {
+            #query_all
+            ::std::result::Result::Ok(__CrudOperationResult::Items(__items))
+        };
+    }
+    let parent_arg = parent_arg.map(|arg| quote! { #arg, });
+    quote! {
+        if page_token.is_none() && limit.is_none() {
+            #query_all
+            return ::std::result::Result::Ok(__CrudOperationResult::Items(__items));
+        }
+        let (__items, __next_cursor) = __repo.#manager_ident().query_page(#parent_arg page_token.as_deref(), __page_limit(limit)?).await?;
+        ::std::result::Result::Ok(__CrudOperationResult::Page { items: __items, next_cursor: __next_cursor })
+    }
+}
+
+fn gen_update_arm(model: &ConfigModel, object: &StandardDef, manager_ident: &Ident) -> TokenStream {
+    let ty_ident = &object.name;
+    if object.is_versioned {
+        // Conditional on the caller's expected version, falling back to the
+        // version carried by the submitted item.
+        let conflict_ident = version_conflict_ident_for(&model.repository_name);
+        quote! {
+            Update { item } => {
+                let __expected_version = expected_version.or(item.auto_fields.version);
+                if !__repo.#manager_ident().update_if_version(&item, __expected_version).await? {
+                    return ::std::result::Result::Err(#conflict_ident::new(stringify!(#ty_ident)).into());
+                }
+                ::std::result::Result::Ok(__CrudOperationResult::Unit)
+            },
+        }
+    } else {
+        quote! {
+            Update { item } => {
+                __repo.#manager_ident().update(&item).await?;
+                ::std::result::Result::Ok(__CrudOperationResult::Unit)
+            },
+        }
+    }
+}
+
+/// Handler function for a standard object. Versioned objects additionally get
+/// a `{handler}_with_expected_version` variant, which the plain handler
+/// forwards to without an expected version.
+fn gen_standard_handler_fn(
+    object: &StandardDef,
+    model: &ConfigModel,
+    handler_ident: &Ident,
+    arms: TokenStream,
+) -> TokenStream {
+    let ty_ident = &object.name;
+    let body = gen_handler_body(model, ty_ident, handler_ident, arms);
+    if object.is_versioned {
+        let versioned_handler_ident = Ident::new(
+            &format!("{}_with_expected_version", handler_ident),
+            handler_ident.span(),
+        );
+        quote! {
+            pub async fn #handler_ident(
+                operation: ::fractic_aws_apigateway::CrudOperation<#ty_ident>
+            ) -> ::std::result::Result<__CrudOperationResult<#ty_ident>, ::fractic_server_error::ServerError> {
+                #versioned_handler_ident(operation, ::std::option::Option::None).await
+            }
+
+            pub async fn #versioned_handler_ident(
+                operation: ::fractic_aws_apigateway::CrudOperation<#ty_ident>,
+                expected_version: ::std::option::Option<u64>,
+            ) -> ::std::result::Result<__CrudOperationResult<#ty_ident>, ::fractic_server_error::ServerError> {
+                #body
+            }
+        }
+    } else {
+        quote! {
+            pub async fn #handler_ident(
+                operation: ::fractic_aws_apigateway::CrudOperation<#ty_ident>
+            ) -> ::std::result::Result<__CrudOperationResult<#ty_ident>, ::fractic_server_error::ServerError> {
+                #body
+            }
+        }
+    }
+}
+
+/// Body shared by all handler functions: dispatch the operation to the arms
+/// applicable to the object, after rejecting invalid data for objects with the
+/// `validate` prefix. With the `tracing` feature, this is wrapped in a span
+/// recording the operation and the parent / item ID it refers to.
+fn gen_handler_body(
+    model: &ConfigModel,
+    ty_ident: &Ident,
+    handler_ident: &Ident,
+    arms: TokenStream,
+) -> TokenStream {
+    let repo_name = &model.repository_name;
+    let validate_payload = if model.is_validated(ty_ident) {
+        let validate_one = gen_data_validation(model, ty_ident, quote! { data }, false);
+        let validate_many = gen_data_validation(model, ty_ident, quote! { data.iter() }, true);
+        let validate_item = gen_data_validation(model, ty_ident, quote! { &item.data }, false);
+        quote! {
+            match &operation {
+                Create { data, .. } => { #validate_one }
+                CreateMultiple { data, .. } | ReplaceAll { data, .. } => { #validate_many }
+                Update { item } => { #validate_item }
+                _ => {}
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+    let body = quote! {
+        use ::fractic_aws_apigateway::CrudOperation::*;
+        #validate_payload
+        let __repo: ::std::sync::Arc<dyn #repo_name> = { __repo_init!() };
+        match operation {
+            #arms
+        }
+    };
+    let span = OperationSpan::new(handler_ident.to_string()).record(quote! {
+        let (__operation, __parent_id, __item_id) = {
+            use ::fractic_aws_apigateway::{CrudOperation::*, ItemRef, ItemRefs};
+            let __operation = match &operation {
+                List { .. } => "list",
+                Create { .. } => "create",
+                CreateMultiple { .. } => "create_multiple",
+                Read { .. } => "read",
+                ReadMultiple { .. } => "read_multiple",
+                Update { .. } => "update",
+                Delete { .. } => "delete",
+                DeleteMultiple { .. } => "delete_multiple",
+                DeleteAll { .. } => "delete_all",
+                ReplaceAll { .. } => "replace_all",
+            };
+            let (__parent_id, __item_id) = match &operation {
+                List { parent_id, .. }
+                | Create { parent_id, .. }
+                | CreateMultiple { parent_id, .. }
+                | DeleteAll { parent_id, .. }
+                | ReplaceAll { parent_id, .. } => (parent_id.as_ref(), ::std::option::Option::None),
+                Read { item_ref } | Delete { item_ref, .. } => match item_ref {
+                    ItemRef::Id(id) => (::std::option::Option::None, ::std::option::Option::Some(id)),
+                    ItemRef::Key { parent_id, .. } | ItemRef::None { parent_id } => {
+                        (parent_id.as_ref(), ::std::option::Option::None)
+                    }
+                },
+                ReadMultiple { item_refs } | DeleteMultiple { item_refs, .. } => match item_refs {
+                    ItemRefs::Key { parent_id, .. } => (parent_id.as_ref(), ::std::option::Option::None),
+                    _ => (::std::option::Option::None, ::std::option::Option::None),
+                },
+                Update { item } => (
+                    ::std::option::Option::None,
+                    ::std::option::Option::Some(::fractic_aws_dynamo::schema::DynamoObject::id(item)),
+                ),
+            };
+            (__operation, __parent_id, __item_id)
+        };
+        __span.record("operation", __operation);
+        if let ::std::option::Option::Some(id) = __parent_id {
+            __span.record("parent_id", ::tracing::field::debug(id));
+        }
+        if let ::std::option::Option::Some(id) = __item_id {
+            __span.record("item_id", ::tracing::field::debug(id));
+        }
+    });
+    instrument(
+        span,
+        body,
+        quote! { ::std::result::Result<__CrudOperationResult<#ty_ident>, ::fractic_server_error::ServerError> },
+        true,
+        true,
+    )
 }
 
 fn method_ident_for_with_suffix(prefix: &str, ident: &Ident, suffix: &str) -> Ident {
@@ -1701,3 +2113,73 @@ fn method_ident_for_with_suffix(prefix: &str, ident: &Ident, suffix: &str) -> Id
     let name = format!("{}_{}{}", prefix, snake, suffix);
     Ident::new(&name, ident.span())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::generate;
+    use crate::{crud::test_model, testing};
+
+    const HANDLERS: [&str; 3] = [
+        "manage_persona_handler",
+        "manage_persona_principle_handler",
+        "manage_persona_metric_handler",
+    ];
+    const DSL: &str = r#"
+        root Persona {}
+        ordered PersonaPrinciple {
+            parent: Persona
+        }
+        batch PersonaMetric {
+            parent: Persona
+        }
+    "#;
+
+    #[test]
+    fn rejects_zero_page_limits_in_every_paginated_list() {
+        let model = test_model(&format!("#[paginated] MyRepo; {}", DSL)).unwrap();
+        let items = testing::items(generate(&model));
+
+        let page_limit = testing::fn_of(&items, None, "__page_limit");
+        assert!(page_limit.body_contains(
+            ":: std :: option :: Option :: Some (0) => :: std :: result :: Result :: Err"
+        ));
+        assert!(page_limit.body_contains("\"list limit must be at least 1\""));
+        for handler in HANDLERS {
+            let handler = testing::fn_of(&items, None, handler);
+            assert!(handler.body_contains("List { parent_id , page_token , limit } =>"));
+            assert!(handler.body_contains("__page_limit (limit) ?"));
+        }
+    }
+
+    #[test]
+    fn lists_without_pagination_by_default() {
+        let model = test_model(&format!("MyRepo; {}", DSL)).unwrap();
+        let items = testing::items(generate(&model));
+
+        assert!(testing::fns(&items, "__page_limit").is_empty());
+        for handler in HANDLERS {
+            let handler = testing::fn_of(&items, None, handler);
+            assert!(handler.body_contains("List { parent_id , .. } =>"));
+            assert!(!handler.body_contains("query_page"));
+        }
+    }
+
+    #[test]
+    fn plain_handler_of_versioned_objects_delegates() {
+        let model = test_model("MyRepo; versioned root Persona {}").unwrap();
+        let items = testing::items(generate(&model));
+
+        let plain = testing::fn_of(&items, None, "manage_persona_handler");
+        assert_eq!(
+            plain.body.as_deref(),
+            Some(
+                "{ manage_persona_handler_with_expected_version (operation , :: std :: option :: \
+                 Option :: None) . await }"
+            )
+        );
+        let versioned =
+            testing::fn_of(&items, None, "manage_persona_handler_with_expected_version");
+        assert_eq!(versioned.param_names(), ["operation", "expected_version"]);
+        assert!(versioned.body_contains("update_if_version (& item , __expected_version)"));
+    }
+}
@@ -0,0 +1,970 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+
+use crate::{
+    crud::model::{BatchDef, ConfigModel, IndexedSingletonDef, SingletonDef, StandardDef},
+    helpers::to_snake_case,
+};
+
+use super::{
+    events::{
+        created_variant_for, deleted_variant_for, event_enum_ident_for, restored_variant_for,
+        updated_variant_for,
+    },
+    repository::{ObjectType, child_manage_ty, root_manage_ty},
+    repository_impl::{cache_items_field_for, cache_lists_field_for},
+};
+
+pub fn generate(model: &ConfigModel) -> TokenStream {
+    let ordered = model
+        .ordered_objects
+        .iter()
+        .filter_map(|object| gen_standard_hooks(model, object, ObjectType::Ordered));
+    let unordered = model
+        .unordered_objects
+        .iter()
+        .filter_map(|object| gen_standard_hooks(model, object, ObjectType::Unordered));
+    let batch = model
+        .batch_objects
+        .iter()
+        .filter_map(|object| gen_batch_hooks(model, object));
+    let singleton = model
+        .singleton_objects
+        .iter()
+        .filter_map(|object| gen_singleton_hooks(model, object));
+    let indexed_singleton = model
+        .indexed_singleton_objects
+        .iter()
+        .filter_map(|object| gen_indexed_singleton_hooks(model, object));
+
+    quote! {
+        #(#ordered)*
+        #(#unordered)*
+        #(#batch)*
+        #(#singleton)*
+        #(#indexed_singleton)*
+    }
+}
+
+/// Accessor used by the generated annotations and handlers to reach the
+/// manager of the given object: the hooked wrapper if its mutations are
+/// intercepted (see `ConfigModel::is_intercepted`), otherwise the repository's manager directly.
+pub(crate) fn manager_ident_for(ty_ident: &Ident, intercepted: bool) -> Ident {
+    if intercepted {
+        hooked_manage_ident_for(ty_ident)
+    } else {
+        let snake = to_snake_case(&ty_ident.to_string());
+        Ident::new(&format!("manage_{}", snake), ty_ident.span())
+    }
+}
+
+pub(crate) fn hooked_manage_ident_for(ty_ident: &Ident) -> Ident {
+    let snake = to_snake_case(&ty_ident.to_string());
+    Ident::new(&format!("manage_{}_hooked", snake), ty_ident.span())
+}
+
+pub(crate) fn hooked_manager_ident_for(ty_ident: &Ident) -> Ident {
+    Ident::new(&format!("{}HookedManager", ty_ident), ty_ident.span())
+}
+
+/// Tokens shared by all hooked managers of a given object.
+struct HookedCtx<'a> {
+    ty_ident: &'a Ident,
+    ty_data: TokenStream,
+    // `<Hooks as {Type}Hooks>`, if the object declares `hooks`.
+    hooks: Option<TokenStream>,
+    // `{Repo}Event`, if the repository emits events.
+    event_enum: Option<Ident>,
+    is_child: bool,
+    // Generic parameters and leading `parent` argument for child objects;
+    // empty for root objects.
+    generics: TokenStream,
+    parent_param: TokenStream,
+    parent_arg: TokenStream,
+    // `{Repo}Cache` fields holding the object's cached items (standard objects
+    // only) and lists, if it declares a `cache_ttl`.
+    cache_items: Option<Ident>,
+    cache_lists: Option<Ident>,
+    // Cache fields of cached descendants, cleared when the object is deleted
+    // or restored.
+    descendant_caches: Vec<Ident>,
+}
+
+impl<'a> HookedCtx<'a> {
+    fn new(model: &ConfigModel, ty_ident: &'a Ident, is_child: bool) -> Self {
+        let hooks = model.hooks_for(ty_ident).map(|hooks_ident| {
+            let hooks_trait = hooks_trait_ident_for(ty_ident);
+            quote! { <#hooks_ident as #hooks_trait> }
+        });
+        let event_enum = model
+            .events
+            .then(|| event_enum_ident_for(&model.repository_name));
+        let is_cached = model.cache_ttl_for(ty_ident).is_some();
+        let (generics, parent_param, parent_arg) = if is_child {
+            // Events record the parent's ID, and cached lists are keyed by it,
+            // so the parent must be a full object rather than any `ParentOf`
+            // implementor.
+            let event_bound = (event_enum.is_some() || is_cached)
+                .then(|| quote! { + ::fractic_aws_dynamo::schema::DynamoObject });
+            (
+                quote! { <P: ::fractic_aws_dynamo::ext::crud::ParentOf<#ty_ident> #event_bound> },
+                quote! { parent: &P, },
+                quote! { parent, },
+            )
+        } else {
+            (TokenStream::new(), TokenStream::new(), TokenStream::new())
+        };
+        Self {
+            ty_ident,
+            ty_data: quote! { <#ty_ident as ::fractic_aws_dynamo::schema::DynamoObject>::Data },
+            hooks,
+            event_enum,
+            is_child,
+            generics,
+            parent_param,
+            parent_arg,
+            cache_items: (is_cached && model.is_standard(ty_ident))
+                .then(|| cache_items_field_for(ty_ident)),
+            cache_lists: is_cached.then(|| cache_lists_field_for(ty_ident)),
+            descendant_caches: model
+                .cached_descendants(ty_ident)
+                .into_iter()
+                .flat_map(|descendant| {
+                    let items = model
+                        .is_standard(descendant)
+                        .then(|| cache_items_field_for(descendant));
+                    items
+                        .into_iter()
+                        .chain(std::iter::once(cache_lists_field_for(descendant)))
+                })
+                .collect(),
+        }
+    }
+
+    fn hook(&self, name: &str, arg: TokenStream) -> TokenStream {
+        match &self.hooks {
+            Some(hooks) => {
+                let name = Ident::new(name, self.ty_ident.span());
+                quote! { #hooks::#name(self.repo, #arg).await?; }
+            }
+            None => TokenStream::new(),
+        }
+    }
+
+    /// Publishes an event for the item with the given ID (a `&PkSk`
+    /// expression). Its parent is the operation's `parent` if `scoped`, and
+    /// is derived from the item ID otherwise.
+    fn event(&self, variant: Ident, id: TokenStream, scoped: bool) -> TokenStream {
+        match &self.event_enum {
+            Some(event_enum) => {
+                let parent_id = if self.is_child && !scoped {
+                    quote! { ::fractic_aws_dynamo::schema::PkSk::parent_id(#id) }
+                } else {
+                    self.parent_id(scoped)
+                };
+                quote! {
+                    self.repo.event_sink().publish(#event_enum::#variant {
+                        id: (#id).clone(),
+                        parent_id: #parent_id,
+                    })?;
+                }
+            }
+            None => TokenStream::new(),
+        }
+    }
+
+    /// ID of the parent the current operation is scoped to, if known.
+    fn parent_id(&self, scoped: bool) -> TokenStream {
+        if self.is_child && scoped {
+            quote! {
+                ::std::option::Option::Some(::fractic_aws_dynamo::schema::DynamoObject::id(parent).clone())
+            }
+        } else {
+            quote! { ::std::option::Option::None }
+        }
+    }
+
+    /// Drops the cache entries made stale by a mutation: the given items, the
+    /// lists under the operation's parent (or all lists, if not scoped to a
+    /// parent), and with `cascade` every entry of the cached descendants.
+    fn invalidate(&self, ids: Option<TokenStream>, scoped: bool, cascade: bool) -> TokenStream {
+        let items = match (&self.cache_items, ids) {
+            (Some(items_field), Some(ids)) => quote! {
+                for id in #ids {
+                    cache.#items_field.remove(id);
+                }
+            },
+            _ => TokenStream::new(),
+        };
+        let lists = match &self.cache_lists {
+            Some(lists_field) if scoped => {
+                let parent_id = self.parent_id(true);
+                quote! { cache.#lists_field.remove(&#parent_id); }
+            }
+            Some(lists_field) => quote! { cache.#lists_field.clear(); },
+            None => TokenStream::new(),
+        };
+        let descendants = if cascade {
+            let fields = &self.descendant_caches;
+            quote! { #(cache.#fields.clear();)* }
+        } else {
+            TokenStream::new()
+        };
+        if items.is_empty() && lists.is_empty() && descendants.is_empty() {
+            return TokenStream::new();
+        }
+        quote! {
+            if let ::std::option::Option::Some(cache) = self.repo.cache() {
+                #items
+                #lists
+                #descendants
+            }
+        }
+    }
+
+    /// Cached `query_all`, for objects that declare a `cache_ttl`.
+    fn cached_query_all(&self) -> TokenStream {
+        let Some(lists_field) = &self.cache_lists else {
+            return TokenStream::new();
+        };
+        let Self {
+            ty_ident,
+            generics,
+            parent_param,
+            parent_arg,
+            ..
+        } = self;
+        let parent_id = self.parent_id(true);
+        quote! {
+            pub async fn query_all #generics(&self, #parent_param) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError> {
+                let cache = self.repo.cache();
+                let key = #parent_id;
+                if let ::std::option::Option::Some(items) = cache.and_then(|cache| cache.#lists_field.get(&key)) {
+                    return ::std::result::Result::Ok(items);
+                }
+                let items = self.manager().query_all(#parent_arg).await?;
+                if let ::std::option::Option::Some(cache) = cache {
+                    cache.#lists_field.insert(key, items.clone());
+                }
+                ::std::result::Result::Ok(items)
+            }
+        }
+    }
+
+    /// Cached `get` and `find`, for standard objects that declare a
+    /// `cache_ttl`.
+    fn cached_get_find(&self) -> TokenStream {
+        let Some(items_field) = &self.cache_items else {
+            return TokenStream::new();
+        };
+        let ty_ident = self.ty_ident;
+        quote! {
+            pub async fn get(&self, id: ::fractic_aws_dynamo::schema::PkSk) -> ::std::result::Result<#ty_ident, ::fractic_server_error::ServerError> {
+                let cache = self.repo.cache();
+                if let ::std::option::Option::Some(item) = cache.and_then(|cache| cache.#items_field.get(&id)) {
+                    return ::std::result::Result::Ok(item);
+                }
+                let item = self.manager().get(id.clone()).await?;
+                if let ::std::option::Option::Some(cache) = cache {
+                    cache.#items_field.insert(id, item.clone());
+                }
+                ::std::result::Result::Ok(item)
+            }
+            pub async fn find(&self, id: ::fractic_aws_dynamo::schema::PkSk) -> ::std::result::Result<::std::option::Option<#ty_ident>, ::fractic_server_error::ServerError> {
+                let cache = self.repo.cache();
+                if let ::std::option::Option::Some(item) = cache.and_then(|cache| cache.#items_field.get(&id)) {
+                    return ::std::result::Result::Ok(::std::option::Option::Some(item));
+                }
+                let item = self.manager().find(id.clone()).await?;
+                if let (::std::option::Option::Some(cache), ::std::option::Option::Some(item)) = (cache, &item) {
+                    cache.#items_field.insert(id, item.clone());
+                }
+                ::std::result::Result::Ok(item)
+            }
+        }
+    }
+
+    fn before_create(&self, data: TokenStream) -> TokenStream {
+        self.hook("before_create", data)
+    }
+
+    fn after_create(&self, item: TokenStream) -> TokenStream {
+        let hook = self.hook("after_create", item.clone());
+        let event = self.event(
+            created_variant_for(self.ty_ident),
+            quote! { &(#item).id },
+            true,
+        );
+        quote! { #hook #event }
+    }
+
+    fn before_update(&self, item: TokenStream) -> TokenStream {
+        self.hook("before_update", item)
+    }
+
+    fn after_update(&self, item: TokenStream) -> TokenStream {
+        self.event(
+            updated_variant_for(self.ty_ident),
+            quote! { &(#item).id },
+            false,
+        )
+    }
+
+    fn before_delete(&self, id: TokenStream) -> TokenStream {
+        self.hook("before_delete", id)
+    }
+
+    fn after_delete(&self, id: TokenStream, scoped: bool) -> TokenStream {
+        let hook = self.hook("after_delete", id.clone());
+        let event = self.event(deleted_variant_for(self.ty_ident), id, scoped);
+        quote! { #hook #event }
+    }
+
+    /// Create method taking a single data item (`add`, `set`).
+    fn create_one(
+        &self,
+        method: &str,
+        extra_param: TokenStream,
+        extra_arg: TokenStream,
+    ) -> TokenStream {
+        let Self {
+            ty_ident,
+            ty_data,
+            generics,
+            parent_param,
+            parent_arg,
+            ..
+        } = self;
+        let method = Ident::new(method, ty_ident.span());
+        let before = self.before_create(quote! { &mut data });
+        let after = self.after_create(quote! { &item });
+        let invalidate = self.invalidate(None, true, false);
+        let data_binding = if self.hooks.is_some() {
+            quote! { mut data }
+        } else {
+            quote! { data }
+        };
+        quote! {
+            pub async fn #method #generics(&self, #parent_param #data_binding: #ty_data, #extra_param) -> ::std::result::Result<#ty_ident, ::fractic_server_error::ServerError> {
+                #before
+                let item = self.manager().#method(#parent_arg data, #extra_arg).await?;
+                #invalidate
+                #after
+                ::std::result::Result::Ok(item)
+            }
+        }
+    }
+
+    /// Create method taking several data items (`batch_add`, `batch_set`).
+    fn create_many(
+        &self,
+        method: &str,
+        extra_param: TokenStream,
+        extra_arg: TokenStream,
+    ) -> TokenStream {
+        let Self {
+            ty_ident,
+            ty_data,
+            generics,
+            parent_param,
+            parent_arg,
+            ..
+        } = self;
+        let method = Ident::new(method, ty_ident.span());
+        let before = for_each(
+            quote! { d },
+            quote! { data.iter_mut() },
+            self.before_create(quote! { d }),
+        );
+        let after = for_each(
+            quote! { item },
+            quote! { &items },
+            self.after_create(quote! { item }),
+        );
+        let invalidate = self.invalidate(None, true, false);
+        let data_binding = if self.hooks.is_some() {
+            quote! { mut data }
+        } else {
+            quote! { data }
+        };
+        quote! {
+            pub async fn #method #generics(&self, #parent_param #data_binding: ::std::vec::Vec<#ty_data>, #extra_param) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError> {
+                #before
+                let items = self.manager().#method(#parent_arg data, #extra_arg).await?;
+                #invalidate
+                #after
+                ::std::result::Result::Ok(items)
+            }
+        }
+    }
+
+    /// Update method (`update`), or for `versioned` objects with `conditional`
+    /// the conditional `update_if_version`, whose post-update side runs only
+    /// if the update was applied.
+    fn update(&self, conditional: bool) -> TokenStream {
+        let ty_ident = self.ty_ident;
+        let before = self.before_update(quote! { item });
+        let after = self.after_update(quote! { item });
+        // A rejected update means the item changed concurrently, so the cached
+        // copy is stale either way.
+        let invalidate = self.invalidate(Some(quote! { [&item.id] }), false, false);
+        if !conditional {
+            return quote! {
+                pub async fn update(&self, item: & #ty_ident) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+                    #before
+                    self.manager().update(item).await?;
+                    #invalidate
+                    #after
+                    ::std::result::Result::Ok(())
+                }
+            };
+        }
+        let after = if after.is_empty() {
+            TokenStream::new()
+        } else {
+            quote! {
+                if updated {
+                    #after
+                }
+            }
+        };
+        quote! {
+            pub async fn update_if_version(&self, item: & #ty_ident, expected_version: ::std::option::Option<u64>) -> ::std::result::Result<bool, ::fractic_server_error::ServerError> {
+                #before
+                let updated = self.manager().update_if_version(item, expected_version).await?;
+                #invalidate
+                #after
+                ::std::result::Result::Ok(updated)
+            }
+        }
+    }
+
+    /// Delete method taking the item to delete (`delete`, `soft_delete`, ...).
+    fn delete_one(&self, method: &str) -> TokenStream {
+        let Self {
+            ty_ident, ty_data, ..
+        } = self;
+        let method = Ident::new(method, ty_ident.span());
+        let before = self.before_delete(quote! { &id });
+        let after = self.after_delete(quote! { &id }, false);
+        let invalidate = self.invalidate(Some(quote! { [&id] }), false, true);
+        quote! {
+            pub async fn #method(&self, item: #ty_ident) -> ::std::result::Result<#ty_data, ::fractic_server_error::ServerError> {
+                let id = item.id.clone();
+                #before
+                let data = self.manager().#method(item).await?;
+                #invalidate
+                #after
+                ::std::result::Result::Ok(data)
+            }
+        }
+    }
+
+    /// Delete method taking several items (`batch_delete`, ...).
+    fn delete_many(&self, method: &str) -> TokenStream {
+        let Self {
+            ty_ident, ty_data, ..
+        } = self;
+        let method = Ident::new(method, ty_ident.span());
+        let before = for_each(
+            quote! { id },
+            quote! { &ids },
+            self.before_delete(quote! { id }),
+        );
+        let after = for_each(
+            quote! { id },
+            quote! { &ids },
+            self.after_delete(quote! { id }, false),
+        );
+        let invalidate = self.invalidate(Some(quote! { &ids }), false, true);
+        quote! {
+            pub async fn #method(&self, items: ::std::vec::Vec<#ty_ident>) -> ::std::result::Result<::std::vec::Vec<#ty_data>, ::fractic_server_error::ServerError> {
+                let ids = items.iter().map(|item| item.id.clone()).collect::<::std::vec::Vec<_>>();
+                #before
+                let data = self.manager().#method(items).await?;
+                #invalidate
+                #after
+                ::std::result::Result::Ok(data)
+            }
+        }
+    }
+
+    /// Restore method, for `soft_delete` objects. It runs no hooks, but
+    /// restored items (and their descendants) reappear in cached lists.
+    fn restore(&self) -> TokenStream {
+        let ty_ident = self.ty_ident;
+        let invalidate = self.invalidate(Some(quote! { [&item.id] }), false, true);
+        let event = self.event(restored_variant_for(ty_ident), quote! { &item.id }, false);
+        if invalidate.is_empty() && event.is_empty() {
+            return TokenStream::new();
+        }
+        quote! {
+            pub async fn restore(&self, item: #ty_ident) -> ::std::result::Result<#ty_ident, ::fractic_server_error::ServerError> {
+                let item = self.manager().restore(item).await?;
+                #invalidate
+                #event
+                ::std::result::Result::Ok(item)
+            }
+        }
+    }
+
+    /// Delete method removing every item (under the parent, if any). The items
+    /// are listed first so that the hooks see each deleted ID.
+    fn delete_all(&self, method: &str) -> TokenStream {
+        let Self {
+            ty_ident,
+            generics,
+            parent_param,
+            parent_arg,
+            ..
+        } = self;
+        let method = Ident::new(method, ty_ident.span());
+        let before = for_each(
+            quote! { id },
+            quote! { &ids },
+            self.before_delete(quote! { id }),
+        );
+        let after = for_each(
+            quote! { id },
+            quote! { &ids },
+            self.after_delete(quote! { id }, true),
+        );
+        let invalidate = self.invalidate(Some(quote! { &ids }), true, true);
+        quote! {
+            pub async fn #method #generics(&self, #parent_param) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+                let ids = self
+                    .manager()
+                    .query_all(#parent_arg)
+                    .await?
+                    .into_iter()
+                    .map(|item| item.id)
+                    .collect::<::std::vec::Vec<_>>();
+                #before
+                self.manager().#method(#parent_arg).await?;
+                #invalidate
+                #after
+                ::std::result::Result::Ok(())
+            }
+        }
+    }
+}
+
+/// `for #binding in #iter { #body }`, or nothing if the body is empty.
+pub(crate) fn for_each(binding: TokenStream, iter: TokenStream, body: TokenStream) -> TokenStream {
+    if body.is_empty() {
+        TokenStream::new()
+    } else {
+        quote! {
+            for #binding in #iter {
+                #body
+            }
+        }
+    }
+}
+
+fn gen_standard_hooks(
+    model: &ConfigModel,
+    object: &StandardDef,
+    kind: ObjectType,
+) -> Option<TokenStream> {
+    let ty_ident = &object.name;
+    if !model.is_intercepted(ty_ident) {
+        return None;
+    }
+    let is_child = object.parents.is_some();
+    let ctx = HookedCtx::new(model, ty_ident, is_child);
+
+    let (after_param, after_arg) = if matches!(kind, ObjectType::Ordered) {
+        (
+            quote! { after: ::std::option::Option<& #ty_ident> },
+            quote! { after },
+        )
+    } else {
+        (TokenStream::new(), TokenStream::new())
+    };
+    let add = ctx.create_one("add", after_param.clone(), after_arg.clone());
+    let batch_add = ctx.create_many("batch_add", after_param, after_arg);
+    let update = ctx.update(false);
+    let update_if_version = if object.is_versioned {
+        ctx.update(true)
+    } else {
+        TokenStream::new()
+    };
+
+    let delete_methods = if object.has_children() {
+        [
+            ctx.delete_one("delete_recursive"),
+            ctx.delete_one("delete_non_recursive"),
+            ctx.delete_many("batch_delete_non_recursive"),
+            ctx.delete_all("batch_delete_all"),
+            ctx.delete_all("batch_delete_all_non_recursive"),
+        ]
+        .into_iter()
+        .collect::<TokenStream>()
+    } else {
+        [
+            ctx.delete_one("delete"),
+            ctx.delete_many("batch_delete"),
+            ctx.delete_all("batch_delete_all"),
+        ]
+        .into_iter()
+        .collect::<TokenStream>()
+    };
+    let soft_delete_methods = if object.is_soft_delete {
+        [
+            ctx.delete_one("soft_delete"),
+            ctx.delete_many("batch_soft_delete"),
+            ctx.delete_all("batch_soft_delete_all"),
+            ctx.restore(),
+        ]
+        .into_iter()
+        .collect::<TokenStream>()
+    } else {
+        TokenStream::new()
+    };
+    let cached_reads = [ctx.cached_get_find(), ctx.cached_query_all()]
+        .into_iter()
+        .collect::<TokenStream>();
+
+    let manage_ty = if is_child {
+        child_manage_ty(kind, object.has_children(), ty_ident)
+    } else {
+        root_manage_ty(kind, object.has_children(), ty_ident)
+    };
+    Some(gen_hooked_manager(
+        model,
+        ty_ident,
+        manage_ty,
+        quote! {
+            #add
+            #batch_add
+            #update
+            #update_if_version
+            #delete_methods
+            #soft_delete_methods
+            #cached_reads
+        },
+    ))
+}
+
+fn gen_batch_hooks(model: &ConfigModel, object: &BatchDef) -> Option<TokenStream> {
+    let ty_ident = &object.name;
+    if !model.is_intercepted(ty_ident) {
+        return None;
+    }
+    let is_child = object.parents.is_some();
+    let ctx = HookedCtx::new(model, ty_ident, is_child);
+    let HookedCtx {
+        ty_data,
+        generics,
+        parent_param,
+        parent_arg,
+        ..
+    } = &ctx;
+
+    let delete_all = ctx.delete_all("batch_delete_all");
+    let before_delete = for_each(
+        quote! { id },
+        quote! { &ids },
+        ctx.before_delete(quote! { id }),
+    );
+    let before_create = for_each(
+        quote! { d },
+        quote! { data.iter_mut() },
+        ctx.before_create(quote! { d }),
+    );
+    let after_delete = for_each(
+        quote! { id },
+        quote! { &ids },
+        ctx.after_delete(quote! { id }, true),
+    );
+    let after_create = for_each(
+        quote! { item },
+        quote! { &self.manager().query_all(#parent_arg).await? },
+        ctx.after_create(quote! { item }),
+    );
+    let data_binding = if ctx.hooks.is_some() {
+        quote! { mut data }
+    } else {
+        quote! { data }
+    };
+    let invalidate = ctx.invalidate(None, true, false);
+    let cached_query_all = ctx.cached_query_all();
+
+    let manage_ty = if is_child {
+        child_manage_ty(ObjectType::Batch, false, ty_ident)
+    } else {
+        root_manage_ty(ObjectType::Batch, false, ty_ident)
+    };
+    Some(gen_hooked_manager(
+        model,
+        ty_ident,
+        manage_ty,
+        quote! {
+            #delete_all
+            // Replacing deletes every existing item and creates the new ones,
+            // so both sets of hooks run.
+            pub async fn batch_replace_all_ordered #generics(&self, #parent_param #data_binding: ::std::vec::Vec<#ty_data>) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+                let ids = self
+                    .manager()
+                    .query_all(#parent_arg)
+                    .await?
+                    .into_iter()
+                    .map(|item| item.id)
+                    .collect::<::std::vec::Vec<_>>();
+                #before_delete
+                #before_create
+                self.manager().batch_replace_all_ordered(#parent_arg data).await?;
+                #invalidate
+                #after_delete
+                #after_create
+                ::std::result::Result::Ok(())
+            }
+            #cached_query_all
+        },
+    ))
+}
+
+fn gen_singleton_hooks(model: &ConfigModel, object: &SingletonDef) -> Option<TokenStream> {
+    let ty_ident = &object.name;
+    if !model.is_intercepted(ty_ident) {
+        return None;
+    }
+    let is_child = object.parents.is_some();
+    let ctx = HookedCtx::new(model, ty_ident, is_child);
+    let HookedCtx {
+        generics,
+        parent_param,
+        parent_arg,
+        ..
+    } = &ctx;
+
+    let set = ctx.create_one("set", TokenStream::new(), TokenStream::new());
+    let before_delete = ctx.before_delete(quote! { &item.id });
+    let after_delete = ctx.after_delete(quote! { &item.id }, true);
+
+    let manage_ty = if is_child {
+        child_manage_ty(ObjectType::Singleton, false, ty_ident)
+    } else {
+        root_manage_ty(ObjectType::Singleton, false, ty_ident)
+    };
+    Some(gen_hooked_manager(
+        model,
+        ty_ident,
+        manage_ty,
+        quote! {
+            #set
+            pub async fn delete #generics(&self, #parent_param) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+                let existing = self.manager().find(#parent_arg).await?;
+                if let ::std::option::Option::Some(item) = &existing {
+                    #before_delete
+                }
+                self.manager().delete(#parent_arg).await?;
+                if let ::std::option::Option::Some(item) = &existing {
+                    #after_delete
+                }
+                ::std::result::Result::Ok(())
+            }
+        },
+    ))
+}
+
+fn gen_indexed_singleton_hooks(
+    model: &ConfigModel,
+    object: &IndexedSingletonDef,
+) -> Option<TokenStream> {
+    let ty_ident = &object.name;
+    if !model.is_intercepted(ty_ident) {
+        return None;
+    }
+    let is_child = object.parents.is_some();
+    let ctx = HookedCtx::new(model, ty_ident, is_child);
+    let HookedCtx {
+        generics,
+        parent_param,
+        parent_arg,
+        ..
+    } = &ctx;
+
+    let set = ctx.create_one("set", TokenStream::new(), TokenStream::new());
+    let batch_set = ctx.create_many("batch_set", TokenStream::new(), TokenStream::new());
+    let delete_all = ctx.delete_all("batch_delete_all");
+    let keyed_generics = if is_child {
+        let event_bound = ctx
+            .event_enum
+            .as_ref()
+            .map(|_| quote! { + ::fractic_aws_dynamo::schema::DynamoObject });
+        quote! { <P: ::fractic_aws_dynamo::ext::crud::ParentOf<#ty_ident> #event_bound, K: ::std::convert::AsRef<str>> }
+    } else {
+        quote! { <K: ::std::convert::AsRef<str>> }
+    };
+    let before_delete = ctx.before_delete(quote! { &item.id });
+    let after_delete = ctx.after_delete(quote! { &item.id }, true);
+    let before_delete_each = for_each(
+        quote! { id },
+        quote! { &ids },
+        ctx.before_delete(quote! { id }),
+    );
+    let after_delete_each = for_each(
+        quote! { id },
+        quote! { &ids },
+        ctx.after_delete(quote! { id }, true),
+    );
+    let invalidate = ctx.invalidate(None, true, false);
+    let cached_query_all = ctx.cached_query_all();
+
+    let manage_ty = if is_child {
+        child_manage_ty(ObjectType::IndexedSingleton, false, ty_ident)
+    } else {
+        root_manage_ty(ObjectType::IndexedSingleton, false, ty_ident)
+    };
+    Some(gen_hooked_manager(
+        model,
+        ty_ident,
+        manage_ty,
+        quote! {
+            #set
+            #batch_set
+            pub async fn delete #generics(&self, #parent_param key: impl ::std::convert::AsRef<str>) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+                let existing = self.manager().find(#parent_arg key.as_ref()).await?;
+                if let ::std::option::Option::Some(item) = &existing {
+                    #before_delete
+                }
+                self.manager().delete(#parent_arg key).await?;
+                #invalidate
+                if let ::std::option::Option::Some(item) = &existing {
+                    #after_delete
+                }
+                ::std::result::Result::Ok(())
+            }
+            pub async fn batch_delete #keyed_generics(&self, #parent_param keys: ::std::vec::Vec<K>) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+                let mut ids = ::std::vec::Vec::new();
+                for key in &keys {
+                    if let ::std::option::Option::Some(item) = self.manager().find(#parent_arg key.as_ref()).await? {
+                        ids.push(item.id);
+                    }
+                }
+                #before_delete_each
+                self.manager().batch_delete(#parent_arg keys).await?;
+                #invalidate
+                #after_delete_each
+                ::std::result::Result::Ok(())
+            }
+            #delete_all
+            #cached_query_all
+        },
+    ))
+}
+
+/// Emits the `{Type}Hooks` trait (if the object declares `hooks`), the hooked
+/// manager wrapping the repository's manager, and the `manage_{type}_hooked`
+/// accessor on the repository.
+fn gen_hooked_manager(
+    model: &ConfigModel,
+    ty_ident: &Ident,
+    manage_ty: TokenStream,
+    methods: TokenStream,
+) -> TokenStream {
+    let repo_name = &model.repository_name;
+    let hooked_ident = hooked_manager_ident_for(ty_ident);
+    let manager_ident = manager_ident_for(ty_ident, false);
+    let hooked_manager_ident = hooked_manage_ident_for(ty_ident);
+    let ty_data = quote! { <#ty_ident as ::fractic_aws_dynamo::schema::DynamoObject>::Data };
+
+    let hooks_trait = if model.hooks_for(ty_ident).is_some() {
+        let hooks_trait = hooks_trait_ident_for(ty_ident);
+        quote! {
+            pub trait #hooks_trait {
+                async fn before_create(_repo: &dyn #repo_name, _data: &mut #ty_data) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+                    ::std::result::Result::Ok(())
+                }
+                async fn after_create(_repo: &dyn #repo_name, _item: & #ty_ident) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+                    ::std::result::Result::Ok(())
+                }
+                async fn before_update(_repo: &dyn #repo_name, _item: & #ty_ident) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+                    ::std::result::Result::Ok(())
+                }
+                async fn before_delete(_repo: &dyn #repo_name, _id: &::fractic_aws_dynamo::schema::PkSk) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+                    ::std::result::Result::Ok(())
+                }
+                async fn after_delete(_repo: &dyn #repo_name, _id: &::fractic_aws_dynamo::schema::PkSk) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+                    ::std::result::Result::Ok(())
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    quote! {
+        #hooks_trait
+
+        // Mutating operations run the hooks and publish events around the
+        // underlying manager call; everything else is reached through `Deref`.
+        #[doc(hidden)]
+        pub struct #hooked_ident<'a> {
+            repo: &'a dyn #repo_name,
+        }
+        impl<'a> #hooked_ident<'a> {
+            fn manager(&self) -> &'a #manage_ty {
+                self.repo.#manager_ident()
+            }
+            #methods
+        }
+        impl ::std::ops::Deref for #hooked_ident<'_> {
+            type Target = #manage_ty;
+            fn deref(&self) -> &Self::Target {
+                self.manager()
+            }
+        }
+        impl dyn #repo_name {
+            pub fn #hooked_manager_ident(&self) -> #hooked_ident<'_> {
+                #hooked_ident { repo: self }
+            }
+        }
+    }
+}
+
+pub(crate) fn hooks_trait_ident_for(ty_ident: &Ident) -> Ident {
+    Ident::new(&format!("{}Hooks", ty_ident), ty_ident.span())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::generate;
+    use crate::{crud::test_model, testing};
+
+    #[test]
+    fn publishes_parent_ids_and_restores() {
+        let model = test_model(
+            r#"
+            #[events]
+            MyRepo;
+            root Project {
+                ordered_children: ProjectTask
+            }
+            soft_delete ordered ProjectTask {
+                parent: Project
+            }
+            "#,
+        )
+        .unwrap();
+        let items = testing::items(generate(&model));
+
+        let derived_parent = "PkSk :: parent_id";
+        for method in ["update", "delete", "batch_delete", "soft_delete", "restore"] {
+            let hooked = testing::fn_of(&items, Some("ProjectTaskHookedManager"), method);
+            assert!(hooked.body_contains(derived_parent), "{}", method);
+        }
+        let restore = testing::fn_of(&items, Some("ProjectTaskHookedManager"), "restore");
+        assert!(restore.body_contains("MyRepoEvent :: ProjectTaskRestored"));
+
+        // Root objects have no parent, and scoped operations use the one given.
+        let update = testing::fn_of(&items, Some("ProjectHookedManager"), "update");
+        assert!(update.body_contains("parent_id : :: std :: option :: Option :: None"));
+        let delete_all =
+            testing::fn_of(&items, Some("ProjectTaskHookedManager"), "batch_delete_all");
+        assert!(!delete_all.body_contains(derived_parent));
+    }
+}
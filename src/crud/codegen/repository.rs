@@ -2,6 +2,7 @@ use proc_macro2::TokenStream;
 use quote::quote;
 use syn::Ident;
 
+use super::{events::event_sink_trait_ident_for, repository_impl::cache_ident_for};
 use crate::{crud::model::ConfigModel, helpers::to_snake_case};
 
 pub fn generate(model: &ConfigModel) -> TokenStream {
@@ -31,6 +32,14 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
         ordered_manage_methods.push(quote! {
             fn #method_ident(&self) -> & #manage_ty;
         });
+        if ordered.is_movable_archive {
+            // Second manager over the archive table, used as the transfer
+            // target when archiving / unarchiving.
+            let archived_method_ident = archived_manage_ident_for(&ordered.name);
+            ordered_manage_methods.push(quote! {
+                fn #archived_method_ident(&self) -> & #manage_ty;
+            });
+        }
     }
 
     let mut unordered_parent_of_impls = Vec::new();
@@ -57,6 +66,14 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
         unordered_manage_methods.push(quote! {
             fn #method_ident(&self) -> & #manage_ty;
         });
+        if unordered.is_movable_archive {
+            // Second manager over the archive table, used as the transfer
+            // target when archiving / unarchiving.
+            let archived_method_ident = archived_manage_ident_for(&unordered.name);
+            unordered_manage_methods.push(quote! {
+                fn #archived_method_ident(&self) -> & #manage_ty;
+            });
+        }
     }
 
     let mut batch_parent_of_impls = Vec::new();
@@ -137,7 +154,47 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
         });
     }
 
+    // Sink receiving the events published by the hooked managers.
+    let event_sink_method = if model.events {
+        let sink_trait = event_sink_trait_ident_for(repo_name);
+        quote! {
+            fn event_sink(&self) -> &dyn #sink_trait;
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    // Cache consulted by the hooked managers. Only `{Repo}Cached` provides
+    // one; every other implementation reads straight from the managers.
+    let cache_method = if model.has_cached_objects() {
+        let cache_ident = cache_ident_for(repo_name);
+        quote! {
+            fn cache(&self) -> ::std::option::Option<& #cache_ident> {
+                ::std::option::Option::None
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    // Error returned when a conditional update of a `versioned` object finds a
+    // different version than the one the caller read.
+    let version_conflict_error = if model.has_versioned_objects() {
+        let error_ident = version_conflict_ident_for(repo_name);
+        quote! {
+            ::fractic_server_error::define_client_error!(
+                #error_ident,
+                "Version conflict while updating {object}; it was modified concurrently.",
+                { object: &str }
+            );
+        }
+    } else {
+        TokenStream::new()
+    };
+
     quote! {
+        #version_conflict_error
+
         #(#ordered_parent_of_impls)*
         #(#unordered_parent_of_impls)*
         #(#batch_parent_of_impls)*
@@ -150,6 +207,8 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
             #(#batch_manage_methods)*
             #(#singleton_manage_methods)*
             #(#indexed_singleton_manage_methods)*
+            #event_sink_method
+            #cache_method
         }
     }
 }
@@ -160,6 +219,15 @@ fn method_ident_for(prefix: &str, ident: &Ident) -> Ident {
     Ident::new(&name, ident.span())
 }
 
+pub(crate) fn version_conflict_ident_for(repo_name: &Ident) -> Ident {
+    Ident::new(&format!("{}VersionConflict", repo_name), repo_name.span())
+}
+
+pub(crate) fn archived_manage_ident_for(ident: &Ident) -> Ident {
+    let snake = to_snake_case(&ident.to_string());
+    Ident::new(&format!("manage_{}_archived", snake), ident.span())
+}
+
 #[derive(Copy, Clone)]
 pub(crate) enum ObjectType {
     Ordered,
@@ -2,7 +2,11 @@ use proc_macro2::TokenStream;
 use quote::quote;
 use syn::Ident;
 
-use super::repository::{ObjectType, child_manage_ty, root_manage_ty};
+use super::{
+    events::{event_sink_trait_ident_for, noop_event_sink_ident_for},
+    hooks::{hooked_manage_ident_for, hooked_manager_ident_for},
+    repository::{ObjectType, archived_manage_ident_for, child_manage_ty, root_manage_ty},
+};
 use crate::{crud::model::ConfigModel, helpers::to_snake_case};
 
 pub fn generate(model: &ConfigModel) -> TokenStream {
@@ -14,6 +18,9 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
     );
     let impl_struct_ident = Ident::new(&format!("{}Impl", repo_name), repo_name.span());
 
+    // Manager accessors, forwarded by `{Repo}Cached`.
+    let mut managers = Vec::new();
+
     // Fields and initializers for ordered collections.
     let mut ordered_fields = Vec::new();
     let mut ordered_inits = Vec::new();
@@ -21,21 +28,34 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
     for ordered in &model.ordered_objects {
         let method_ident = method_ident_for("manage", &ordered.name);
         let ty_ident = &ordered.name;
-        let db_ident = db_ident_for(ordered.is_archive);
+        let db_ident = db_ident_for(ordered.is_archive, ordered.table.as_ref());
         let manage_ty = if ordered.parents.is_none() {
             root_manage_ty(ObjectType::Ordered, ordered.has_children(), ty_ident)
         } else {
             child_manage_ty(ObjectType::Ordered, ordered.has_children(), ty_ident)
         };
+        managers.push((method_ident.clone(), manage_ty.clone()));
         gen_field_init_impl(
             &mut ordered_fields,
             &mut ordered_inits,
             &mut ordered_trait_impls,
             &method_ident,
             db_ident,
-            manage_ty,
+            manage_ty.clone(),
             ordered.is_archive,
         );
+        if ordered.is_movable_archive {
+            managers.push((archived_manage_ident_for(ty_ident), manage_ty.clone()));
+            gen_field_init_impl(
+                &mut ordered_fields,
+                &mut ordered_inits,
+                &mut ordered_trait_impls,
+                &archived_manage_ident_for(ty_ident),
+                db_ident_for(true, None),
+                manage_ty,
+                true,
+            );
+        }
     }
 
     // Fields and initializers for unordered collections.
@@ -45,21 +65,34 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
     for unordered in &model.unordered_objects {
         let method_ident = method_ident_for("manage", &unordered.name);
         let ty_ident = &unordered.name;
-        let db_ident = db_ident_for(unordered.is_archive);
+        let db_ident = db_ident_for(unordered.is_archive, unordered.table.as_ref());
         let manage_ty = if unordered.parents.is_none() {
             root_manage_ty(ObjectType::Unordered, unordered.has_children(), ty_ident)
         } else {
             child_manage_ty(ObjectType::Unordered, unordered.has_children(), ty_ident)
         };
+        managers.push((method_ident.clone(), manage_ty.clone()));
         gen_field_init_impl(
             &mut unordered_fields,
             &mut unordered_inits,
             &mut unordered_trait_impls,
             &method_ident,
             db_ident,
-            manage_ty,
+            manage_ty.clone(),
             unordered.is_archive,
         );
+        if unordered.is_movable_archive {
+            managers.push((archived_manage_ident_for(ty_ident), manage_ty.clone()));
+            gen_field_init_impl(
+                &mut unordered_fields,
+                &mut unordered_inits,
+                &mut unordered_trait_impls,
+                &archived_manage_ident_for(ty_ident),
+                db_ident_for(true, None),
+                manage_ty,
+                true,
+            );
+        }
     }
 
     // Fields, inits, trait impls for batch collections.
@@ -69,12 +102,13 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
     for batch in &model.batch_objects {
         let method_ident = method_ident_for("manage", &batch.name);
         let ty_ident = &batch.name;
-        let db_ident = db_ident_for(batch.is_archive);
+        let db_ident = db_ident_for(batch.is_archive, batch.table.as_ref());
         let manage_ty = if batch.parents.is_none() {
             root_manage_ty(ObjectType::Batch, false, ty_ident)
         } else {
             child_manage_ty(ObjectType::Batch, false, ty_ident)
         };
+        managers.push((method_ident.clone(), manage_ty.clone()));
         gen_field_init_impl(
             &mut batch_fields,
             &mut batch_inits,
@@ -93,12 +127,13 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
     for singleton in &model.singleton_objects {
         let method_ident = method_ident_for("manage", &singleton.name);
         let ty_ident = &singleton.name;
-        let db_ident = db_ident_for(singleton.is_archive);
+        let db_ident = db_ident_for(singleton.is_archive, singleton.table.as_ref());
         let manage_ty = if singleton.parents.is_none() {
             root_manage_ty(ObjectType::Singleton, false, ty_ident)
         } else {
             child_manage_ty(ObjectType::Singleton, false, ty_ident)
         };
+        managers.push((method_ident.clone(), manage_ty.clone()));
         gen_field_init_impl(
             &mut singleton_fields,
             &mut singleton_inits,
@@ -117,12 +152,16 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
     for indexed_singleton in &model.indexed_singleton_objects {
         let method_ident = method_ident_for("manage", &indexed_singleton.name);
         let ty_ident = &indexed_singleton.name;
-        let db_ident = db_ident_for(indexed_singleton.is_archive);
+        let db_ident = db_ident_for(
+            indexed_singleton.is_archive,
+            indexed_singleton.table.as_ref(),
+        );
         let manage_ty = if indexed_singleton.parents.is_none() {
             root_manage_ty(ObjectType::IndexedSingleton, false, ty_ident)
         } else {
             child_manage_ty(ObjectType::IndexedSingleton, false, ty_ident)
         };
+        managers.push((method_ident.clone(), manage_ty.clone()));
         gen_field_init_impl(
             &mut indexed_singleton_fields,
             &mut indexed_singleton_inits,
@@ -134,6 +173,52 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
         );
     }
 
+    // One DynamoUtil per custom table, built from the accessor passed for it in
+    // the map form of the macro.
+    let custom_tables = model.custom_tables();
+    let table_db_inits = custom_tables
+        .iter()
+        .map(|table| {
+            let db_ident = table_db_ident_for(table);
+            let method_ident = table_db_method_ident_for(table);
+            quote! {
+                let #db_ident = ::std::sync::Arc::new(::fractic_aws_dynamo::util::DynamoUtil::new(ctx, ctx.$#method_ident()).await?);
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // Event sink, replaceable after construction.
+    let (event_sink_field, event_sink_init, event_sink_builder, event_sink_impl) = if model.events {
+        let sink_trait = event_sink_trait_ident_for(repo_name);
+        let noop_sink = noop_event_sink_ident_for(repo_name);
+        (
+            quote! {
+                event_sink: ::std::sync::Arc<dyn #sink_trait>,
+            },
+            quote! {
+                event_sink: ::std::sync::Arc::new(#noop_sink),
+            },
+            quote! {
+                pub fn with_event_sink(mut self, event_sink: ::std::sync::Arc<dyn #sink_trait>) -> Self {
+                    self.event_sink = event_sink;
+                    self
+                }
+            },
+            quote! {
+                fn event_sink(&self) -> &dyn #sink_trait {
+                    self.event_sink.as_ref()
+                }
+            },
+        )
+    } else {
+        (
+            TokenStream::new(),
+            TokenStream::new(),
+            TokenStream::new(),
+            TokenStream::new(),
+        )
+    };
+
     let out_case1_noarchive = quote! {
         pub struct #impl_struct_ident {
             #(#ordered_fields,)*
@@ -141,6 +226,7 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
             #(#batch_fields,)*
             #(#singleton_fields,)*
             #(#indexed_singleton_fields,)*
+            #event_sink_field
         }
 
         impl #impl_struct_ident {
@@ -148,6 +234,7 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
                 let main_db = ::std::sync::Arc::new(::fractic_aws_dynamo::util::DynamoUtil::new(ctx, ctx.$ctx_main_db_method()).await?);
                 let archive_db = main_db.clone();
                 let _ = &archive_db;
+                #(#table_db_inits)*
                 let crud_algorithms = ::std::sync::Arc::new(<$crud_algorithms>::new(main_db.clone()));
                 Ok(Self {
                     #(#ordered_inits,)*
@@ -155,8 +242,10 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
                     #(#batch_inits,)*
                     #(#singleton_inits,)*
                     #(#indexed_singleton_inits,)*
+                    #event_sink_init
                 })
             }
+            #event_sink_builder
         }
 
         impl #repo_name for #impl_struct_ident {
@@ -165,6 +254,7 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
             #(#batch_trait_impls)*
             #(#singleton_trait_impls)*
             #(#indexed_singleton_trait_impls)*
+            #event_sink_impl
         }
     };
     let out_case1_clone = out_case1_noarchive.clone();
@@ -176,12 +266,14 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
             #(#batch_fields,)*
             #(#singleton_fields,)*
             #(#indexed_singleton_fields,)*
+            #event_sink_field
         }
 
         impl #impl_struct_ident {
             pub async fn new(ctx: __ctx!()) -> ::std::result::Result<Self, ::fractic_server_error::ServerError> {
                 let main_db = ::std::sync::Arc::new(::fractic_aws_dynamo::util::DynamoUtil::new(ctx, ctx.$ctx_main_db_method()).await?);
                 let archive_db = ::std::sync::Arc::new(::fractic_aws_dynamo::util::DynamoUtil::new(ctx, ctx.$ctx_archive_db_method()).await?);
+                #(#table_db_inits)*
                 let crud_algorithms = ::std::sync::Arc::new(<$crud_algorithms>::new(
                     main_db.clone(),
                     archive_db.clone(),
@@ -192,8 +284,10 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
                     #(#batch_inits,)*
                     #(#singleton_inits,)*
                     #(#indexed_singleton_inits,)*
+                    #event_sink_init
                 })
             }
+            #event_sink_builder
         }
 
         impl #repo_name for #impl_struct_ident {
@@ -202,26 +296,78 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
             #(#batch_trait_impls)*
             #(#singleton_trait_impls)*
             #(#indexed_singleton_trait_impls)*
+            #event_sink_impl
         }
     };
     let out_case2_clone = out_case2_witharchive.clone();
 
+    // Map form: `{ main: .., archive: .., <table>: .. }`, with custom tables in
+    // the order given by `ConfigModel::custom_tables`. The positional forms
+    // cannot route custom tables, so they are rejected when any are declared.
+    let table_pattern = custom_tables
+        .iter()
+        .map(|table| {
+            let method_ident = table_db_method_ident_for(table);
+            quote! { , #table: $#method_ident:ident }
+        })
+        .collect::<Vec<_>>();
+    let (out_case1_positional, out_case2_positional) = if custom_tables.is_empty() {
+        (out_case1_clone.clone(), out_case2_clone.clone())
+    } else {
+        let example = custom_tables
+            .iter()
+            .map(|table| format!(", {}: {}", table, table_db_ident_for(table)))
+            .collect::<String>();
+        let msg = format!(
+            "{} routes objects to custom tables; pass the DB accessors as a map instead, e.g. \
+             `Ctx => {{ main: main_db, archive: archive_db{} }}, CrudAlgorithmsWithArchive`",
+            repo_name, example
+        );
+        let err = quote! { ::core::compile_error!(#msg); };
+        (err.clone(), err)
+    };
+    let out_case1_positional_clone = out_case1_positional.clone();
+    let out_case2_positional_clone = out_case2_positional.clone();
+    let table_pattern_clone = table_pattern.clone();
+    let table_pattern_clone2 = table_pattern.clone();
+    let table_pattern_clone3 = table_pattern.clone();
+
+    let cached_repository = gen_cached_repository(model, &managers);
+
     quote! {
+        #cached_repository
+
         #[allow(unused_macros)]
         macro_rules! #macro_name_ident {
             (dyn $ctx_view:path => $ctx_main_db_method:ident, $crud_algorithms:ty) => {
                 macro_rules! __ctx { () => { &dyn $ctx_view } }
-                #out_case1_noarchive
+                #out_case1_positional
             };
             (dyn $ctx_view:path => ($ctx_main_db_method:ident, $ctx_archive_db_method:ident), $crud_algorithms:ty) => {
                 macro_rules! __ctx { () => { &dyn $ctx_view } }
-                #out_case2_witharchive
+                #out_case2_positional
             };
             ($ctx:path => $ctx_main_db_method:ident, $crud_algorithms:ty) => {
                 macro_rules! __ctx { () => { & $ctx } }
-                #out_case1_clone
+                #out_case1_positional_clone
             };
             ($ctx:path => ($ctx_main_db_method:ident, $ctx_archive_db_method:ident), $crud_algorithms:ty) => {
+                macro_rules! __ctx { () => { & $ctx } }
+                #out_case2_positional_clone
+            };
+            (dyn $ctx_view:path => { main: $ctx_main_db_method:ident #(#table_pattern)* $(,)? }, $crud_algorithms:ty) => {
+                macro_rules! __ctx { () => { &dyn $ctx_view } }
+                #out_case1_noarchive
+            };
+            (dyn $ctx_view:path => { main: $ctx_main_db_method:ident, archive: $ctx_archive_db_method:ident #(#table_pattern_clone)* $(,)? }, $crud_algorithms:ty) => {
+                macro_rules! __ctx { () => { &dyn $ctx_view } }
+                #out_case2_witharchive
+            };
+            ($ctx:path => { main: $ctx_main_db_method:ident #(#table_pattern_clone2)* $(,)? }, $crud_algorithms:ty) => {
+                macro_rules! __ctx { () => { & $ctx } }
+                #out_case1_clone
+            };
+            ($ctx:path => { main: $ctx_main_db_method:ident, archive: $ctx_archive_db_method:ident #(#table_pattern_clone3)* $(,)? }, $crud_algorithms:ty) => {
                 macro_rules! __ctx { () => { & $ctx } }
                 #out_case2_clone
             };
@@ -232,6 +378,219 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
     }
 }
 
+/// Emits `{Repo}Cache` and the `{Repo}Cached` wrapper, if any object declares
+/// a `cache_ttl`. The cached reads and the invalidation on mutations live in
+/// the hooked managers (see `hooks.rs`), which find the cache through the
+/// repository's `cache` method.
+fn gen_cached_repository(model: &ConfigModel, managers: &[(Ident, TokenStream)]) -> TokenStream {
+    if !model.has_cached_objects() {
+        return TokenStream::new();
+    }
+    let repo_name = &model.repository_name;
+    let cache_ident = cache_ident_for(repo_name);
+    let cache_map_ident = cache_map_ident_for(repo_name);
+    let cached_ident = Ident::new(&format!("{}Cached", repo_name), repo_name.span());
+
+    // Standard objects cache single items (`get` / `find`) as well as lists;
+    // batch and indexed singleton objects only cache lists.
+    let standard = model
+        .ordered_objects
+        .iter()
+        .chain(model.unordered_objects.iter())
+        .filter_map(|object| Some((&object.name, object.cache_ttl?, true)));
+    let others = model
+        .batch_objects
+        .iter()
+        .filter_map(|object| Some((&object.name, object.cache_ttl?, false)))
+        .chain(
+            model
+                .indexed_singleton_objects
+                .iter()
+                .filter_map(|object| Some((&object.name, object.cache_ttl?, false))),
+        );
+    let mut fields = Vec::new();
+    let mut inits = Vec::new();
+    let mut clears = Vec::new();
+    for (ty_ident, ttl, caches_items) in standard.chain(others) {
+        let lists_field = cache_lists_field_for(ty_ident);
+        fields.push(quote! {
+            #lists_field: #cache_map_ident<::std::option::Option<::fractic_aws_dynamo::schema::PkSk>, ::std::vec::Vec<#ty_ident>>,
+        });
+        inits.push(quote! {
+            #lists_field: #cache_map_ident::new(::std::time::Duration::from_secs(#ttl)),
+        });
+        clears.push(quote! { self.#lists_field.clear(); });
+        if caches_items {
+            let items_field = cache_items_field_for(ty_ident);
+            fields.push(quote! {
+                #items_field: #cache_map_ident<::fractic_aws_dynamo::schema::PkSk, #ty_ident>,
+            });
+            inits.push(quote! {
+                #items_field: #cache_map_ident::new(::std::time::Duration::from_secs(#ttl)),
+            });
+            clears.push(quote! { self.#items_field.clear(); });
+        }
+    }
+
+    let cache_doc = format!(
+        " Entries cached by `{}`, keyed by ID for single items and by parent ID for lists.",
+        cached_ident
+    );
+
+    let forwards = managers.iter().map(|(method_ident, manage_ty)| {
+        quote! {
+            fn #method_ident(&self) -> & #manage_ty {
+                self.inner.#method_ident()
+            }
+        }
+    });
+    // Inherent accessors take precedence over the trait's, so mutations made
+    // through `cached.manage_*()` go through the invalidating hooked managers.
+    let hooked_accessors = model
+        .ordered_objects
+        .iter()
+        .chain(model.unordered_objects.iter())
+        .map(|object| &object.name)
+        .chain(model.batch_objects.iter().map(|object| &object.name))
+        .chain(model.singleton_objects.iter().map(|object| &object.name))
+        .chain(
+            model
+                .indexed_singleton_objects
+                .iter()
+                .map(|object| &object.name),
+        )
+        .filter(|ty_ident| model.is_intercepted(ty_ident))
+        .map(|ty_ident| {
+            let method_ident = method_ident_for("manage", ty_ident);
+            let hooked_method_ident = hooked_manage_ident_for(ty_ident);
+            let hooked_ident = hooked_manager_ident_for(ty_ident);
+            quote! {
+                pub fn #method_ident(&self) -> #hooked_ident<'_> {
+                    <dyn #repo_name>::#hooked_method_ident(self)
+                }
+            }
+        });
+    let event_sink_forward = if model.events {
+        let sink_trait = event_sink_trait_ident_for(repo_name);
+        quote! {
+            fn event_sink(&self) -> &dyn #sink_trait {
+                self.inner.event_sink()
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    quote! {
+        #[doc(hidden)]
+        pub struct #cache_map_ident<K, V> {
+            ttl: ::std::time::Duration,
+            entries: ::std::sync::Mutex<::std::collections::HashMap<K, (::std::time::Instant, V)>>,
+        }
+        impl<K: ::std::cmp::Eq + ::std::hash::Hash, V: ::std::clone::Clone> #cache_map_ident<K, V> {
+            fn new(ttl: ::std::time::Duration) -> Self {
+                Self {
+                    ttl,
+                    entries: ::std::sync::Mutex::new(::std::collections::HashMap::new()),
+                }
+            }
+            fn entries(&self) -> ::std::sync::MutexGuard<'_, ::std::collections::HashMap<K, (::std::time::Instant, V)>> {
+                // Entries are always left consistent, so a panic while the
+                // lock was held does not invalidate them.
+                self.entries.lock().unwrap_or_else(::std::sync::PoisonError::into_inner)
+            }
+            fn get(&self, key: &K) -> ::std::option::Option<V> {
+                let mut entries = self.entries();
+                match entries.get(key) {
+                    ::std::option::Option::Some((cached_at, value)) if cached_at.elapsed() < self.ttl => {
+                        ::std::option::Option::Some(value.clone())
+                    }
+                    ::std::option::Option::Some(_) => {
+                        entries.remove(key);
+                        ::std::option::Option::None
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+            fn insert(&self, key: K, value: V) {
+                self.entries().insert(key, (::std::time::Instant::now(), value));
+            }
+            fn remove(&self, key: &K) {
+                self.entries().remove(key);
+            }
+            fn clear(&self) {
+                self.entries().clear();
+            }
+        }
+
+        #[doc = #cache_doc]
+        pub struct #cache_ident {
+            #(#fields)*
+        }
+        impl #cache_ident {
+            fn new() -> Self {
+                Self {
+                    #(#inits)*
+                }
+            }
+            /// Drops every cached entry.
+            pub fn clear(&self) {
+                #(#clears)*
+            }
+        }
+
+        /// Repository caching the reads of the objects that declare a
+        /// `cache_ttl`. Reads and mutations made through the hooked managers
+        /// (the generated annotations and handlers, and this wrapper's own
+        /// `manage_*` accessors) use and invalidate the cache. The trait's
+        /// plain managers, still reachable through `dyn` or `inner()`, bypass
+        /// it.
+        pub struct #cached_ident<R: #repo_name> {
+            inner: R,
+            cache: #cache_ident,
+        }
+        impl<R: #repo_name> #cached_ident<R> {
+            pub fn new(inner: R) -> Self {
+                Self {
+                    inner,
+                    cache: #cache_ident::new(),
+                }
+            }
+            pub fn inner(&self) -> &R {
+                &self.inner
+            }
+        }
+        impl<R: #repo_name + 'static> #cached_ident<R> {
+            #(#hooked_accessors)*
+        }
+        impl<R: #repo_name> #repo_name for #cached_ident<R> {
+            #(#forwards)*
+            #event_sink_forward
+            fn cache(&self) -> ::std::option::Option<& #cache_ident> {
+                ::std::option::Option::Some(&self.cache)
+            }
+        }
+    }
+}
+
+pub(crate) fn cache_ident_for(repo_name: &Ident) -> Ident {
+    Ident::new(&format!("{}Cache", repo_name), repo_name.span())
+}
+
+fn cache_map_ident_for(repo_name: &Ident) -> Ident {
+    Ident::new(&format!("{}CacheMap", repo_name), repo_name.span())
+}
+
+pub(crate) fn cache_items_field_for(ty_ident: &Ident) -> Ident {
+    let snake = to_snake_case(&ty_ident.to_string());
+    Ident::new(&format!("{}_items", snake), ty_ident.span())
+}
+
+pub(crate) fn cache_lists_field_for(ty_ident: &Ident) -> Ident {
+    let snake = to_snake_case(&ty_ident.to_string());
+    Ident::new(&format!("{}_lists", snake), ty_ident.span())
+}
+
 fn gen_field_init_impl(
     fields: &mut Vec<TokenStream>,
     inits: &mut Vec<TokenStream>,
@@ -266,10 +625,62 @@ fn method_ident_for(prefix: &str, ident: &Ident) -> Ident {
     Ident::new(&name, ident.span())
 }
 
-fn db_ident_for(is_archive: bool) -> TokenStream {
-    if is_archive {
+fn db_ident_for(is_archive: bool, table: Option<&Ident>) -> TokenStream {
+    if let Some(table) = table {
+        let ident = table_db_ident_for(table);
+        quote! { #ident }
+    } else if is_archive {
         quote! { archive_db }
     } else {
         quote! { main_db }
     }
 }
+
+fn table_db_ident_for(table: &Ident) -> Ident {
+    Ident::new(&format!("{}_db", table), table.span())
+}
+
+fn table_db_method_ident_for(table: &Ident) -> Ident {
+    Ident::new(&format!("ctx_{}_db_method", table), table.span())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::generate;
+    use crate::{crud::test_model, testing};
+
+    #[test]
+    fn cached_repository_hands_out_invalidating_managers() {
+        let model = test_model(
+            r#"
+            MyRepo;
+            root Project {
+                ordered_children: ProjectTask
+            }
+            ordered ProjectTask {
+                parent: Project
+                cache_ttl: 60
+            }
+            root Note {}
+            "#,
+        )
+        .unwrap();
+        let items = testing::items(generate(&model));
+        // Each of the macro's rules defines the same accessors.
+        let outputs = |name: &str| {
+            testing::fns(&items, name)
+                .iter()
+                .map(|f| f.output())
+                .collect::<Vec<_>>()
+        };
+
+        // The cached object and its ancestor are intercepted; `Note` is not.
+        assert!(
+            outputs("manage_project_task").contains(&"-> ProjectTaskHookedManager < '_ >".into())
+        );
+        assert!(outputs("manage_project").contains(&"-> ProjectHookedManager < '_ >".into()));
+        let note = outputs("manage_note");
+        assert!(!note.is_empty());
+        assert!(note.iter().all(|output| !output.contains("HookedManager")));
+    }
+}
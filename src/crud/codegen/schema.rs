@@ -0,0 +1,117 @@
+use proc_macro2::TokenStream;
+use syn::Ident;
+
+use crate::{
+    crud::model::{ConfigModel, HasParents as _, StandardDef},
+    schema::{Json, schema_const},
+};
+
+/// `{REPO}_SCHEMA`, if the repository declares `#[schema]`. Objects are listed
+/// grouped by kind; unordered objects without a parent are reported as
+/// `root`, which is how they were declared.
+pub fn generate(model: &ConfigModel) -> TokenStream {
+    if !model.schema {
+        return TokenStream::new();
+    }
+
+    let mut objects = Vec::new();
+    for object in &model.phantom_objects {
+        let children = object
+            .ordered_children
+            .iter()
+            .chain(&object.unordered_children)
+            .chain(&object.batch_children)
+            .chain(&object.singleton_children)
+            .chain(&object.indexed_singleton_children);
+        objects.push(object_json(
+            &object.name,
+            "phantom",
+            None,
+            children,
+            false,
+            false,
+        ));
+    }
+    for object in &model.ordered_objects {
+        objects.push(standard_json(object, "ordered"));
+    }
+    for object in &model.unordered_objects {
+        let kind = match object.parents {
+            Some(_) => "unordered",
+            None => "root",
+        };
+        objects.push(standard_json(object, kind));
+    }
+    for object in &model.batch_objects {
+        objects.push(object_json(
+            &object.name,
+            "batch",
+            object.parents(),
+            [],
+            object.is_archive,
+            false,
+        ));
+    }
+    for object in &model.singleton_objects {
+        objects.push(object_json(
+            &object.name,
+            "singleton",
+            object.parents(),
+            [],
+            object.is_archive,
+            false,
+        ));
+    }
+    for object in &model.indexed_singleton_objects {
+        objects.push(object_json(
+            &object.name,
+            "indexed_singleton",
+            object.parents(),
+            [],
+            object.is_archive,
+            false,
+        ));
+    }
+
+    let document = Json::Object(vec![
+        ("repository", Json::string(&model.repository_name)),
+        ("objects", Json::Array(objects)),
+    ]);
+    schema_const(&model.repository_name, document, "crud_scaffolding")
+}
+
+fn standard_json(object: &StandardDef, kind: &str) -> Json {
+    let children = object
+        .ordered_children
+        .iter()
+        .chain(&object.unordered_children)
+        .chain(&object.batch_children)
+        .chain(&object.singleton_children)
+        .chain(&object.indexed_singleton_children);
+    object_json(
+        &object.name,
+        kind,
+        object.parents(),
+        children,
+        object.is_archive,
+        object.is_movable_archive,
+    )
+}
+
+fn object_json<'a>(
+    name: &Ident,
+    kind: &str,
+    parents: Option<&[Ident]>,
+    children: impl IntoIterator<Item = &'a Ident>,
+    is_archive: bool,
+    is_movable_archive: bool,
+) -> Json {
+    Json::Object(vec![
+        ("name", Json::string(name)),
+        ("kind", Json::string(kind)),
+        ("parents", Json::strings(parents.unwrap_or_default())),
+        ("children", Json::strings(children)),
+        ("archive", Json::Bool(is_archive)),
+        ("movable_archive", Json::Bool(is_movable_archive)),
+    ])
+}
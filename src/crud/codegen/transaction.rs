@@ -0,0 +1,690 @@
+//! Transactional write builder. The generated items are emitted inside the
+//! annotations macro, since (like the annotations) they reach the repository
+//! through the caller's context type.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+
+use crate::{
+    crud::model::{BatchDef, ConfigModel, IndexedSingletonDef, SingletonDef, StandardDef},
+    helpers::to_snake_case,
+};
+
+use super::{
+    annotations::pluralize_pascal,
+    events::{created_variant_for, deleted_variant_for, event_enum_ident_for, updated_variant_for},
+    hooks::{for_each, hooks_trait_ident_for, manager_ident_for},
+    validation::gen_data_validation,
+};
+
+pub fn generate(model: &ConfigModel) -> TokenStream {
+    if !model.transactions {
+        return TokenStream::new();
+    }
+    let repo_name = &model.repository_name;
+    let transaction_ident = transaction_ident_for(repo_name);
+
+    let staged = model
+        .ordered_objects
+        .iter()
+        .map(|object| gen_standard_staging(model, object, true))
+        .chain(
+            model
+                .unordered_objects
+                .iter()
+                .map(|object| gen_standard_staging(model, object, false)),
+        )
+        .chain(
+            model
+                .batch_objects
+                .iter()
+                .map(|object| gen_batch_staging(model, object)),
+        )
+        .chain(
+            model
+                .singleton_objects
+                .iter()
+                .map(|object| gen_singleton_staging(model, object)),
+        )
+        .chain(
+            model
+                .indexed_singleton_objects
+                .iter()
+                .map(|object| gen_indexed_singleton_staging(model, object)),
+        )
+        .collect::<Vec<_>>();
+
+    let fields = staged.iter().map(|s| &s.fields);
+    let inits = staged.iter().map(|s| &s.inits);
+    let methods = staged.iter().map(|s| &s.methods);
+    let after_commit = staged.iter().map(|s| &s.after_commit);
+    let any_hooks = staged.iter().any(|s| !s.after_commit.is_empty());
+
+    let (events_field, events_init, publish_events) = if model.events {
+        let event_enum = event_enum_ident_for(repo_name);
+        (
+            quote! { events: ::std::vec::Vec<#event_enum>, },
+            quote! { events: ::std::vec::Vec::new(), },
+            quote! {
+                for event in self.events {
+                    repo.event_sink().publish(event)?;
+                }
+            },
+        )
+    } else {
+        (TokenStream::new(), TokenStream::new(), TokenStream::new())
+    };
+    // Staged writes bypass the hooked managers, so a cached repository is
+    // cleared wholesale once they are applied.
+    let clear_cache = if model.has_cached_objects() {
+        quote! {
+            if let ::std::option::Option::Some(cache) = repo.cache() {
+                cache.clear();
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+    // The context is only needed to reach the repository for the cache, hooks
+    // and events handled after the commit.
+    let (ctx_binding, bind_repo) = if any_hooks || model.events || model.has_cached_objects() {
+        (
+            quote! { ctx },
+            quote! { let repo = ctx.$ctx_repo_accessor().await?; },
+        )
+    } else {
+        (quote! { _ctx }, TokenStream::new())
+    };
+
+    quote! {
+        /// Stages writes across any of the repository's objects and commits
+        /// them atomically: if any write fails (for example a version
+        /// conflict), none of them are applied. `before_*` hooks run while
+        /// staging; `after_*` hooks and events only once the commit succeeds.
+        pub struct #transaction_ident {
+            inner: ::fractic_aws_dynamo::ext::crud::CrudTransaction,
+            #(#fields)*
+            #events_field
+        }
+
+        impl #transaction_ident {
+            pub fn new() -> Self {
+                Self {
+                    inner: ::fractic_aws_dynamo::ext::crud::CrudTransaction::new(),
+                    #(#inits)*
+                    #events_init
+                }
+            }
+
+            #(#methods)*
+
+            pub async fn commit(self, #ctx_binding: __ctx!()) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+                #bind_repo
+                self.inner.commit().await?;
+                #clear_cache
+                #(#after_commit)*
+                #publish_events
+                ::std::result::Result::Ok(())
+            }
+        }
+
+        impl ::std::default::Default for #transaction_ident {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    }
+}
+
+pub(crate) fn transaction_ident_for(repo_name: &Ident) -> Ident {
+    Ident::new(&format!("{}Transaction", repo_name), repo_name.span())
+}
+
+/// Tokens contributed by a single object to the transaction builder.
+struct Staged {
+    fields: TokenStream,
+    inits: TokenStream,
+    methods: TokenStream,
+    after_commit: TokenStream,
+}
+
+/// Tokens shared by all staging methods of a given object.
+struct StagingCtx<'a> {
+    ty_ident: &'a Ident,
+    ty_data: TokenStream,
+    manager_ident: Ident,
+    // `<Hooks as {Type}Hooks>`, if the object declares `hooks`.
+    hooks: Option<TokenStream>,
+    // Rejects invalid `data` / `item.data`, if the object declares `validate`.
+    data_check: TokenStream,
+    item_check: TokenStream,
+    data_checks: TokenStream,
+    // `{Repo}Event`, if the repository emits events.
+    event_enum: Option<Ident>,
+    is_child: bool,
+    // Generic parameters and leading `parent` argument for child objects;
+    // empty for root objects.
+    generics: TokenStream,
+    parent_param: TokenStream,
+    parent_arg: TokenStream,
+    // Items created / IDs deleted by the staged writes, kept for the
+    // `after_*` hooks.
+    created_field: Ident,
+    deleted_field: Ident,
+}
+
+impl<'a> StagingCtx<'a> {
+    fn new(model: &ConfigModel, ty_ident: &'a Ident, is_child: bool) -> Self {
+        let snake = to_snake_case(&ty_ident.to_string());
+        let hooks = model.hooks_for(ty_ident).map(|hooks_ident| {
+            let hooks_trait = hooks_trait_ident_for(ty_ident);
+            quote! { <#hooks_ident as #hooks_trait> }
+        });
+        let event_enum = model
+            .events
+            .then(|| event_enum_ident_for(&model.repository_name));
+        let (generics, parent_param, parent_arg) = if is_child {
+            // Events record the parent's ID, so the parent must be a full
+            // object rather than any `ParentOf` implementor.
+            let event_bound = event_enum
+                .as_ref()
+                .map(|_| quote! { + ::fractic_aws_dynamo::schema::DynamoObject });
+            (
+                quote! { <P: ::fractic_aws_dynamo::ext::crud::ParentOf<#ty_ident> #event_bound> },
+                quote! { parent: &P, },
+                quote! { parent, },
+            )
+        } else {
+            (TokenStream::new(), TokenStream::new(), TokenStream::new())
+        };
+        Self {
+            ty_ident,
+            ty_data: quote! { <#ty_ident as ::fractic_aws_dynamo::schema::DynamoObject>::Data },
+            // Hooks are run by the transaction itself, so always stage through
+            // the plain manager.
+            manager_ident: manager_ident_for(ty_ident, false),
+            hooks,
+            data_check: gen_data_validation(model, ty_ident, quote! { &data }, false),
+            item_check: gen_data_validation(model, ty_ident, quote! { &item.data }, false),
+            data_checks: gen_data_validation(model, ty_ident, quote! { data.iter() }, true),
+            event_enum,
+            is_child,
+            generics,
+            parent_param,
+            parent_arg,
+            created_field: Ident::new(&format!("created_{}", snake), ty_ident.span()),
+            deleted_field: Ident::new(&format!("deleted_{}", snake), ty_ident.span()),
+        }
+    }
+
+    fn method_ident(&self, prefix: &str, suffix: &str) -> Ident {
+        let snake = to_snake_case(&self.ty_ident.to_string());
+        Ident::new(
+            &format!("{}_{}{}", prefix, snake, suffix),
+            self.ty_ident.span(),
+        )
+    }
+
+    fn staged(&self, methods: TokenStream) -> Staged {
+        let Self {
+            ty_ident,
+            created_field,
+            deleted_field,
+            ..
+        } = self;
+        match &self.hooks {
+            Some(hooks) => Staged {
+                fields: quote! {
+                    #created_field: ::std::vec::Vec<#ty_ident>,
+                    #deleted_field: ::std::vec::Vec<::fractic_aws_dynamo::schema::PkSk>,
+                },
+                inits: quote! {
+                    #created_field: ::std::vec::Vec::new(),
+                    #deleted_field: ::std::vec::Vec::new(),
+                },
+                methods,
+                after_commit: quote! {
+                    for item in &self.#created_field {
+                        #hooks::after_create(&*repo, item).await?;
+                    }
+                    for id in &self.#deleted_field {
+                        #hooks::after_delete(&*repo, id).await?;
+                    }
+                },
+            },
+            None => Staged {
+                fields: TokenStream::new(),
+                inits: TokenStream::new(),
+                methods,
+                after_commit: TokenStream::new(),
+            },
+        }
+    }
+
+    fn hook(&self, name: &str, arg: TokenStream) -> TokenStream {
+        match &self.hooks {
+            Some(hooks) => {
+                let name = Ident::new(name, self.ty_ident.span());
+                quote! { #hooks::#name(&*repo, #arg).await?; }
+            }
+            None => TokenStream::new(),
+        }
+    }
+
+    /// Records an event for the item with the given ID (a `&PkSk`
+    /// expression), as the hooked managers do.
+    fn event(&self, variant: Ident, id: TokenStream, scoped: bool) -> TokenStream {
+        match &self.event_enum {
+            Some(event_enum) => {
+                let parent_id = if self.is_child && !scoped {
+                    quote! { ::fractic_aws_dynamo::schema::PkSk::parent_id(#id) }
+                } else {
+                    self.parent_id(scoped)
+                };
+                quote! {
+                    self.events.push(#event_enum::#variant {
+                        id: (#id).clone(),
+                        parent_id: #parent_id,
+                    });
+                }
+            }
+            None => TokenStream::new(),
+        }
+    }
+
+    /// ID of the parent the current write is scoped to, if known.
+    fn parent_id(&self, scoped: bool) -> TokenStream {
+        if self.is_child && scoped {
+            quote! {
+                ::std::option::Option::Some(::fractic_aws_dynamo::schema::DynamoObject::id(parent).clone())
+            }
+        } else {
+            quote! { ::std::option::Option::None }
+        }
+    }
+
+    fn data_binding(&self) -> TokenStream {
+        if self.hooks.is_some() {
+            quote! { mut data }
+        } else {
+            quote! { data }
+        }
+    }
+
+    fn staged_create(&self, item: TokenStream) -> TokenStream {
+        let created_field = &self.created_field;
+        let record = match &self.hooks {
+            Some(_) => quote! { self.#created_field.push((#item).clone()); },
+            None => TokenStream::new(),
+        };
+        let event = self.event(
+            created_variant_for(self.ty_ident),
+            quote! { &(#item).id },
+            true,
+        );
+        quote! { #record #event }
+    }
+
+    fn staged_delete(&self, id: TokenStream, scoped: bool) -> TokenStream {
+        let deleted_field = &self.deleted_field;
+        let record = match &self.hooks {
+            Some(_) => quote! { self.#deleted_field.push((#id).clone()); },
+            None => TokenStream::new(),
+        };
+        let event = self.event(deleted_variant_for(self.ty_ident), id, scoped);
+        quote! { #record #event }
+    }
+
+    /// Stages the creation of a single item (`add_*`, `set_*`).
+    fn create_one(
+        &self,
+        prefix: &str,
+        manager_method: &str,
+        extra_param: TokenStream,
+        extra_arg: TokenStream,
+    ) -> TokenStream {
+        let Self {
+            ty_ident,
+            ty_data,
+            manager_ident,
+            data_check,
+            generics,
+            parent_param,
+            parent_arg,
+            ..
+        } = self;
+        let method = self.method_ident(prefix, "");
+        let manager_method = Ident::new(manager_method, ty_ident.span());
+        let data_binding = self.data_binding();
+        let before = self.hook("before_create", quote! { &mut data });
+        let staged = self.staged_create(quote! { &item });
+        quote! {
+            pub async fn #method #generics(&mut self, ctx: __ctx!(), #parent_param #data_binding: #ty_data, #extra_param) -> ::std::result::Result<#ty_ident, ::fractic_server_error::ServerError> {
+                #data_check
+                let repo = ctx.$ctx_repo_accessor().await?;
+                #before
+                let item = repo.#manager_ident().#manager_method(&mut self.inner, #parent_arg data, #extra_arg).await?;
+                #staged
+                ::std::result::Result::Ok(item)
+            }
+        }
+    }
+
+    /// Stages the update of an item, conditional on its version for
+    /// `versioned` objects so that a concurrent write fails the commit.
+    fn update(&self, is_versioned: bool) -> TokenStream {
+        let Self {
+            ty_ident,
+            manager_ident,
+            item_check,
+            ..
+        } = self;
+        let method = self.method_ident("update", "");
+        let before = self.hook("before_update", quote! { item });
+        let stage = if is_versioned {
+            quote! {
+                repo.#manager_ident().stage_update_if_version(&mut self.inner, item, item.auto_fields.version).await?;
+            }
+        } else {
+            quote! {
+                repo.#manager_ident().stage_update(&mut self.inner, item).await?;
+            }
+        };
+        let event = self.event(updated_variant_for(ty_ident), quote! { &item.id }, false);
+        quote! {
+            pub async fn #method(&mut self, ctx: __ctx!(), item: & #ty_ident) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+                #item_check
+                let repo = ctx.$ctx_repo_accessor().await?;
+                #before
+                #stage
+                #event
+                ::std::result::Result::Ok(())
+            }
+        }
+    }
+
+    /// Stages the deletion of an item.
+    fn delete_one(&self, prefix: &str, suffix: &str, manager_method: &str) -> TokenStream {
+        let Self {
+            ty_ident,
+            manager_ident,
+            ..
+        } = self;
+        let method = self.method_ident(prefix, suffix);
+        let manager_method = Ident::new(manager_method, ty_ident.span());
+        let before = self.hook("before_delete", quote! { &id });
+        let staged = self.staged_delete(quote! { &id }, false);
+        let bind_id = if before.is_empty() && staged.is_empty() {
+            TokenStream::new()
+        } else {
+            quote! { let id = item.id.clone(); }
+        };
+        quote! {
+            pub async fn #method(&mut self, ctx: __ctx!(), item: #ty_ident) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+                let repo = ctx.$ctx_repo_accessor().await?;
+                #bind_id
+                #before
+                repo.#manager_ident().#manager_method(&mut self.inner, item).await?;
+                #staged
+                ::std::result::Result::Ok(())
+            }
+        }
+    }
+}
+
+fn gen_standard_staging(model: &ConfigModel, object: &StandardDef, ordered: bool) -> Staged {
+    let ty_ident = &object.name;
+    let ctx = StagingCtx::new(model, ty_ident, object.parents.is_some());
+
+    let (after_param, after_arg) = if ordered {
+        (
+            quote! { after: ::std::option::Option<& #ty_ident> },
+            quote! { after },
+        )
+    } else {
+        (TokenStream::new(), TokenStream::new())
+    };
+    let add = ctx.create_one("add", "stage_add", after_param, after_arg);
+    let update = ctx.update(object.is_versioned);
+    // Soft deletes tombstone the item, as the non-transactional `delete` does.
+    // Recursive deletes touch an unbounded number of items, which does not fit
+    // in a single transaction.
+    let delete = if object.is_soft_delete {
+        ctx.delete_one("delete", "", "stage_soft_delete")
+    } else if object.has_children() {
+        ctx.delete_one("delete", "_non_recursive", "stage_delete_non_recursive")
+    } else {
+        ctx.delete_one("delete", "", "stage_delete")
+    };
+
+    ctx.staged(quote! {
+        #add
+        #update
+        #delete
+    })
+}
+
+fn gen_batch_staging(model: &ConfigModel, object: &BatchDef) -> Staged {
+    let ty_ident = &object.name;
+    let ctx = StagingCtx::new(model, ty_ident, object.parents.is_some());
+    let StagingCtx {
+        ty_data,
+        manager_ident,
+        data_checks,
+        generics,
+        parent_param,
+        parent_arg,
+        ..
+    } = &ctx;
+
+    let plural_snake = to_snake_case(&pluralize_pascal(&ty_ident.to_string()));
+    let method = Ident::new(&format!("replace_all_{}", plural_snake), ty_ident.span());
+    let data_binding = ctx.data_binding();
+    let before_create = for_each(
+        quote! { d },
+        quote! { data.iter_mut() },
+        ctx.hook("before_create", quote! { d }),
+    );
+    // Existing items are only listed if something needs their IDs.
+    let before_delete = ctx.hook("before_delete", quote! { id });
+    let staged_delete = ctx.staged_delete(quote! { id }, true);
+    let (list_existing, before_delete, staged_delete) = if staged_delete.is_empty() {
+        (TokenStream::new(), TokenStream::new(), TokenStream::new())
+    } else {
+        (
+            quote! {
+                let ids = repo
+                    .#manager_ident()
+                    .query_all(#parent_arg)
+                    .await?
+                    .into_iter()
+                    .map(|item| item.id)
+                    .collect::<::std::vec::Vec<_>>();
+            },
+            for_each(quote! { id }, quote! { &ids }, before_delete),
+            for_each(quote! { id }, quote! { &ids }, staged_delete),
+        )
+    };
+    let staged_create = for_each(
+        quote! { item },
+        quote! { &items },
+        ctx.staged_create(quote! { item }),
+    );
+
+    ctx.staged(quote! {
+        // Replacing deletes every existing item and creates the new ones, so
+        // both sets of hooks run.
+        pub async fn #method #generics(&mut self, ctx: __ctx!(), #parent_param #data_binding: ::std::vec::Vec<#ty_data>) -> ::std::result::Result<::std::vec::Vec<#ty_ident>, ::fractic_server_error::ServerError> {
+            #data_checks
+            let repo = ctx.$ctx_repo_accessor().await?;
+            #list_existing
+            #before_delete
+            #before_create
+            let items = repo.#manager_ident().stage_replace_all_ordered(&mut self.inner, #parent_arg data).await?;
+            #staged_delete
+            #staged_create
+            ::std::result::Result::Ok(items)
+        }
+    })
+}
+
+fn gen_singleton_staging(model: &ConfigModel, object: &SingletonDef) -> Staged {
+    let ty_ident = &object.name;
+    let ctx = StagingCtx::new(model, ty_ident, object.parents.is_some());
+    let StagingCtx {
+        generics,
+        parent_param,
+        parent_arg,
+        ..
+    } = &ctx;
+
+    let set = ctx.create_one("set", "stage_set", TokenStream::new(), TokenStream::new());
+    let delete = ctx.method_ident("delete", "");
+    let delete_body = gen_find_and_delete(&ctx, parent_arg.clone(), parent_arg.clone());
+
+    ctx.staged(quote! {
+        #set
+        pub async fn #delete #generics(&mut self, ctx: __ctx!(), #parent_param) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+            let repo = ctx.$ctx_repo_accessor().await?;
+            #delete_body
+            ::std::result::Result::Ok(())
+        }
+    })
+}
+
+fn gen_indexed_singleton_staging(model: &ConfigModel, object: &IndexedSingletonDef) -> Staged {
+    let ty_ident = &object.name;
+    let ctx = StagingCtx::new(model, ty_ident, object.parents.is_some());
+    let StagingCtx {
+        generics,
+        parent_param,
+        parent_arg,
+        ..
+    } = &ctx;
+
+    let set = ctx.create_one("set", "stage_set", TokenStream::new(), TokenStream::new());
+    let delete = ctx.method_ident("delete", "");
+    let delete_body = gen_find_and_delete(
+        &ctx,
+        quote! { #parent_arg key.as_ref() },
+        quote! { #parent_arg key },
+    );
+
+    ctx.staged(quote! {
+        #set
+        pub async fn #delete #generics(&mut self, ctx: __ctx!(), #parent_param key: impl ::std::convert::AsRef<str>) -> ::std::result::Result<(), ::fractic_server_error::ServerError> {
+            let repo = ctx.$ctx_repo_accessor().await?;
+            #delete_body
+            ::std::result::Result::Ok(())
+        }
+    })
+}
+
+/// Stages the deletion of a (possibly absent) singleton, looking it up first
+/// only if its ID is needed by the hooks or events.
+fn gen_find_and_delete(
+    ctx: &StagingCtx,
+    find_args: TokenStream,
+    delete_args: TokenStream,
+) -> TokenStream {
+    let manager_ident = &ctx.manager_ident;
+    let before_delete = ctx.hook("before_delete", quote! { &item.id });
+    let staged_delete = ctx.staged_delete(quote! { &item.id }, true);
+    if staged_delete.is_empty() {
+        return quote! {
+            repo.#manager_ident().stage_delete(&mut self.inner, #delete_args).await?;
+        };
+    }
+    let before_delete = if_existing(before_delete);
+    let staged_delete = if_existing(staged_delete);
+    quote! {
+        let existing = repo.#manager_ident().find(#find_args).await?;
+        #before_delete
+        repo.#manager_ident().stage_delete(&mut self.inner, #delete_args).await?;
+        #staged_delete
+    }
+}
+
+fn if_existing(body: TokenStream) -> TokenStream {
+    if body.is_empty() {
+        return TokenStream::new();
+    }
+    quote! {
+        if let ::std::option::Option::Some(item) = &existing {
+            #body
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::generate;
+    use crate::{crud::test_model, testing};
+
+    #[test]
+    fn stages_soft_deletes_for_soft_delete_objects() {
+        let model = test_model(
+            r#"
+            #[transactions]
+            MyRepo;
+            soft_delete root Project {
+                ordered_children: ProjectTask
+            }
+            soft_delete ordered ProjectTask {
+                parent: Project
+            }
+            "#,
+        )
+        .unwrap();
+        let items = testing::items(generate(&model));
+
+        for method in ["delete_project", "delete_project_task"] {
+            let delete = testing::fn_of(&items, Some("MyRepoTransaction"), method);
+            assert_eq!(delete.param_names(), ["ctx", "item"]);
+            assert_eq!(
+                delete.output(),
+                "-> :: std :: result :: Result < () , :: fractic_server_error :: ServerError >"
+            );
+            assert!(delete.body_contains(". stage_soft_delete (& mut self . inner , item)"));
+        }
+        assert!(testing::fns(&items, "delete_project_non_recursive").is_empty());
+    }
+
+    #[test]
+    fn stages_non_recursive_deletes_for_parents() {
+        let model = test_model(
+            r#"
+            #[transactions]
+            MyRepo;
+            root Project {
+                ordered_children: ProjectTask
+            }
+            ordered ProjectTask {
+                parent: Project
+            }
+            "#,
+        )
+        .unwrap();
+        let items = testing::items(generate(&model));
+
+        let delete_parent = testing::fn_of(
+            &items,
+            Some("MyRepoTransaction"),
+            "delete_project_non_recursive",
+        );
+        assert!(
+            delete_parent.body_contains(". stage_delete_non_recursive (& mut self . inner , item)")
+        );
+        assert!(testing::fns(&items, "delete_project").is_empty());
+        let delete_child = testing::fn_of(&items, Some("MyRepoTransaction"), "delete_project_task");
+        assert!(delete_child.body_contains(". stage_delete (& mut self . inner , item)"));
+    }
+
+    #[test]
+    fn omits_transaction_without_option() {
+        let model = test_model("MyRepo; root Project {}").unwrap();
+        let items = testing::items(generate(&model));
+        assert!(!testing::type_names(&items).contains(&"MyRepoTransaction".to_string()));
+    }
+}
@@ -0,0 +1,70 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+
+use crate::crud::model::ConfigModel;
+
+pub fn generate(model: &ConfigModel) -> TokenStream {
+    let traits = model.validated_objects().into_iter().map(|ty_ident| {
+        let trait_ident = validate_trait_ident_for(ty_ident);
+        quote! {
+            /// Check applied to data received for a write (create, update, or
+            /// set) before anything is stored. Must be implemented for the
+            /// object's data type, as required by its `validate` prefix.
+            pub trait #trait_ident {
+                /// Describe why the data is invalid, if it is.
+                fn validate(&self) -> ::std::result::Result<(), ::std::string::String>;
+            }
+
+            // Report a missing implementation here, rather than in whichever
+            // generated write path happens to be expanded first.
+            const _: fn() = || {
+                fn __assert_validate<T: #trait_ident + ?Sized>() {}
+                __assert_validate::<<#ty_ident as ::fractic_aws_dynamo::schema::DynamoObject>::Data>();
+            };
+        }
+    });
+    quote! { #(#traits)* }
+}
+
+/// Statements rejecting invalid data for the given object (nothing, unless it
+/// has the `validate` prefix). `data` is an expression evaluating to a
+/// reference to the data, or to an iterator over references if `many`.
+pub(crate) fn gen_data_validation(
+    model: &ConfigModel,
+    ty_ident: &Ident,
+    data: TokenStream,
+    many: bool,
+) -> TokenStream {
+    if !model.is_validated(ty_ident) {
+        return TokenStream::new();
+    }
+    let trait_ident = validate_trait_ident_for(ty_ident);
+    let check = quote! {
+        if let ::std::result::Result::Err(__reason) = #trait_ident::validate(__data) {
+            return ::std::result::Result::Err(
+                ::fractic_aws_apigateway::InvalidCrudRequestParameters::new(
+                    &format!("invalid {} data: {}", stringify!(#ty_ident), __reason)
+                ).into()
+            );
+        }
+    };
+    if many {
+        quote! {
+            for __data in #data {
+                #check
+            }
+        }
+    } else {
+        quote! {
+            {
+                let __data = #data;
+                #check
+            }
+        }
+    }
+}
+
+pub(crate) fn validate_trait_ident_for(ty_ident: &Ident) -> Ident {
+    Ident::new(&format!("{}Validate", ty_ident), ty_ident.span())
+}
@@ -0,0 +1,235 @@
+//! Repositories split across modules. Each `crud_scaffolding_fragment!`
+//! contributes its objects through a hidden macro (as `#[crud_object]` does,
+//! see `crud::attribute`), and the repository's main invocation lists the
+//! modules to merge with `#[fragments(...)]`. The merged definitions are then
+//! validated and generated as if they had been written in one block.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    Attribute, Error, Ident, Meta, Path, PathSegment, Result, Token,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+};
+
+use crate::crud::{
+    ast::ConfigAst,
+    attribute::{contributor_macro, step},
+};
+
+/// Input of `crud_scaffolding_fragment!`: the repository the objects belong
+/// to, followed by object definitions as in `crud_scaffolding!`.
+pub struct FragmentArgs {
+    repository_name: Ident,
+    objects: TokenStream,
+}
+
+impl Parse for FragmentArgs {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let repository_name: Ident = input.parse()?;
+        input.parse::<Token![;]>()?;
+        let objects: TokenStream = input.parse()?;
+        // Report syntax errors here, rather than where the fragment is merged.
+        syn::parse2::<ConfigAst>(quote! { #repository_name; #objects })?;
+        Ok(Self {
+            repository_name,
+            objects,
+        })
+    }
+}
+
+/// Expansion of `crud_scaffolding_fragment!`.
+pub fn expand_fragment(args: FragmentArgs) -> TokenStream {
+    let FragmentArgs {
+        repository_name,
+        objects,
+    } = args;
+    contributor_macro(&fragment_macro_ident_for(&repository_name), &objects)
+}
+
+/// Hidden macro contributing a module's fragment of `repo_name`. Named after
+/// the repository only, since `#[fragments(...)]` lists modules; a module can
+/// therefore hold a single fragment per repository. The ident is spanned to
+/// the fragment's repository name, so a duplicate is reported there.
+pub(crate) fn fragment_macro_ident_for(repo_name: &Ident) -> Ident {
+    Ident::new(&format!("__crud_fragment_{}", repo_name), repo_name.span())
+}
+
+/// Separate the `#[fragments(...)]` option from the other repository options,
+/// resolving each listed module to its fragment macro.
+pub(crate) fn split_fragments(
+    options: Vec<Attribute>,
+    repo_name: &Ident,
+) -> Result<(Vec<Attribute>, Vec<Path>)> {
+    let mut other = Vec::new();
+    let mut fragments: Option<Vec<Path>> = None;
+    for attr in options {
+        if !attr.path().is_ident("fragments") {
+            other.push(attr);
+            continue;
+        }
+        if fragments.is_some() {
+            return Err(Error::new_spanned(
+                attr.path(),
+                "duplicate `fragments` option",
+            ));
+        }
+        let Meta::List(list) = &attr.meta else {
+            return Err(Error::new_spanned(
+                &attr.meta,
+                "expected a list of modules (e.g., `#[fragments(crate::billing)]`)",
+            ));
+        };
+        let modules = list.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?;
+        if modules.is_empty() {
+            return Err(Error::new_spanned(
+                list,
+                "expected at least one module (e.g., `#[fragments(crate::billing)]`)",
+            ));
+        }
+        fragments = Some(
+            modules
+                .into_iter()
+                .map(|mut module| {
+                    module
+                        .segments
+                        .push(PathSegment::from(fragment_macro_ident_for(repo_name)));
+                    module
+                })
+                .collect(),
+        );
+    }
+    Ok((other, fragments.unwrap_or_default()))
+}
+
+/// Leading part of a `crud_scaffolding!` invocation, enough to find a
+/// `#[fragments(...)]` option.
+struct Header {
+    options: Vec<Attribute>,
+    repository_name: Ident,
+    objects: TokenStream,
+}
+
+impl Parse for Header {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let options = input.call(Attribute::parse_outer)?;
+        let repository_name: Ident = input.parse()?;
+        input.parse::<Token![;]>()?;
+        Ok(Self {
+            options,
+            repository_name,
+            objects: input.parse()?,
+        })
+    }
+}
+
+/// If a `crud_scaffolding!` input merges fragments, the invocation collecting
+/// them (with the input's own objects as the initial definitions). `None` if
+/// there is nothing to merge, or the header is malformed (in which case the
+/// regular parser reports the error).
+pub fn merge_fragments(input: &TokenStream) -> Option<Result<TokenStream>> {
+    let Header {
+        options,
+        repository_name,
+        objects,
+    } = syn::parse2(input.clone()).ok()?;
+    let (options, fragments) = match split_fragments(options, &repository_name) {
+        Ok(split) => split,
+        Err(err) => return Some(Err(err)),
+    };
+    if fragments.is_empty() {
+        return None;
+    }
+    Some(Ok(step(
+        quote! { #(#options)* #repository_name },
+        fragments,
+        objects,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::TokenStream;
+    use quote::quote;
+    use syn::{Attribute, Ident, parse::Parser};
+
+    use super::{merge_fragments, split_fragments};
+
+    fn options(tokens: TokenStream) -> Vec<Attribute> {
+        Attribute::parse_outer.parse2(tokens).unwrap()
+    }
+
+    fn repo() -> Ident {
+        syn::parse_str("MyRepo").unwrap()
+    }
+
+    #[test]
+    fn resolves_fragment_modules_to_their_macros() {
+        let (other, fragments) = split_fragments(
+            options(quote! { #[events] #[fragments(crate::billing, super::tasks)] }),
+            &repo(),
+        )
+        .unwrap();
+
+        assert_eq!(other.len(), 1);
+        assert!(other[0].path().is_ident("events"));
+        let fragments: Vec<String> = fragments
+            .iter()
+            .map(|path| quote! { #path }.to_string())
+            .collect();
+        assert_eq!(
+            fragments,
+            [
+                quote! { crate::billing::__crud_fragment_MyRepo }.to_string(),
+                quote! { super::tasks::__crud_fragment_MyRepo }.to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_fragment_options() {
+        let err = |tokens: TokenStream| {
+            split_fragments(options(tokens), &repo())
+                .unwrap_err()
+                .to_string()
+        };
+        assert!(
+            err(quote! { #[fragments(a)] #[fragments(b)] })
+                .contains("duplicate `fragments` option")
+        );
+        assert!(err(quote! { #[fragments()] }).contains("expected at least one module"));
+        assert!(err(quote! { #[fragments] }).contains("expected a list of modules"));
+    }
+
+    #[test]
+    fn merges_fragments_after_the_inline_objects() {
+        let merged = merge_fragments(&quote! {
+            #[events]
+            #[fragments(billing)]
+            MyRepo;
+            root Persona {}
+        })
+        .unwrap()
+        .unwrap();
+
+        let expected = quote! {
+            billing::__crud_fragment_MyRepo! {
+                [#[events] MyRepo]
+                [[::fractic_crate_scaffolding::__crud_repository_step]]
+                root Persona {}
+            }
+        };
+        assert_eq!(merged.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn leaves_inputs_without_fragments_to_the_parser() {
+        assert!(merge_fragments(&quote! { #[events] MyRepo; root Persona {} }).is_none());
+        assert!(merge_fragments(&quote! { root Persona {} }).is_none());
+        assert!(
+            merge_fragments(&quote! { #[fragments()] MyRepo; })
+                .unwrap()
+                .is_err()
+        );
+    }
+}
@@ -2,26 +2,60 @@ use proc_macro2::TokenStream;
 use quote::quote;
 
 mod ast;
+mod attribute;
+mod fragment;
 mod codegen {
     pub mod annotations;
+    pub mod client;
+    pub mod diagram;
+    pub mod events;
+    pub mod filters;
     pub mod handlers;
+    pub mod hooks;
     pub mod repository;
     pub mod repository_impl;
+    pub mod schema;
+    pub mod transaction;
+    pub mod validation;
 }
 mod model;
 
 pub use ast::ConfigAst;
+pub use attribute::{
+    ObjectArgs, RepositoryArgs, StepArgs, expand_object, expand_repository, expand_step,
+};
+pub use fragment::{FragmentArgs, expand_fragment, merge_fragments};
 pub use model::ConfigModel;
 
 pub fn generate(model: &ConfigModel) -> TokenStream {
     let repository_trait = codegen::repository::generate(model);
+    let filter_structs = codegen::filters::generate(model);
+    let events = codegen::events::generate(model);
+    let validation_traits = codegen::validation::generate(model);
+    let hooked_managers = codegen::hooks::generate(model);
     let repository_impl_macro = codegen::repository_impl::generate(model);
     let annotations_macro = codegen::annotations::generate(model);
     let handlers_macro = codegen::handlers::generate(model);
+    let client = codegen::client::generate(model);
+    let schema = codegen::schema::generate(model);
+    let diagrams = codegen::diagram::generate(model);
     quote! {
         #repository_trait
+        #filter_structs
+        #events
+        #validation_traits
+        #hooked_managers
         #repository_impl_macro
         #annotations_macro
         #handlers_macro
+        #client
+        #schema
+        #diagrams
     }
 }
+
+/// Model of a DSL snippet, for the codegen tests.
+#[cfg(test)]
+pub(crate) fn test_model(dsl: &str) -> syn::Result<ConfigModel> {
+    ConfigModel::try_from(syn::parse_str::<ConfigAst>(dsl)?)
+}
@@ -6,6 +6,19 @@ use crate::crud::ast;
 #[derive(Debug)]
 pub struct ConfigModel {
     pub repository_name: Ident,
+    pub events: bool,
+    // Whether to emit a `{REPO}_SCHEMA` JSON description of the objects.
+    pub schema: bool,
+    // Whether to emit `{REPO}_MERMAID` / `{REPO}_DOT` diagrams of the
+    // hierarchy.
+    pub diagram: bool,
+    // Whether to emit a typed `{Repo}CrudClient` for the generated handlers.
+    pub client: bool,
+    // Whether list operations accept a page token and limit, and list
+    // annotations gain `_page` variants.
+    pub paginated: bool,
+    // Whether to emit a `{Repo}Transaction` staging writes across objects.
+    pub transactions: bool,
     pub phantom_objects: Vec<PhantomDef>,
     pub ordered_objects: Vec<StandardDef>,
     pub unordered_objects: Vec<StandardDef>,
@@ -27,6 +40,10 @@ pub struct PhantomDef {
 #[derive(Debug)]
 pub struct StandardDef {
     pub is_archive: bool,
+    pub is_soft_delete: bool,
+    pub is_movable_archive: bool,
+    pub is_versioned: bool,
+    pub is_validated: bool,
     pub name: Ident,
     pub parents: Option<Vec<Ident>>,
     pub ordered_children: Vec<Ident>,
@@ -34,27 +51,47 @@ pub struct StandardDef {
     pub batch_children: Vec<Ident>,
     pub singleton_children: Vec<Ident>,
     pub indexed_singleton_children: Vec<Ident>,
+    pub filterable: Vec<Ident>,
+    pub table: Option<Ident>,
+    pub hooks: Option<Ident>,
+    // Seconds for which reads are cached by a `{Repo}Cached` repository.
+    pub cache_ttl: Option<u64>,
 }
 
 #[derive(Debug)]
 pub struct BatchDef {
     pub is_archive: bool,
+    pub is_validated: bool,
     pub name: Ident,
     pub parents: Option<Vec<Ident>>,
+    pub filterable: Vec<Ident>,
+    pub table: Option<Ident>,
+    pub hooks: Option<Ident>,
+    // Seconds for which reads are cached by a `{Repo}Cached` repository.
+    pub cache_ttl: Option<u64>,
 }
 
 #[derive(Debug)]
 pub struct SingletonDef {
     pub is_archive: bool,
+    pub is_validated: bool,
     pub name: Ident,
     pub parents: Option<Vec<Ident>>,
+    pub table: Option<Ident>,
+    pub hooks: Option<Ident>,
 }
 
 #[derive(Debug)]
 pub struct IndexedSingletonDef {
     pub is_archive: bool,
+    pub is_validated: bool,
     pub name: Ident,
     pub parents: Option<Vec<Ident>>,
+    pub filterable: Vec<Ident>,
+    pub table: Option<Ident>,
+    pub hooks: Option<Ident>,
+    // Seconds for which reads are cached by a `{Repo}Cached` repository.
+    pub cache_ttl: Option<u64>,
 }
 
 impl TryFrom<ast::ConfigAst> for ConfigModel {
@@ -71,6 +108,10 @@ impl TryFrom<ast::ConfigAst> for ConfigModel {
         for obj in value.objects {
             let ast::ObjectDef {
                 is_archive,
+                is_soft_delete,
+                is_movable_archive,
+                is_versioned,
+                is_validated,
                 kind,
                 name,
                 props,
@@ -82,8 +123,101 @@ impl TryFrom<ast::ConfigAst> for ConfigModel {
                 batch_children,
                 singleton_children,
                 indexed_singleton_children,
+                filterable,
+                table,
+                hooks,
+                cache_ttl,
             } = props;
 
+            if is_soft_delete
+                && !matches!(
+                    kind,
+                    ast::ObjectKind::Root | ast::ObjectKind::Ordered | ast::ObjectKind::Unordered
+                )
+            {
+                return Err(Error::new(
+                    name.span(),
+                    "the `soft_delete` prefix is only supported on `root`, `ordered`, and \
+                     `unordered` objects",
+                ));
+            }
+
+            if is_versioned
+                && !matches!(
+                    kind,
+                    ast::ObjectKind::Root | ast::ObjectKind::Ordered | ast::ObjectKind::Unordered
+                )
+            {
+                return Err(Error::new(
+                    name.span(),
+                    "the `versioned` prefix is only supported on `root`, `ordered`, and \
+                     `unordered` objects",
+                ));
+            }
+
+            if is_movable_archive {
+                if is_archive {
+                    return Err(Error::new(
+                        name.span(),
+                        "the `archive` and `movable_archive` prefixes cannot be combined",
+                    ));
+                }
+                if !matches!(
+                    kind,
+                    ast::ObjectKind::Root | ast::ObjectKind::Ordered | ast::ObjectKind::Unordered
+                ) {
+                    return Err(Error::new(
+                        name.span(),
+                        "the `movable_archive` prefix is only supported on `root`, `ordered`, \
+                         and `unordered` objects",
+                    ));
+                }
+            }
+
+            if let Some(ref table) = table {
+                if table == "main" || table == "archive" {
+                    return Err(Error::new(
+                        table.span(),
+                        "`main` and `archive` are reserved table names; omit `table` or use the \
+                         `archive` prefix instead",
+                    ));
+                }
+                if is_archive || is_movable_archive {
+                    return Err(Error::new(
+                        name.span(),
+                        "a `table` property cannot be combined with the `archive` or \
+                         `movable_archive` prefixes",
+                    ));
+                }
+                if kind == ast::ObjectKind::Phantom {
+                    return Err(Error::new(
+                        name.span(),
+                        "`phantom` objects cannot have a `table` property",
+                    ));
+                }
+            }
+
+            let cache_ttl = match cache_ttl {
+                Some(lit) => {
+                    if matches!(kind, ast::ObjectKind::Phantom | ast::ObjectKind::Singleton) {
+                        return Err(Error::new(
+                            name.span(),
+                            "the `cache_ttl` property is only supported on `root`, `ordered`, \
+                             `unordered`, `batch`, and `indexed_singleton` objects",
+                        ));
+                    }
+                    let secs = lit.base10_parse::<u64>()?;
+                    if secs == 0 {
+                        return Err(Error::new(
+                            lit.span(),
+                            "`cache_ttl` must be a positive number of seconds",
+                        ));
+                    }
+                    Some(secs)
+                }
+                None => None,
+            };
+
             match kind {
                 ast::ObjectKind::Phantom => {
                     if is_archive {
@@ -92,12 +226,30 @@ impl TryFrom<ast::ConfigAst> for ConfigModel {
                             "`phantom` objects cannot use the `archive` prefix",
                         ));
                     }
+                    if is_validated {
+                        return Err(Error::new(
+                            name.span(),
+                            "`phantom` objects cannot use the `validate` prefix",
+                        ));
+                    }
                     if parent.is_some() {
                         return Err(Error::new(
                             name.span(),
                             "`phantom` objects cannot have a `parent` property",
                         ));
                     }
+                    if !filterable.is_empty() {
+                        return Err(Error::new(
+                            name.span(),
+                            "`phantom` objects cannot have a `filterable` property",
+                        ));
+                    }
+                    if hooks.is_some() {
+                        return Err(Error::new(
+                            name.span(),
+                            "`phantom` objects cannot have a `hooks` property",
+                        ));
+                    }
                     phantom_objects.push(PhantomDef {
                         name,
                         ordered_children,
@@ -116,6 +268,10 @@ impl TryFrom<ast::ConfigAst> for ConfigModel {
                     }
                     unordered_objects.push(StandardDef {
                         is_archive,
+                        is_soft_delete,
+                        is_movable_archive,
+                        is_versioned,
+                        is_validated,
                         name,
                         parents: None,
                         ordered_children,
@@ -123,12 +279,20 @@ impl TryFrom<ast::ConfigAst> for ConfigModel {
                         batch_children,
                         singleton_children,
                         indexed_singleton_children,
+                        filterable,
+                        table,
+                        hooks,
+                        cache_ttl,
                     });
                 }
                 ast::ObjectKind::Ordered => {
                     let parents = validate_parents(name.span(), "`ordered`", parent)?;
                     ordered_objects.push(StandardDef {
                         is_archive,
+                        is_soft_delete,
+                        is_movable_archive,
+                        is_versioned,
+                        is_validated,
                         name,
                         parents,
                         ordered_children,
@@ -136,12 +300,20 @@ impl TryFrom<ast::ConfigAst> for ConfigModel {
                         batch_children,
                         singleton_children,
                         indexed_singleton_children,
+                        filterable,
+                        table,
+                        hooks,
+                        cache_ttl,
                     });
                 }
                 ast::ObjectKind::Unordered => {
                     let parents = validate_parents(name.span(), "`unordered`", parent)?;
                     unordered_objects.push(StandardDef {
                         is_archive,
+                        is_soft_delete,
+                        is_movable_archive,
+                        is_versioned,
+                        is_validated,
                         name,
                         parents,
                         ordered_children,
@@ -149,6 +321,10 @@ impl TryFrom<ast::ConfigAst> for ConfigModel {
                         batch_children,
                         singleton_children,
                         indexed_singleton_children,
+                        filterable,
+                        table,
+                        hooks,
+                        cache_ttl,
                     });
                 }
                 ast::ObjectKind::Batch => {
@@ -168,8 +344,13 @@ impl TryFrom<ast::ConfigAst> for ConfigModel {
                     }
                     batch_objects.push(BatchDef {
                         is_archive,
+                        is_validated,
                         name,
                         parents,
+                        filterable,
+                        table,
+                        hooks,
+                        cache_ttl,
                     });
                 }
                 ast::ObjectKind::Singleton => {
@@ -184,6 +365,12 @@ impl TryFrom<ast::ConfigAst> for ConfigModel {
                             "`singleton` objects cannot have child properties",
                         ));
                     }
+                    if !filterable.is_empty() {
+                        return Err(Error::new(
+                            name.span(),
+                            "`singleton` objects cannot have a `filterable` property",
+                        ));
+                    }
 
                     if let Some(ref parents) = parent {
                         if parents.is_empty() {
@@ -197,8 +384,11 @@ impl TryFrom<ast::ConfigAst> for ConfigModel {
 
                     singleton_objects.push(SingletonDef {
                         is_archive,
+                        is_validated,
                         name,
                         parents: parent,
+                        table,
+                        hooks,
                     });
                 }
                 ast::ObjectKind::IndexedSingleton => {
@@ -226,8 +416,13 @@ impl TryFrom<ast::ConfigAst> for ConfigModel {
 
                     indexed_singleton_objects.push(IndexedSingletonDef {
                         is_archive,
+                        is_validated,
                         name,
                         parents: parent,
+                        filterable,
+                        table,
+                        hooks,
+                        cache_ttl,
                     });
                 }
             }
@@ -235,6 +430,12 @@ impl TryFrom<ast::ConfigAst> for ConfigModel {
 
         Ok(Self {
             repository_name: value.repository_name,
+            events: value.options.events,
+            schema: value.options.schema,
+            diagram: value.options.diagram,
+            client: value.options.client,
+            paginated: value.options.paginated,
+            transactions: value.options.transactions,
             phantom_objects,
             ordered_objects,
             unordered_objects,
@@ -245,6 +446,216 @@ impl TryFrom<ast::ConfigAst> for ConfigModel {
     }
 }
 
+impl ConfigModel {
+    /// Fields declared `filterable` on the named object, or an empty slice if
+    /// the object is unknown or declares none.
+    pub fn filterable_fields(&self, name: &Ident) -> &[Ident] {
+        self.ordered_objects
+            .iter()
+            .chain(self.unordered_objects.iter())
+            .find(|object| &object.name == name)
+            .map(|object| object.filterable.as_slice())
+            .or_else(|| {
+                self.batch_objects
+                    .iter()
+                    .find(|object| &object.name == name)
+                    .map(|object| object.filterable.as_slice())
+            })
+            .or_else(|| {
+                self.indexed_singleton_objects
+                    .iter()
+                    .find(|object| &object.name == name)
+                    .map(|object| object.filterable.as_slice())
+            })
+            .unwrap_or_default()
+    }
+
+    /// Distinct custom tables referenced by `table` properties, sorted by name.
+    /// This is the order in which the generated impl macro expects their DB
+    /// accessors.
+    pub fn custom_tables(&self) -> Vec<&Ident> {
+        let mut tables = self
+            .ordered_objects
+            .iter()
+            .chain(self.unordered_objects.iter())
+            .filter_map(|object| object.table.as_ref())
+            .chain(self.batch_objects.iter().filter_map(|o| o.table.as_ref()))
+            .chain(
+                self.singleton_objects
+                    .iter()
+                    .filter_map(|o| o.table.as_ref()),
+            )
+            .chain(
+                self.indexed_singleton_objects
+                    .iter()
+                    .filter_map(|o| o.table.as_ref()),
+            )
+            .collect::<Vec<_>>();
+        tables.sort_by_key(|table| table.to_string());
+        tables.dedup_by_key(|table| table.to_string());
+        tables
+    }
+
+    /// Hooks type declared on the named object, if any.
+    pub fn hooks_for(&self, name: &Ident) -> Option<&Ident> {
+        self.ordered_objects
+            .iter()
+            .chain(self.unordered_objects.iter())
+            .find(|object| &object.name == name)
+            .and_then(|object| object.hooks.as_ref())
+            .or_else(|| {
+                self.batch_objects
+                    .iter()
+                    .find(|object| &object.name == name)
+                    .and_then(|object| object.hooks.as_ref())
+            })
+            .or_else(|| {
+                self.singleton_objects
+                    .iter()
+                    .find(|object| &object.name == name)
+                    .and_then(|object| object.hooks.as_ref())
+            })
+            .or_else(|| {
+                self.indexed_singleton_objects
+                    .iter()
+                    .find(|object| &object.name == name)
+                    .and_then(|object| object.hooks.as_ref())
+            })
+    }
+
+    /// Whether mutations of the named object go through a hooked manager,
+    /// either because it declares `hooks`, because the repository emits
+    /// events, or because it (or one of its descendants) is cached.
+    pub fn is_intercepted(&self, name: &Ident) -> bool {
+        self.events
+            || self.hooks_for(name).is_some()
+            || self.cache_ttl_for(name).is_some()
+            || !self.cached_descendants(name).is_empty()
+    }
+
+    /// Cache TTL (in seconds) declared on the named object, if any.
+    pub fn cache_ttl_for(&self, name: &Ident) -> Option<u64> {
+        self.ordered_objects
+            .iter()
+            .chain(self.unordered_objects.iter())
+            .find(|object| &object.name == name)
+            .and_then(|object| object.cache_ttl)
+            .or_else(|| {
+                self.batch_objects
+                    .iter()
+                    .find(|object| &object.name == name)
+                    .and_then(|object| object.cache_ttl)
+            })
+            .or_else(|| {
+                self.indexed_singleton_objects
+                    .iter()
+                    .find(|object| &object.name == name)
+                    .and_then(|object| object.cache_ttl)
+            })
+    }
+
+    /// Whether any object declares a `cache_ttl`.
+    pub fn has_cached_objects(&self) -> bool {
+        self.ordered_objects
+            .iter()
+            .chain(self.unordered_objects.iter())
+            .any(|object| object.cache_ttl.is_some())
+            || self.batch_objects.iter().any(|o| o.cache_ttl.is_some())
+            || self
+                .indexed_singleton_objects
+                .iter()
+                .any(|o| o.cache_ttl.is_some())
+    }
+
+    /// Objects below the named one (children, grandchildren, ...) that declare
+    /// a `cache_ttl`, whose cached entries go stale when it is deleted.
+    pub fn cached_descendants(&self, name: &Ident) -> Vec<&Ident> {
+        let mut descendants = Vec::new();
+        let mut pending = vec![name];
+        while let Some(current) = pending.pop() {
+            let Some(object) = self
+                .ordered_objects
+                .iter()
+                .chain(self.unordered_objects.iter())
+                .find(|object| &object.name == current)
+            else {
+                continue;
+            };
+            for child in object
+                .ordered_children
+                .iter()
+                .chain(object.unordered_children.iter())
+                .chain(object.batch_children.iter())
+                .chain(object.indexed_singleton_children.iter())
+            {
+                if child != name && !descendants.contains(&child) {
+                    descendants.push(child);
+                    pending.push(child);
+                }
+            }
+        }
+        descendants.retain(|child| self.cache_ttl_for(child).is_some());
+        descendants
+    }
+
+    /// Whether any object was declared with the `versioned` prefix.
+    pub fn has_versioned_objects(&self) -> bool {
+        self.ordered_objects
+            .iter()
+            .chain(self.unordered_objects.iter())
+            .any(|object| object.is_versioned)
+    }
+
+    /// Names of the objects declared with the `validate` prefix.
+    pub fn validated_objects(&self) -> Vec<&Ident> {
+        self.ordered_objects
+            .iter()
+            .chain(self.unordered_objects.iter())
+            .filter(|object| object.is_validated)
+            .map(|object| &object.name)
+            .chain(
+                self.batch_objects
+                    .iter()
+                    .filter(|object| object.is_validated)
+                    .map(|object| &object.name),
+            )
+            .chain(
+                self.singleton_objects
+                    .iter()
+                    .filter(|object| object.is_validated)
+                    .map(|object| &object.name),
+            )
+            .chain(
+                self.indexed_singleton_objects
+                    .iter()
+                    .filter(|object| object.is_validated)
+                    .map(|object| &object.name),
+            )
+            .collect()
+    }
+
+    /// Whether the named object was declared with the `validate` prefix.
+    pub fn is_validated(&self, name: &Ident) -> bool {
+        self.validated_objects().contains(&name)
+    }
+
+    /// Whether the named object is a `root`, `ordered`, or `unordered` object.
+    pub fn is_standard(&self, name: &Ident) -> bool {
+        self.ordered_objects
+            .iter()
+            .chain(self.unordered_objects.iter())
+            .any(|object| &object.name == name)
+    }
+
+    /// Whether the named object was declared with the `soft_delete` prefix.
+    pub fn is_soft_delete(&self, name: &Ident) -> bool {
+        self.ordered_objects
+            .iter()
+            .chain(self.unordered_objects.iter())
+            .any(|object| &object.name == name && object.is_soft_delete)
+    }
+}
+
 impl StandardDef {
     pub fn has_children(&self) -> bool {
         !self.ordered_children.is_empty()
@@ -274,6 +685,145 @@ fn validate_parents(
     Ok(parents)
 }
 
+/// Object as seen by `ConfigModel::validate_hierarchy`.
+struct HierarchyNode<'a> {
+    name: &'a Ident,
+    // Kind under which the object can be listed as a child (ex. `ordered`),
+    // or `None` for phantom objects.
+    relation: Option<&'static str>,
+    parents: &'a [Ident],
+    // Children, each with the relationship it is listed under.
+    children: Vec<(&'a Ident, &'static str)>,
+}
+
+impl ConfigModel {
+    /// Check that object names are unique, and that every child listed by an
+    /// object of this repository is declared with that object as a parent,
+    /// and under the kind it is listed as. Only applied to definitions merged
+    /// from `#[crud_object]`s or fragments, where the two sides of a
+    /// relationship are easily declared apart; a single `crud_scaffolding!`
+    /// block is accepted as is. References to types not declared in the
+    /// repository are left to the compiler.
+    pub fn validate_hierarchy(&self) -> Result<()> {
+        validate_hierarchy(self)
+    }
+}
+
+fn validate_hierarchy(model: &ConfigModel) -> Result<()> {
+    fn children_of<'a>(
+        ordered: &'a [Ident],
+        unordered: &'a [Ident],
+        batch: &'a [Ident],
+        singleton: &'a [Ident],
+        indexed_singleton: &'a [Ident],
+    ) -> Vec<(&'a Ident, &'static str)> {
+        let tag = |list: &'a [Ident], relation| list.iter().map(move |child| (child, relation));
+        tag(ordered, "ordered")
+            .chain(tag(unordered, "unordered"))
+            .chain(tag(batch, "batch"))
+            .chain(tag(singleton, "singleton"))
+            .chain(tag(indexed_singleton, "indexed_singleton"))
+            .collect()
+    }
+
+    let mut nodes = Vec::new();
+    for object in &model.phantom_objects {
+        nodes.push(HierarchyNode {
+            name: &object.name,
+            relation: None,
+            parents: &[],
+            children: children_of(
+                &object.ordered_children,
+                &object.unordered_children,
+                &object.batch_children,
+                &object.singleton_children,
+                &object.indexed_singleton_children,
+            ),
+        });
+    }
+    let standard = (model
+        .ordered_objects
+        .iter()
+        .map(|object| (object, "ordered")))
+    .chain(
+        model
+            .unordered_objects
+            .iter()
+            .map(|object| (object, "unordered")),
+    );
+    for (object, relation) in standard {
+        nodes.push(HierarchyNode {
+            name: &object.name,
+            relation: Some(relation),
+            parents: object.parents().unwrap_or_default(),
+            children: children_of(
+                &object.ordered_children,
+                &object.unordered_children,
+                &object.batch_children,
+                &object.singleton_children,
+                &object.indexed_singleton_children,
+            ),
+        });
+    }
+    let leaves = (model.batch_objects.iter())
+        .map(|object| (&object.name, object.parents(), "batch"))
+        .chain(
+            model
+                .singleton_objects
+                .iter()
+                .map(|object| (&object.name, object.parents(), "singleton")),
+        )
+        .chain(
+            model
+                .indexed_singleton_objects
+                .iter()
+                .map(|object| (&object.name, object.parents(), "indexed_singleton")),
+        );
+    for (name, parents, relation) in leaves {
+        nodes.push(HierarchyNode {
+            name,
+            relation: Some(relation),
+            parents: parents.unwrap_or_default(),
+            children: Vec::new(),
+        });
+    }
+
+    let find = |name: &Ident| nodes.iter().find(|node| node.name == name);
+    for (i, node) in nodes.iter().enumerate() {
+        if nodes[..i].iter().any(|other| other.name == node.name) {
+            return Err(Error::new(
+                node.name.span(),
+                format!("duplicate object `{}`", node.name),
+            ));
+        }
+        for &(child, relation) in &node.children {
+            let Some(child_node) = find(child) else {
+                continue;
+            };
+            if child_node.relation != Some(relation) {
+                return Err(Error::new(
+                    child.span(),
+                    format!(
+                        "`{}` is listed in `{}_children` of `{}`, but is not declared with \
+                         the `{}` kind",
+                        child, relation, node.name, relation
+                    ),
+                ));
+            }
+            if !child_node.parents.contains(node.name) {
+                return Err(Error::new(
+                    child.span(),
+                    format!(
+                        "`{}` is listed as a child of `{}`, but does not declare it as a `parent`",
+                        child, node.name
+                    ),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
 pub trait HasParents {
     fn parents(&self) -> Option<&[Ident]>;
 }
@@ -376,4 +926,329 @@ mod tests {
         assert_eq!(model.singleton_objects.len(), 1);
         assert_eq!(model.indexed_singleton_objects.len(), 1);
     }
+
+    #[test]
+    fn carries_filterable_fields_and_rejects_them_on_singletons() {
+        let ast: ConfigAst = syn::parse_str(
+            r#"
+            MyRepo;
+            unordered PersonaSample {
+                parent: Persona
+                filterable: status, created_at
+            }
+            "#,
+        )
+        .unwrap();
+
+        let model = ConfigModel::try_from(ast).unwrap();
+
+        let name = &model.unordered_objects[0].name;
+        assert_eq!(model.filterable_fields(name).len(), 2);
+        assert_eq!(model.filterable_fields(name)[1], "created_at");
+
+        let ast: ConfigAst = syn::parse_str(
+            r#"
+            MyRepo;
+            singleton Note {
+                filterable: status
+            }
+            "#,
+        )
+        .unwrap();
+
+        let err = ConfigModel::try_from(ast).unwrap_err();
+
+        assert!(
+            err.to_string()
+                .contains("`singleton` objects cannot have a `filterable` property")
+        );
+    }
+
+    #[test]
+    fn rejects_movable_archive_combined_with_archive() {
+        let ast: ConfigAst = syn::parse_str(
+            r#"
+            MyRepo;
+            movable_archive root Project {}
+            "#,
+        )
+        .unwrap();
+
+        let model = ConfigModel::try_from(ast).unwrap();
+
+        assert!(model.unordered_objects[0].is_movable_archive);
+        assert!(!model.unordered_objects[0].is_archive);
+
+        let ast: ConfigAst = syn::parse_str(
+            r#"
+            MyRepo;
+            archive movable_archive root Project {}
+            "#,
+        )
+        .unwrap();
+
+        let err = ConfigModel::try_from(ast).unwrap_err();
+
+        assert!(err.to_string().contains("cannot be combined"));
+    }
+
+    #[test]
+    fn collects_custom_tables_in_sorted_order() {
+        let ast: ConfigAst = syn::parse_str(
+            r#"
+            MyRepo;
+            root Project {
+                table: reporting
+            }
+            batch Metric {
+                parent: Project
+                table: analytics
+            }
+            singleton Summary {
+                parent: Project
+                table: reporting
+            }
+            "#,
+        )
+        .unwrap();
+
+        let model = ConfigModel::try_from(ast).unwrap();
+
+        let tables = model.custom_tables();
+        assert_eq!(tables.len(), 2);
+        assert_eq!(*tables[0], "analytics");
+        assert_eq!(*tables[1], "reporting");
+
+        let ast: ConfigAst = syn::parse_str(
+            r#"
+            MyRepo;
+            root Project {
+                table: archive
+            }
+            "#,
+        )
+        .unwrap();
+
+        let err = ConfigModel::try_from(ast).unwrap_err();
+
+        assert!(err.to_string().contains("reserved table names"));
+    }
+
+    #[test]
+    fn resolves_hooks_by_object_name_and_rejects_them_on_phantoms() {
+        let ast: ConfigAst = syn::parse_str(
+            r#"
+            MyRepo;
+            root Project {
+                hooks: ProjectHooksImpl
+            }
+            ordered Task {
+                parent: Project
+            }
+            singleton Summary {
+                parent: Project
+                hooks: SummaryHooksImpl
+            }
+            "#,
+        )
+        .unwrap();
+
+        let model = ConfigModel::try_from(ast).unwrap();
+
+        let project = model.unordered_objects[0].name.clone();
+        let task = model.ordered_objects[0].name.clone();
+        let summary = model.singleton_objects[0].name.clone();
+        assert_eq!(model.hooks_for(&project).unwrap(), "ProjectHooksImpl");
+        assert!(model.hooks_for(&task).is_none());
+        assert_eq!(model.hooks_for(&summary).unwrap(), "SummaryHooksImpl");
+
+        let ast: ConfigAst = syn::parse_str(
+            r#"
+            MyRepo;
+            phantom Lookup {
+                hooks: LookupHooksImpl
+            }
+            "#,
+        )
+        .unwrap();
+
+        let err = ConfigModel::try_from(ast).unwrap_err();
+
+        assert!(err.to_string().contains("cannot have a `hooks` property"));
+    }
+
+    #[test]
+    fn accepts_versioned_prefix_only_on_standard_objects() {
+        let ast: ConfigAst = syn::parse_str(
+            r#"
+            MyRepo;
+            versioned root Project {}
+            "#,
+        )
+        .unwrap();
+
+        let model = ConfigModel::try_from(ast).unwrap();
+
+        assert!(model.unordered_objects[0].is_versioned);
+        assert!(model.has_versioned_objects());
+
+        let ast: ConfigAst = syn::parse_str(
+            r#"
+            MyRepo;
+            versioned singleton Summary {}
+            "#,
+        )
+        .unwrap();
+
+        let err = ConfigModel::try_from(ast).unwrap_err();
+
+        assert!(
+            err.to_string()
+                .contains("the `versioned` prefix is only supported")
+        );
+    }
+
+    #[test]
+    fn resolves_cache_ttl_and_intercepts_ancestors_of_cached_objects() {
+        let ast: ConfigAst = syn::parse_str(
+            r#"
+            MyRepo;
+            root Project {
+                ordered_children: Task
+            }
+            ordered Task {
+                parent: Project
+                batch_children: Label
+            }
+            batch Label {
+                parent: Task
+                cache_ttl: 30
+            }
+            root Other {}
+            "#,
+        )
+        .unwrap();
+
+        let model = ConfigModel::try_from(ast).unwrap();
+
+        let project = model.unordered_objects[0].name.clone();
+        let other = model.unordered_objects[1].name.clone();
+        let label = model.batch_objects[0].name.clone();
+        assert_eq!(model.cache_ttl_for(&label), Some(30));
+        assert!(model.has_cached_objects());
+        assert_eq!(model.cached_descendants(&project), vec![&label]);
+        assert!(model.is_intercepted(&project));
+        assert!(!model.is_intercepted(&other));
+
+        let ast: ConfigAst = syn::parse_str(
+            r#"
+            MyRepo;
+            singleton Summary {
+                cache_ttl: 30
+            }
+            "#,
+        )
+        .unwrap();
+
+        let err = ConfigModel::try_from(ast).unwrap_err();
+
+        assert!(
+            err.to_string()
+                .contains("the `cache_ttl` property is only supported")
+        );
+    }
+
+    #[test]
+    fn collects_validated_objects_and_rejects_validate_on_phantoms() {
+        let ast: ConfigAst = syn::parse_str(
+            r#"
+            MyRepo;
+            validate root Project {
+                batch_children: Label
+            }
+            batch Label {
+                parent: Project
+            }
+            validate singleton Summary {}
+            "#,
+        )
+        .unwrap();
+
+        let model = ConfigModel::try_from(ast).unwrap();
+
+        let project = model.unordered_objects[0].name.clone();
+        let label = model.batch_objects[0].name.clone();
+        assert_eq!(model.validated_objects().len(), 2);
+        assert!(model.is_validated(&project));
+        assert!(!model.is_validated(&label));
+
+        let ast: ConfigAst = syn::parse_str(
+            r#"
+            MyRepo;
+            validate phantom Shared {}
+            "#,
+        )
+        .unwrap();
+
+        let err = ConfigModel::try_from(ast).unwrap_err();
+
+        assert!(
+            err.to_string()
+                .contains("`phantom` objects cannot use the `validate` prefix")
+        );
+    }
+
+    #[test]
+    fn validates_parent_child_relationships_of_merged_objects() {
+        let hierarchy_err = |dsl: &str| {
+            // The model itself accepts a single block as it always has.
+            let model = crate::crud::test_model(dsl).unwrap();
+            model.validate_hierarchy().unwrap_err().to_string()
+        };
+
+        assert!(
+            hierarchy_err(
+                r#"
+                MyRepo;
+                root Project {
+                    ordered_children: Task
+                }
+                unordered Task {
+                    parent: Project
+                }
+                "#
+            )
+            .contains("`Task` is listed in `ordered_children` of `Project`")
+        );
+        assert!(
+            hierarchy_err(
+                r#"
+                MyRepo;
+                root Project {
+                    batch_children: Label
+                }
+                batch Label {}
+                "#
+            )
+            .contains("`Label` is listed as a child of `Project`, but does not declare it")
+        );
+        assert!(
+            hierarchy_err("MyRepo; root Project {} singleton Project {}")
+                .contains("duplicate object `Project`")
+        );
+
+        let model = crate::crud::test_model(
+            r#"
+            MyRepo;
+            root Project {
+                ordered_children: Task
+            }
+            ordered Task {
+                parent: Project
+            }
+            "#,
+        )
+        .unwrap();
+        assert!(model.validate_hierarchy().is_ok());
+    }
 }
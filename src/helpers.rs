@@ -1,3 +1,6 @@
+use syn::parse::ParseStream;
+use syn::{Attribute, Error, Meta, Result};
+
 /// Minimal PascalCase/camelCase to snake_case.
 pub fn to_snake_case(name: &str) -> String {
     // Handles transitions from lower->upper and acronym boundaries reasonably.
@@ -53,3 +56,61 @@ pub fn to_pascal_case(input: &str) -> String {
     }
     out
 }
+
+/// Parse repository options given as outer attributes (ex. `#[events]
+/// MyRepo;`), setting the flag named by each. Options parsed elsewhere (ex.
+/// `fragments(...)`) are listed in `handled_elsewhere`, which are skipped here
+/// but listed among the expected options.
+pub fn parse_flag_options(
+    input: ParseStream<'_>,
+    flags: &mut [(&str, &mut bool)],
+    handled_elsewhere: &[&str],
+) -> Result<()> {
+    let expected = flags
+        .iter()
+        .map(|(name, _)| format!("`{}`", name))
+        .chain(
+            handled_elsewhere
+                .iter()
+                .map(|name| format!("`{}(...)`", name)),
+        )
+        .collect::<Vec<_>>()
+        .join(", ");
+    for attr in input.call(Attribute::parse_outer)? {
+        if handled_elsewhere
+            .iter()
+            .any(|name| attr.path().is_ident(name))
+        {
+            continue;
+        }
+        let Meta::Path(path) = &attr.meta else {
+            return Err(Error::new_spanned(
+                &attr.meta,
+                "repository options do not take arguments",
+            ));
+        };
+        let Some(ident) = path.get_ident() else {
+            return Err(Error::new_spanned(
+                path,
+                format!("unknown repository option; expected one of: {}", expected),
+            ));
+        };
+        let Some((_, flag)) = flags.iter_mut().find(|(name, _)| ident == name) else {
+            return Err(Error::new(
+                ident.span(),
+                format!(
+                    "unknown repository option `{}`; expected one of: {}",
+                    ident, expected
+                ),
+            ));
+        };
+        if **flag {
+            return Err(Error::new(
+                ident.span(),
+                format!("duplicate `{}` option", ident),
+            ));
+        }
+        **flag = true;
+    }
+    Ok(())
+}
@@ -0,0 +1,335 @@
+use proc_macro2::{Delimiter, Group, Ident, TokenStream};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Attribute, Error, FnArg, Pat, Result, ReturnType, Signature, Type, Visibility};
+
+/// Whether generated code should be wrapped in `tracing` spans. Controlled by
+/// this crate's `tracing` feature; the consuming crate must then depend on
+/// `tracing` itself, since the generated code refers to `::tracing`.
+pub fn enabled() -> bool {
+    cfg!(feature = "tracing")
+}
+
+/// Span opened around a single generated operation.
+pub struct OperationSpan {
+    name: String,
+    fields: TokenStream,
+}
+
+impl OperationSpan {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            fields: TokenStream::new(),
+        }
+    }
+
+    /// Record a fixed operation kind (ex. `create`, `read`).
+    pub fn operation(mut self, kind: &str) -> Self {
+        self.fields.extend(quote! {
+            __span.record("operation", #kind);
+        });
+        self
+    }
+
+    pub fn parent_id(mut self, id: TokenStream) -> Self {
+        self.fields.extend(quote! {
+            __span.record("parent_id", ::tracing::field::debug(#id));
+        });
+        self
+    }
+
+    pub fn item_id(mut self, id: TokenStream) -> Self {
+        self.fields.extend(quote! {
+            __span.record("item_id", ::tracing::field::debug(#id));
+        });
+        self
+    }
+
+    /// Arbitrary statements recording fields on `__span`, for fields only
+    /// known at runtime (ex. derived from the incoming operation).
+    pub fn record(mut self, statements: TokenStream) -> Self {
+        self.fields.extend(statements);
+        self
+    }
+}
+
+/// Wrap a function body in the given span. The body is run inside an `async
+/// move` block (or a `move` closure if not `is_async`), so early returns and
+/// `?` behave as they would in the original function; `ret` is the function's
+/// return type, needed to type the block. Latency is always recorded; if
+/// `fallible`, so is the outcome, with errors additionally logged.
+pub fn instrument(
+    span: OperationSpan,
+    body: TokenStream,
+    ret: TokenStream,
+    is_async: bool,
+    fallible: bool,
+) -> TokenStream {
+    if !enabled() {
+        return body;
+    }
+    wrap(span, body, ret, is_async, fallible)
+}
+
+fn wrap(
+    span: OperationSpan,
+    body: TokenStream,
+    ret: TokenStream,
+    is_async: bool,
+    fallible: bool,
+) -> TokenStream {
+    let OperationSpan { name, fields } = span;
+    let run = if is_async {
+        quote! { ::tracing::Instrument::instrument(async move { #body }, __span.clone()).await }
+    } else {
+        quote! { __span.in_scope(move || { #body }) }
+    };
+    let outcome = if fallible {
+        quote! {
+            match &__result {
+                ::std::result::Result::Ok(_) => {
+                    __span.record("outcome", "ok");
+                }
+                ::std::result::Result::Err(__error) => {
+                    __span.record("outcome", "error");
+                    ::tracing::error!(parent: &__span, error = %__error, "{} failed", #name);
+                }
+            }
+        }
+    } else {
+        quote! {
+            __span.record("outcome", "ok");
+        }
+    };
+    quote! {
+        let __span = ::tracing::info_span!(
+            #name,
+            operation = ::tracing::field::Empty,
+            parent_id = ::tracing::field::Empty,
+            item_id = ::tracing::field::Empty,
+            outcome = ::tracing::field::Empty,
+            latency_ms = ::tracing::field::Empty,
+        );
+        #fields
+        let __start = ::std::time::Instant::now();
+        let __result: #ret = #run;
+        __span.record("latency_ms", __start.elapsed().as_millis() as u64);
+        #outcome
+        __result
+    }
+}
+
+/// What `self` refers to in a block of generated methods: the object the
+/// methods belong to, or the parent of the objects they operate on.
+#[derive(Clone, Copy)]
+pub enum SelfRole {
+    Item,
+    Parent,
+}
+
+/// Instrument every `fn` in a block of impl items, naming each span
+/// `{owner}::{fn}`. The operation kind is inferred from the method name, and
+/// IDs are taken from `id` / `parent_id` parameters or from `self` (according
+/// to `role`).
+pub fn instrument_methods(owner: &Ident, role: SelfRole, items: TokenStream) -> TokenStream {
+    if !enabled() {
+        return items;
+    }
+    wrap_methods(owner, role, items)
+}
+
+fn wrap_methods(owner: &Ident, role: SelfRole, items: TokenStream) -> TokenStream {
+    let ImplFns(fns) = syn::parse2(items).expect("generated impl items should parse");
+    fns.into_iter()
+        .map(
+            |ImplFn {
+                 attrs,
+                 vis,
+                 sig,
+                 body,
+             }| {
+                let (ret, fallible) = match &sig.output {
+                    ReturnType::Default => (quote! { () }, false),
+                    ReturnType::Type(_, ty) => (quote! { #ty }, is_result(ty)),
+                };
+                let span = method_span(owner, role, &sig);
+                let mut wrapped = Group::new(
+                    Delimiter::Brace,
+                    wrap(span, body.stream(), ret, sig.asyncness.is_some(), fallible),
+                );
+                wrapped.set_span(body.span());
+                quote! { #(#attrs)* #vis #sig #wrapped }
+            },
+        )
+        .collect()
+}
+
+/// Functions of a block of generated impl items. Only signatures are parsed:
+/// bodies are kept as tokens, since they may refer to the metavariables of
+/// the `macro_rules!` they are generated into (ex. `$ctx_repo_accessor`).
+struct ImplFns(Vec<ImplFn>);
+
+struct ImplFn {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    sig: Signature,
+    body: Group,
+}
+
+impl Parse for ImplFns {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let mut fns = Vec::new();
+        while !input.is_empty() {
+            let attrs = input.call(Attribute::parse_outer)?;
+            let vis = input.parse()?;
+            let sig = input.parse()?;
+            let body: Group = input.parse()?;
+            if body.delimiter() != Delimiter::Brace {
+                return Err(Error::new(body.span(), "expected a function body"));
+            }
+            fns.push(ImplFn {
+                attrs,
+                vis,
+                sig,
+                body,
+            });
+        }
+        Ok(Self(fns))
+    }
+}
+
+fn is_result(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Result"),
+        _ => false,
+    }
+}
+
+fn method_span(owner: &Ident, role: SelfRole, sig: &Signature) -> OperationSpan {
+    let name = &sig.ident;
+    let mut span = OperationSpan::new(format!("{}::{}", owner, name))
+        .operation(operation_kind(&name.to_string()));
+    for input in &sig.inputs {
+        match input {
+            FnArg::Receiver(_) => {
+                let id = quote! { <Self as ::fractic_aws_dynamo::schema::DynamoObject>::id(&self) };
+                span = match role {
+                    SelfRole::Item => span.item_id(id),
+                    SelfRole::Parent => span.parent_id(id),
+                };
+            }
+            FnArg::Typed(typed) => {
+                if let Pat::Ident(pat) = &*typed.pat {
+                    if pat.ident == "id" {
+                        span = span.item_id(quote! { &id });
+                    } else if pat.ident == "parent_id" {
+                        span = span.parent_id(quote! { &parent_id });
+                    }
+                }
+            }
+        }
+    }
+    span
+}
+
+/// Operation kind recorded for a generated method, inferred from its name
+/// (ex. `add_team`, `batch_delete`, `unchecked_list`).
+fn operation_kind(name: &str) -> &'static str {
+    let name = name.strip_prefix("unchecked_").unwrap_or(name);
+    let name = name.strip_prefix("batch_").unwrap_or(name);
+    if name.starts_with("add") {
+        "create"
+    } else if name.starts_with("set") || name.starts_with("replace") {
+        "write"
+    } else if name.starts_with("update") {
+        "update"
+    } else if name.starts_with("delete") || name.starts_with("soft_delete") {
+        "delete"
+    } else if name.starts_with("archive") || name.starts_with("unarchive") {
+        "archive"
+    } else if name.starts_with("restore") {
+        "restore"
+    } else {
+        "read"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::{format_ident, quote};
+
+    use super::{SelfRole, instrument_methods, operation_kind, wrap_methods};
+    use crate::testing;
+
+    #[test]
+    fn infers_operation_kind_from_method_name() {
+        assert_eq!(operation_kind("add_team"), "create");
+        assert_eq!(operation_kind("unchecked_batch_add"), "create");
+        assert_eq!(operation_kind("replace_all_tags"), "write");
+        assert_eq!(operation_kind("batch_delete_all"), "delete");
+        assert_eq!(operation_kind("list_deleted_teams"), "read");
+        assert_eq!(operation_kind("unarchive"), "archive");
+    }
+
+    fn methods() -> proc_macro2::TokenStream {
+        quote! {
+            async fn get(ctx: __ctx!(), id: PkSk) -> ::std::result::Result<Team, ServerError> {
+                ctx.$ctx_repo_accessor().await?.manage_team().get(id).await
+            }
+            fn visit<F>(&self, f: impl Fn(&Team) -> bool, g: F) -> usize
+            where
+                F: Fn(u8, u8) -> u8,
+            {
+                fn nested() -> u8 { 0 }
+                0
+            }
+        }
+    }
+
+    #[test]
+    fn wraps_each_method_body_in_a_span() {
+        let wrapped = wrap_methods(&format_ident!("Team"), SelfRole::Item, methods());
+        let items = testing::items(quote! { impl Team { #wrapped } });
+
+        let get = testing::fn_of(&items, Some("Team"), "get");
+        assert!(get.sig.asyncness.is_some());
+        assert_eq!(get.param_names(), ["ctx", "id"]);
+        assert!(get.body_contains(":: tracing :: info_span ! (\"Team::get\""));
+        assert!(get.body_contains("__span . record (\"operation\" , \"read\")"));
+        assert!(
+            get.body_contains(
+                "__span . record (\"item_id\" , :: tracing :: field :: debug (& id))"
+            )
+        );
+        assert!(get.body_contains("__span . record (\"outcome\" , \"error\")"));
+        assert!(get.body_contains("ctx . ctx_repo_accessor ()"));
+
+        // Generic bounds, `impl Fn(..) ->` arguments and nested functions
+        // are left to the signature and body.
+        let visit = testing::fn_of(&items, Some("Team"), "visit");
+        assert_eq!(visit.output(), "-> usize");
+        assert_eq!(visit.param_names(), ["f", "g"]);
+        assert!(visit.sig.generics.where_clause.is_some());
+        assert!(visit.body_contains("__span . in_scope (move | | { fn nested () -> u8 { 0 } 0 })"));
+        assert!(visit.body_contains(
+            "< Self as :: fractic_aws_dynamo :: schema :: DynamoObject > :: id (& self)"
+        ));
+        assert!(!visit.body_contains("\"error\""));
+        assert_eq!(testing::fns(&items, "nested").len(), 0);
+    }
+
+    #[test]
+    fn leaves_methods_unchanged_without_the_feature() {
+        let instrumented = instrument_methods(&format_ident!("Team"), SelfRole::Item, methods());
+        if cfg!(feature = "tracing") {
+            assert!(instrumented.to_string().contains("info_span"));
+        } else {
+            assert_eq!(instrumented.to_string(), methods().to_string());
+        }
+    }
+}
@@ -4,13 +4,124 @@ use syn::parse::{Parse as _, Parser};
 
 mod crud;
 mod helpers;
+mod instrument;
 mod repository;
+mod schema;
+#[cfg(test)]
+mod testing;
 
+/// Generates a CRUD repository from the object DSL (ex.
+/// `crud_scaffolding!(MyRepo; root Persona {})`).
+///
+/// The generated code calls into `fractic_aws_dynamo` and
+/// `fractic_aws_apigateway`, which this crate does not depend on. Besides the
+/// basic managers and `CrudOperation`, some options and modifiers need those
+/// crates to provide the following:
+///
+/// - `#[paginated]`: `page_token: Option<String>` and `limit: Option<usize>`
+///   fields on `CrudOperation::List`, and a `query_page([parent,] cursor:
+///   Option<&str>, limit: usize) -> Result<(Vec<T>, Option<String>)>` manager
+///   method.
+/// - `filterable`: `ext::crud::FilterCondition::new(field, op, value)`, an
+///   `ext::crud::FilterOp` enum with `Eq`, `Ne`, `Lt`, `Le`, `Gt`, `Ge` and
+///   `BeginsWith`, and a `query_filtered([parent,] conditions:
+///   Vec<FilterCondition>, projection: Option<&[&str]>) -> Result<Vec<T>>`
+///   manager method.
+/// - `soft_delete`: manager methods `soft_delete(item) -> Result<T::Data>`,
+///   `batch_soft_delete(items) -> Result<Vec<T::Data>>`,
+///   `batch_soft_delete_all([parent]) -> Result<()>`, `restore(item) ->
+///   Result<T>` and `query_deleted([parent]) -> Result<Vec<T>>`, each also
+///   covering the item's descendants.
+/// - `movable_archive`: a `transfer(item, target: &Self) -> Result<T>`
+///   manager method, moving the item and its descendants to the table of
+///   another manager of the same type.
+/// - `versioned`: a `version: Option<u64>` field on `AutoFields`, bumped on
+///   every write, and an `update_if_version(item, expected_version:
+///   Option<u64>) -> Result<bool>` manager method which only writes while the
+///   stored version matches.
+/// - `#[transactions]`: an `ext::crud::CrudTransaction` with `new()` and
+///   `commit() -> Result<()>`, and manager methods staging writes in it
+///   (`stage_add`, `stage_set`, `stage_update`, `stage_update_if_version`,
+///   `stage_delete`, `stage_delete_non_recursive`, `stage_soft_delete` and
+///   `stage_replace_all_ordered`), each taking `&mut CrudTransaction` first
+///   and otherwise mirroring its unstaged counterpart.
+/// - `#[events]`: a `PkSk::parent_id(&self) -> Option<PkSk>` method, giving
+///   the parent of items updated or deleted without naming it.
 #[proc_macro]
 pub fn crud_scaffolding(input: TokenStream) -> TokenStream {
+    expand_crud(input.into(), false)
+}
+
+/// Declares a crud object next to its type, as an alternative to listing it
+/// in `crud_scaffolding!`. Takes the same modifiers, kind, and properties as
+/// the DSL (ex. `#[crud_object(archive, ordered, parent = Persona)]`); the
+/// repository is then generated by `crud_repository!`.
+#[proc_macro_attribute]
+pub fn crud_object(args: TokenStream, item: TokenStream) -> TokenStream {
+    let args = match crud::ObjectArgs::parse.parse(args) {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    match crud::expand_object(args, item.into()) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Generates the same code as `crud_scaffolding!` from objects declared with
+/// `#[crud_object(...)]` (ex. `crud_repository!(MyRepo: [Persona, models::PersonaPrinciple])`).
+/// Repository options may precede the name, as in the DSL.
+#[proc_macro]
+pub fn crud_repository(input: TokenStream) -> TokenStream {
+    let args = match crud::RepositoryArgs::parse.parse(input) {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    match crud::expand_repository(args) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Declares some of a repository's objects outside its `crud_scaffolding!`
+/// block (ex. `crud_scaffolding_fragment!(MyRepo; ordered Invoice { ... })`).
+/// The module containing the fragment must be listed in the repository's
+/// `#[fragments(...)]` option, which merges the objects into the single
+/// generated repository, checking that every listed child declares its
+/// parent under the matching kind. Each module can hold only one fragment per
+/// repository; a second one fails with "the name `__crud_fragment_MyRepo` is
+/// defined multiple times", so merge them into one invocation instead.
+#[proc_macro]
+pub fn crud_scaffolding_fragment(input: TokenStream) -> TokenStream {
+    match crud::FragmentArgs::parse.parse(input) {
+        Ok(args) => crud::expand_fragment(args).into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[doc(hidden)]
+#[proc_macro]
+pub fn __crud_repository_step(input: TokenStream) -> TokenStream {
+    let args = match crud::StepArgs::parse.parse(input) {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    expand_crud(crud::expand_step(args), true)
+}
+
+/// `merged` is set for definitions collected from several places, whose
+/// parent-child relationships are then checked across objects.
+fn expand_crud(input: proc_macro2::TokenStream, merged: bool) -> TokenStream {
+    // Gather objects declared in fragments first, if any.
+    match crud::merge_fragments(&input) {
+        Some(Ok(tokens)) => return tokens.into(),
+        Some(Err(err)) => return err.to_compile_error().into(),
+        None => {}
+    }
+
     // Parse into AST.
     let parser = crud::ConfigAst::parse;
-    let ast = match parser.parse(input) {
+    let ast = match parser.parse2(input) {
         Ok(cfg) => cfg,
         Err(err) => return err.to_compile_error().into(),
     };
@@ -20,6 +131,9 @@ pub fn crud_scaffolding(input: TokenStream) -> TokenStream {
         Ok(model) => model,
         Err(err) => return err.to_compile_error().into(),
     };
+    if merged && let Err(err) = model.validate_hierarchy() {
+        return err.to_compile_error().into();
+    }
 
     // Hand off to codegen.
     let tokens = crud::generate(&model);
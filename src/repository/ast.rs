@@ -1,6 +1,9 @@
 use proc_macro2::{Group, Span, TokenStream as TokenStream2, TokenTree};
 use syn::parse::{Parse, ParseStream};
-use syn::{Attribute, Error, Ident, Result, Token, braced, token};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Error, Ident, LitInt, Path, Result, Token, braced, token};
+
+use crate::helpers::parse_flag_options;
 
 mod kw {
     syn::custom_keyword!(function);
@@ -10,18 +13,35 @@ mod kw {
     syn::custom_keyword!(input);
     syn::custom_keyword!(output);
     syn::custom_keyword!(deprecated);
+    syn::custom_keyword!(idempotent);
+    syn::custom_keyword!(concurrent);
+    syn::custom_keyword!(retry);
+    syn::custom_keyword!(timeout_ms);
 }
 
 #[derive(Debug)]
 pub struct ConfigAst {
+    pub options: RepositoryOptions,
     pub repository_name: Ident,
+    pub supertraits: Vec<Path>,
     pub functions: Vec<FunctionAst>,
 }
 
 impl Parse for ConfigAst {
     fn parse(input: ParseStream<'_>) -> Result<Self> {
-        // Expect repository name then semicolon.
+        // Optional repository-level options (e.g., `#[schema]`), then the
+        // repository name, optional base repositories (e.g.,
+        // `MyRepo: BaseRepo + MetaRepo`), and a semicolon.
+        let options: RepositoryOptions = input.parse()?;
         let repository_name: Ident = input.parse()?;
+        let supertraits = if input.peek(Token![:]) {
+            let _colon: Token![:] = input.parse()?;
+            Punctuated::<Path, Token![+]>::parse_separated_nonempty(input)?
+                .into_iter()
+                .collect()
+        } else {
+            Vec::new()
+        };
         let _semi: Token![;] = input.parse()?;
 
         // Parse zero or more function blocks.
@@ -40,12 +60,45 @@ impl Parse for ConfigAst {
         }
 
         Ok(Self {
+            options,
             repository_name,
+            supertraits,
             functions,
         })
     }
 }
 
+/// Options given as outer attributes on the repository name (e.g.,
+/// `#[schema] MyRepo;`).
+#[derive(Debug, Default)]
+pub struct RepositoryOptions {
+    pub schema: bool,
+    pub native_async: bool,
+    pub http_client: bool,
+    pub grpc: bool,
+    pub json_schema: bool,
+    pub batch: bool,
+}
+
+impl Parse for RepositoryOptions {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let mut options = Self::default();
+        parse_flag_options(
+            input,
+            &mut [
+                ("schema", &mut options.schema),
+                ("native_async", &mut options.native_async),
+                ("http_client", &mut options.http_client),
+                ("grpc", &mut options.grpc),
+                ("json_schema", &mut options.json_schema),
+                ("batch", &mut options.batch),
+            ],
+            &[],
+        )?;
+        Ok(options)
+    }
+}
+
 #[derive(Debug)]
 pub struct FunctionAst {
     pub name: Ident,
@@ -53,6 +106,10 @@ pub struct FunctionAst {
     pub output: ValueAst,
     pub kind: FunctionKindAst,
     pub deprecated: Option<DeprecatedAst>,
+    pub idempotent: bool,
+    pub concurrent: bool,
+    pub retry: Option<LitInt>,
+    pub timeout_ms: Option<LitInt>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -95,11 +152,16 @@ impl Parse for FunctionAst {
         let content;
         let _brace = braced!(content in input);
 
-        // Parse properties: 'input', 'output', and optional 'deprecated'
+        // Parse properties: 'input', 'output', and optional 'deprecated',
+        // 'idempotent', 'concurrent', 'retry' and 'timeout_ms'
         // (order-insensitive).
         let mut input_val: Option<ValueAst> = None;
         let mut output_val: Option<ValueAst> = None;
         let mut deprecated_val: Option<DeprecatedAst> = None;
+        let mut idempotent_val = false;
+        let mut concurrent_val = false;
+        let mut retry_val: Option<LitInt> = None;
+        let mut timeout_ms_val: Option<LitInt> = None;
         while !content.is_empty() {
             // Check for accidental comma.
             if content.peek(Token![,]) {
@@ -117,7 +179,7 @@ impl Parse for FunctionAst {
                 let _colon: Token![:] = content.parse()?;
                 let value = parse_value_until_key_or_end(
                     &content,
-                    &[KeyStop::Output, KeyStop::Deprecated],
+                    &[KeyStop::Output, KeyStop::Deprecated, KeyStop::Policy],
                 )?;
                 if input_val.is_some() {
                     return Err(Error::new(name.span(), "duplicate `input` property"));
@@ -127,7 +189,10 @@ impl Parse for FunctionAst {
                 // Parse: output: <value>
                 let _k: kw::output = content.parse()?;
                 let _colon: Token![:] = content.parse()?;
-                let value = parse_value_until_key_or_end(&content, &[KeyStop::Deprecated])?;
+                let value = parse_value_until_key_or_end(
+                    &content,
+                    &[KeyStop::Deprecated, KeyStop::Policy],
+                )?;
                 if output_val.is_some() {
                     return Err(Error::new(name.span(), "duplicate `output` property"));
                 }
@@ -171,13 +236,46 @@ impl Parse for FunctionAst {
                     }
                     deprecated_val = Some(DeprecatedAst::Flag);
                 }
+            } else if content.peek(kw::idempotent) {
+                // Parse: idempotent
+                let _k: kw::idempotent = content.parse()?;
+                if idempotent_val {
+                    return Err(Error::new(name.span(), "duplicate `idempotent` property"));
+                }
+                idempotent_val = true;
+            } else if content.peek(kw::concurrent) {
+                // Parse: concurrent
+                let _k: kw::concurrent = content.parse()?;
+                if concurrent_val {
+                    return Err(Error::new(name.span(), "duplicate `concurrent` property"));
+                }
+                concurrent_val = true;
+            } else if content.peek(kw::retry) {
+                // Parse: retry: <attempts>
+                let _k: kw::retry = content.parse()?;
+                let _colon: Token![:] = content.parse()?;
+                let value: LitInt = content.parse()?;
+                if retry_val.is_some() {
+                    return Err(Error::new(name.span(), "duplicate `retry` property"));
+                }
+                retry_val = Some(value);
+            } else if content.peek(kw::timeout_ms) {
+                // Parse: timeout_ms: <milliseconds>
+                let _k: kw::timeout_ms = content.parse()?;
+                let _colon: Token![:] = content.parse()?;
+                let value: LitInt = content.parse()?;
+                if timeout_ms_val.is_some() {
+                    return Err(Error::new(name.span(), "duplicate `timeout_ms` property"));
+                }
+                timeout_ms_val = Some(value);
             } else {
                 // Unexpected token in function body.
                 let ahead: Ident = content.parse()?;
                 return Err(Error::new(
                     ahead.span(),
                     format!(
-                        "unknown key `{}`; expected `input`, `output`, or `deprecated`",
+                        "unknown key `{}`; expected `input`, `output`, `deprecated`, \
+                         `idempotent`, `concurrent`, `retry`, or `timeout_ms`",
                         ahead
                     ),
                 ));
@@ -194,6 +292,10 @@ impl Parse for FunctionAst {
             output,
             kind,
             deprecated: deprecated_val,
+            idempotent: idempotent_val,
+            concurrent: concurrent_val,
+            retry: retry_val,
+            timeout_ms: timeout_ms_val,
         })
     }
 }
@@ -261,6 +363,9 @@ impl Parse for FieldAst {
 enum KeyStop {
     Output,
     Deprecated,
+    /// Any of the call policy keys (`idempotent`, `concurrent`, `retry`,
+    /// `timeout_ms`).
+    Policy,
 }
 
 /// Parse a ValueAst until either the next key (currently only `output`) or end
@@ -361,6 +466,14 @@ fn read_tokens_until_next_key_or_end(
             {
                 break;
             }
+            if stops.iter().any(|s| matches!(s, KeyStop::Policy))
+                && (content.peek(kw::idempotent)
+                    || content.peek(kw::concurrent)
+                    || (content.peek(kw::retry) && content.peek2(Token![:]))
+                    || (content.peek(kw::timeout_ms) && content.peek2(Token![:])))
+            {
+                break;
+            }
         }
         // Consume token while tracking nesting. Treat nested groups as opaque
         // but preserved.
@@ -397,3 +510,100 @@ fn parse_next_group(input: ParseStream<'_>) -> Result<Group> {
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ConfigAst;
+
+    #[test]
+    fn parses_call_policies_in_any_order() {
+        let ast: ConfigAst = syn::parse_str(
+            r#"
+            MyRepo;
+            function fetch_user {
+                input: { id: String }
+                output: User
+                timeout_ms: 500
+                idempotent
+                retry: 3
+            }
+            function save_user {
+                input: User
+                output: None
+            }
+            "#,
+        )
+        .unwrap();
+
+        let fetch = &ast.functions[0];
+        assert!(fetch.idempotent);
+        assert_eq!(fetch.retry.as_ref().unwrap().base10_digits(), "3");
+        assert_eq!(fetch.timeout_ms.as_ref().unwrap().base10_digits(), "500");
+        let save = &ast.functions[1];
+        assert!(!save.idempotent);
+        assert!(save.retry.is_none());
+        assert!(save.timeout_ms.is_none());
+    }
+
+    #[test]
+    fn rejects_duplicate_call_policies() {
+        for body in [
+            "idempotent idempotent",
+            "retry: 1 retry: 2",
+            "timeout_ms: 1 timeout_ms: 2",
+        ] {
+            let err = syn::parse_str::<ConfigAst>(&format!(
+                "MyRepo; function f {{ input: None output: None {} }}",
+                body
+            ))
+            .unwrap_err();
+            assert!(err.to_string().starts_with("duplicate `"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn parses_base_repositories_in_header() {
+        let ast: ConfigAst = syn::parse_str("MyRepo: BaseRepo + meta::MetaRepo;").unwrap();
+        assert_eq!(ast.repository_name.to_string(), "MyRepo");
+        let supertraits: Vec<String> = ast
+            .supertraits
+            .iter()
+            .map(|path| quote::quote! { #path }.to_string())
+            .collect();
+        assert_eq!(supertraits, ["BaseRepo", "meta :: MetaRepo"]);
+
+        let ast: ConfigAst = syn::parse_str("MyRepo;").unwrap();
+        assert!(ast.supertraits.is_empty());
+    }
+
+    #[test]
+    fn rejects_empty_base_repository_list() {
+        assert!(syn::parse_str::<ConfigAst>("MyRepo: ;").is_err());
+        assert!(syn::parse_str::<ConfigAst>("MyRepo: BaseRepo +;").is_err());
+    }
+
+    #[test]
+    fn parses_native_async_option() {
+        let ast: ConfigAst = syn::parse_str("#[native_async] #[schema] MyRepo;").unwrap();
+        assert!(ast.options.native_async);
+        assert!(ast.options.schema);
+
+        let ast: ConfigAst = syn::parse_str("#[schema] MyRepo;").unwrap();
+        assert!(!ast.options.native_async);
+    }
+
+    #[test]
+    fn rejects_duplicate_and_malformed_options() {
+        let err =
+            syn::parse_str::<ConfigAst>("#[native_async] #[native_async] MyRepo;").unwrap_err();
+        assert_eq!(err.to_string(), "duplicate `native_async` option");
+        let err = syn::parse_str::<ConfigAst>("#[native_async(boxed)] MyRepo;").unwrap_err();
+        assert_eq!(err.to_string(), "repository options do not take arguments");
+        let err = syn::parse_str::<ConfigAst>("#[native] MyRepo;").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown repository option `native`; expected one of: `schema`, `native_async`, \
+             `http_client`, `grpc`, `json_schema`, `batch`"
+        );
+    }
+}
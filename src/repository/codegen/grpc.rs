@@ -0,0 +1,706 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{Error, PathArguments, Result, Type};
+
+use super::repository::dyn_adapter_ident_for;
+use crate::{
+    helpers::{to_pascal_case, to_snake_case},
+    repository::model::{
+        ConfigModel, FieldSpec, FunctionModel, ValueModel, single_generic_argument,
+    },
+};
+
+/// With `#[grpc]`: `{REPO}_PROTO`, a proto3 definition of the repository as a
+/// gRPC service (one rpc per function, with a message per input, output and
+/// helper struct), and `{Repo}GrpcAdapter`, which serves that service from a
+/// repository implementation.
+///
+/// The generated code refers to `::tonic`, and to the `prost` messages that
+/// `tonic-build` generates from `{REPO}_PROTO`, so the consuming crate must
+/// depend on `tonic` and `prost` itself and compile the `.proto` with
+/// `tonic-build`.
+///
+/// The adapter implements the server trait that `tonic-build` generates from
+/// the `.proto`, so its trait impl is emitted by an `impl_{repo}_grpc!` macro
+/// taking the path of the module holding the generated code (ex.
+/// `impl_my_repo_grpc!(crate::proto)`). Functions inherited from base
+/// repositories are served by the base repositories' own services.
+pub fn generate(model: &ConfigModel) -> TokenStream {
+    if !model.grpc {
+        return TokenStream::new();
+    }
+    match generate_checked(model) {
+        Ok(tokens) => tokens,
+        Err(err) => err.to_compile_error(),
+    }
+}
+
+fn generate_checked(model: &ConfigModel) -> Result<TokenStream> {
+    let repo_name = &model.repository_name;
+    let repo_snake = to_snake_case(&repo_name.to_string());
+    let adapter_ident = format_ident!("{}GrpcAdapter", repo_name);
+    let macro_name_ident = Ident::new(&format!("impl_{}_grpc", repo_snake), repo_name.span());
+    let server_module_ident = format_ident!("{}_server", repo_snake);
+    let repo_dyn = if model.native_async {
+        dyn_adapter_ident_for(repo_name)
+    } else {
+        repo_name.clone()
+    };
+
+    let proto_const = proto_const(model)?;
+
+    let helper_conversions = model
+        .helper_structs
+        .iter()
+        .map(|helper| {
+            let name = &helper.name;
+            let proto_name = proto_message_ident(name);
+            let from_fn = from_proto_fn_ident(&repo_snake, name);
+            let to_fn = to_proto_fn_ident(&repo_snake, name);
+            let fields = helper
+                .fields
+                .iter()
+                .map(|field| Ok((field, proto_field(&field.ty_tokens, model)?)))
+                .collect::<Result<Vec<_>>>()?;
+            let from_fields = fields.iter().map(|(field, proto)| {
+                let field_name = &field.name;
+                let value =
+                    proto.decode_expr(&repo_snake, quote! { __message.#field_name }, field_name);
+                quote! { #field_name: #value? }
+            });
+            let to_fields = fields.iter().map(|(field, proto)| {
+                let field_name = &field.name;
+                let value = proto.encode_expr(&repo_snake, quote! { __value.#field_name });
+                quote! { #field_name: #value }
+            });
+            Ok(quote! {
+                #[allow(dead_code)]
+                fn #from_fn(
+                    __message: $($proto)+::#proto_name,
+                ) -> ::std::result::Result<#name, ::tonic::Status> {
+                    ::std::result::Result::Ok(#name { #(#from_fields),* })
+                }
+
+                #[allow(dead_code)]
+                fn #to_fn(__value: #name) -> $($proto)+::#proto_name {
+                    $($proto)+::#proto_name { #(#to_fields),* }
+                }
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let methods = model
+        .functions
+        .iter()
+        .map(|f| generate_method(f, model, &repo_snake))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        #proto_const
+
+        /// Serves the repository as the gRPC service described by the
+        /// repository's `_PROTO` definition, once the server trait generated
+        /// from it is implemented with the repository's `impl_*_grpc!` macro.
+        #[derive(::core::clone::Clone)]
+        pub struct #adapter_ident {
+            repo: ::std::sync::Arc<dyn #repo_dyn>,
+        }
+
+        impl #adapter_ident {
+            pub fn new(repo: ::std::sync::Arc<dyn #repo_dyn>) -> Self {
+                Self { repo }
+            }
+        }
+
+        #[allow(unused_macros)]
+        #[macro_export]
+        macro_rules! #macro_name_ident {
+            ($($proto:tt)+) => {
+                #(#helper_conversions)*
+
+                #[::tonic::async_trait]
+                #[allow(deprecated)]
+                impl $($proto)+::#server_module_ident::#repo_name for #adapter_ident {
+                    #(#methods)*
+                }
+            };
+        }
+
+        #[allow(unused_imports)]
+        pub(crate) use #macro_name_ident;
+    })
+}
+
+fn generate_method(
+    f: &FunctionModel,
+    model: &ConfigModel,
+    repo_snake: &str,
+) -> Result<TokenStream> {
+    let fn_ident = &f.name;
+    let base_pascal = to_pascal_case(&fn_ident.to_string());
+    let input_ident = format_ident!("{}Input", base_pascal);
+    let output_ident = format_ident!("{}Output", base_pascal);
+
+    // Convert the request message into the trait method's arguments.
+    let (convert_input, args) = match &f.input {
+        ValueModel::None => (quote! { let _ = __message; }, Vec::new()),
+        ValueModel::SingleType { ty_tokens } => {
+            let proto = proto_field(ty_tokens, model)?;
+            let name = format_ident!("input");
+            let value = proto.decode_expr(repo_snake, quote! { __message.input }, &name);
+            let arg = proto.argument(&name);
+            (quote! { let input = #value?; }, vec![arg])
+        }
+        ValueModel::Struct { fields } => {
+            let mut statements = Vec::new();
+            let mut args = Vec::new();
+            for field in fields {
+                let proto = proto_field(&field.ty_tokens, model)?;
+                let name = &field.name;
+                let value = proto.decode_expr(repo_snake, quote! { __message.#name }, name);
+                statements.push(quote! { let #name = #value?; });
+                args.push(proto.argument(name));
+            }
+            (quote! { #(#statements)* }, args)
+        }
+    };
+
+    let call = if f.is_blocking {
+        quote! { self.repo.#fn_ident(#(#args),*) }
+    } else {
+        quote! { self.repo.#fn_ident(#(#args),*).await }
+    };
+    let call = if f.is_direct {
+        call
+    } else {
+        quote! { #call.map_err(|e| ::tonic::Status::internal(e.to_string()))? }
+    };
+
+    // Convert the method's output into the response message.
+    let output = match &f.output {
+        ValueModel::None => quote! { $($proto)+::#output_ident {} },
+        ValueModel::SingleType { ty_tokens } => {
+            let value = proto_field(ty_tokens, model)?.encode_expr(repo_snake, quote! { __result });
+            quote! { $($proto)+::#output_ident { output: #value } }
+        }
+        ValueModel::Struct { fields } if fields.len() == 1 => {
+            let name = &fields[0].name;
+            let value = proto_field(&fields[0].ty_tokens, model)?
+                .encode_expr(repo_snake, quote! { __result });
+            quote! { $($proto)+::#output_ident { #name: #value } }
+        }
+        ValueModel::Struct { fields } => {
+            let fields = fields
+                .iter()
+                .map(|field| {
+                    let name = &field.name;
+                    let value = proto_field(&field.ty_tokens, model)?
+                        .encode_expr(repo_snake, quote! { __result.#name });
+                    Ok(quote! { #name: #value })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            quote! { $($proto)+::#output_ident { #(#fields),* } }
+        }
+    };
+
+    Ok(quote! {
+        async fn #fn_ident(
+            &self,
+            request: ::tonic::Request<$($proto)+::#input_ident>,
+        ) -> ::std::result::Result<::tonic::Response<$($proto)+::#output_ident>, ::tonic::Status> {
+            let __message = request.into_inner();
+            #convert_input
+            #[allow(unused_variables)]
+            let __result = #call;
+            ::std::result::Result::Ok(::tonic::Response::new(#output))
+        }
+    })
+}
+
+// Proto definition.
+// ----------------------------------------------------------------------------
+
+/// `pub const {REPO}_PROTO: &str` holding the `.proto` file.
+fn proto_const(model: &ConfigModel) -> Result<TokenStream> {
+    let repo_name = &model.repository_name;
+    let const_ident = Ident::new(
+        &format!(
+            "{}_PROTO",
+            to_snake_case(&repo_name.to_string()).to_uppercase()
+        ),
+        repo_name.span(),
+    );
+    let doc = format!(
+        " proto3 definition of `{}` as a gRPC service, as declared in \
+         `repository_scaffolding!`.",
+        repo_name
+    );
+    let proto = render_proto(model)?;
+    Ok(quote! {
+        #[doc = #doc]
+        pub const #const_ident: &str = #proto;
+    })
+}
+
+fn render_proto(model: &ConfigModel) -> Result<String> {
+    let repo_name = model.repository_name.to_string();
+    let mut out = String::new();
+    out.push_str("syntax = \"proto3\";\n\n");
+    out.push_str(&format!("package {};\n\n", to_snake_case(&repo_name)));
+
+    out.push_str(&format!("service {} {{\n", repo_name));
+    for f in &model.functions {
+        let base_pascal = to_pascal_case(&f.name.to_string());
+        if let Some(note) = &f.deprecated_note {
+            for line in note.value().lines() {
+                out.push_str(&format!("  // Deprecated: {}\n", line.trim()));
+            }
+        }
+        let rpc = format!("  rpc {0}({0}Input) returns ({0}Output)", base_pascal);
+        if f.is_deprecated {
+            out.push_str(&format!(
+                "{} {{\n    option deprecated = true;\n  }}\n",
+                rpc
+            ));
+        } else {
+            out.push_str(&format!("{};\n", rpc));
+        }
+    }
+    out.push_str("}\n");
+
+    for f in &model.functions {
+        let base_pascal = to_pascal_case(&f.name.to_string());
+        out.push('\n');
+        render_value_message(
+            &mut out,
+            &format!("{}Input", base_pascal),
+            &f.input,
+            "input",
+            model,
+        )?;
+        out.push('\n');
+        render_value_message(
+            &mut out,
+            &format!("{}Output", base_pascal),
+            &f.output,
+            "output",
+            model,
+        )?;
+    }
+    for helper in &model.helper_structs {
+        out.push('\n');
+        render_message(
+            &mut out,
+            &proto_message_ident(&helper.name).to_string(),
+            &helper.fields,
+            model,
+        )?;
+    }
+    Ok(out)
+}
+
+/// Message for a function's input or output. Single types are wrapped in a
+/// message with one field named `input` / `output`.
+fn render_value_message(
+    out: &mut String,
+    message: &str,
+    value: &ValueModel,
+    single_field: &str,
+    model: &ConfigModel,
+) -> Result<()> {
+    match value {
+        ValueModel::None => {
+            out.push_str(&format!("message {} {{}}\n", message));
+            Ok(())
+        }
+        ValueModel::SingleType { ty_tokens } => {
+            let field = proto_field(ty_tokens, model)?;
+            out.push_str(&format!(
+                "message {} {{\n  {} = 1;\n}}\n",
+                message,
+                field.declaration(single_field)
+            ));
+            Ok(())
+        }
+        ValueModel::Struct { fields } => render_message(out, message, fields, model),
+    }
+}
+
+fn render_message(
+    out: &mut String,
+    message: &str,
+    fields: &[FieldSpec],
+    model: &ConfigModel,
+) -> Result<()> {
+    out.push_str(&format!("message {} {{\n", message));
+    for (index, field) in fields.iter().enumerate() {
+        let proto = proto_field(&field.ty_tokens, model)?;
+        out.push_str(&format!(
+            "  {} = {};\n",
+            proto.declaration(&field.name.to_string()),
+            index + 1
+        ));
+    }
+    out.push_str("}\n");
+    Ok(())
+}
+
+/// Proto message name of a helper struct. Helper names join their path with
+/// underscores (ex. `CreateUser_Address`), which `prost` would drop from the
+/// generated Rust type name anyway.
+fn proto_message_ident(helper: &Ident) -> Ident {
+    Ident::new(&helper.to_string().replace('_', ""), helper.span())
+}
+
+fn from_proto_fn_ident(repo_snake: &str, helper: &Ident) -> Ident {
+    format_ident!(
+        "__{}_grpc_from_{}",
+        repo_snake,
+        to_snake_case(&helper.to_string())
+    )
+}
+
+fn to_proto_fn_ident(repo_snake: &str, helper: &Ident) -> Ident {
+    format_ident!(
+        "__{}_grpc_to_{}",
+        repo_snake,
+        to_snake_case(&helper.to_string())
+    )
+}
+
+// Type mapping.
+// ----------------------------------------------------------------------------
+
+/// How a Rust field type is represented in protobuf.
+struct ProtoField {
+    label: Label,
+    elem: Elem,
+    /// Whether the trait method takes the value by reference.
+    by_ref: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Label {
+    Singular,
+    Optional,
+    Repeated,
+}
+
+enum Elem {
+    /// Scalar with the same Rust type on both sides (ex. `i64` / `int64`).
+    Scalar {
+        proto: &'static str,
+    },
+    /// Scalar widened to a larger proto type (ex. `u16` as `uint32`), checked
+    /// when converted back.
+    Widened {
+        proto: &'static str,
+        proto_rust: Ident,
+        rust: Ident,
+    },
+    String,
+    Bytes,
+    Message(Ident),
+}
+
+impl ProtoField {
+    fn declaration(&self, name: &str) -> String {
+        let ty = match &self.elem {
+            Elem::Scalar { proto } | Elem::Widened { proto, .. } => proto.to_string(),
+            Elem::String => "string".to_string(),
+            Elem::Bytes => "bytes".to_string(),
+            Elem::Message(helper) => proto_message_ident(helper).to_string(),
+        };
+        match self.label {
+            Label::Singular => format!("{} {}", ty, name),
+            Label::Optional => format!("optional {} {}", ty, name),
+            Label::Repeated => format!("repeated {} {}", ty, name),
+        }
+    }
+
+    /// Trait method argument for a value converted into the local `name`.
+    fn argument(&self, name: &Ident) -> TokenStream {
+        if self.by_ref {
+            quote! { &#name }
+        } else {
+            quote! { #name }
+        }
+    }
+
+    /// Expression converting a `prost` field into the Rust value, evaluating
+    /// to `Result<_, tonic::Status>`.
+    fn decode_expr(&self, repo_snake: &str, value: TokenStream, field: &Ident) -> TokenStream {
+        let field_name = field.to_string();
+        let convert = |value: TokenStream| self.elem.decode_expr(repo_snake, value, &field_name);
+        match self.label {
+            // `prost` represents singular message fields as `Option`s.
+            Label::Singular if matches!(self.elem, Elem::Message(_)) => {
+                let converted = convert(quote! { __value });
+                quote! {
+                    #value
+                        .ok_or_else(|| ::tonic::Status::invalid_argument(concat!("missing field `", #field_name, "`")))
+                        .and_then(|__value| #converted)
+                }
+            }
+            Label::Singular => convert(value),
+            Label::Optional => {
+                let converted = convert(quote! { __value });
+                quote! { #value.map(|__value| #converted).transpose() }
+            }
+            Label::Repeated => {
+                let converted = convert(quote! { __value });
+                quote! {
+                    #value
+                        .into_iter()
+                        .map(|__value| #converted)
+                        .collect::<::std::result::Result<::std::vec::Vec<_>, ::tonic::Status>>()
+                }
+            }
+        }
+    }
+
+    /// Expression converting the Rust value into a `prost` field.
+    fn encode_expr(&self, repo_snake: &str, value: TokenStream) -> TokenStream {
+        let convert = |value: TokenStream| self.elem.encode_expr(repo_snake, value);
+        match self.label {
+            Label::Singular if matches!(self.elem, Elem::Message(_)) => {
+                let converted = convert(value);
+                quote! { ::std::option::Option::Some(#converted) }
+            }
+            Label::Singular => convert(value),
+            Label::Optional => {
+                let converted = convert(quote! { __value });
+                quote! { #value.map(|__value| #converted) }
+            }
+            Label::Repeated => {
+                let converted = convert(quote! { __value });
+                quote! { #value.into_iter().map(|__value| #converted).collect() }
+            }
+        }
+    }
+}
+
+impl Elem {
+    fn decode_expr(&self, repo_snake: &str, value: TokenStream, field_name: &str) -> TokenStream {
+        match self {
+            Elem::Scalar { .. } | Elem::String | Elem::Bytes => {
+                quote! { ::std::result::Result::<_, ::tonic::Status>::Ok(#value) }
+            }
+            Elem::Widened { rust, .. } => quote! {
+                <#rust as ::std::convert::TryFrom<_>>::try_from(#value)
+                    .map_err(|_| ::tonic::Status::invalid_argument(concat!("field `", #field_name, "` is out of range")))
+            },
+            Elem::Message(helper) => {
+                let from_fn = from_proto_fn_ident(repo_snake, helper);
+                quote! { #from_fn(#value) }
+            }
+        }
+    }
+
+    fn encode_expr(&self, repo_snake: &str, value: TokenStream) -> TokenStream {
+        match self {
+            Elem::Scalar { .. } | Elem::String | Elem::Bytes => value,
+            // Lossless: `usize` / `isize` are at most 64 bits wide.
+            Elem::Widened { proto_rust, .. } => quote! { (#value) as #proto_rust },
+            Elem::Message(helper) => {
+                let to_fn = to_proto_fn_ident(repo_snake, helper);
+                quote! { #to_fn(#value) }
+            }
+        }
+    }
+}
+
+/// Map a field type to protobuf: scalars, `String`, `Vec<u8>`, helper structs,
+/// and `Option` / `Vec` of these.
+fn proto_field(ty_tokens: &TokenStream, model: &ConfigModel) -> Result<ProtoField> {
+    let unsupported = || {
+        Error::new_spanned(
+            ty_tokens,
+            format!(
+                "`#[grpc]` cannot represent `{}` in protobuf; use scalars, `String`, \
+                 `Vec<u8>`, inline structs, or an `Option` / `Vec` of these",
+                crate::schema::type_string(ty_tokens)
+            ),
+        )
+    };
+    let ty = syn::parse2::<Type>(ty_tokens.clone()).map_err(|_| unsupported())?;
+    let (ty, by_ref) = match ty {
+        Type::Reference(r) => (*r.elem, true),
+        ty => (ty, false),
+    };
+
+    if let Some(elem) = proto_elem(&ty, model) {
+        return Ok(ProtoField {
+            label: Label::Singular,
+            elem,
+            by_ref,
+        });
+    }
+    let (container, inner) = single_generic_argument(&ty).ok_or_else(unsupported)?;
+    let label = if container == "Option" {
+        Label::Optional
+    } else if container == "Vec" {
+        Label::Repeated
+    } else {
+        return Err(unsupported());
+    };
+    let elem = proto_elem(inner, model).ok_or_else(unsupported)?;
+    Ok(ProtoField {
+        label,
+        elem,
+        by_ref,
+    })
+}
+
+fn proto_elem(ty: &Type, model: &ConfigModel) -> Option<Elem> {
+    if let Some((container, inner)) = single_generic_argument(ty) {
+        let is_u8 = matches!(inner, Type::Path(p) if p.path.is_ident("u8"));
+        return (container == "Vec" && is_u8).then_some(Elem::Bytes);
+    }
+    let Type::Path(p) = ty else {
+        return None;
+    };
+    let ident = p.path.get_ident().or_else(|| {
+        // Also accept `std::string::String` and the like.
+        let last = p.path.segments.last()?;
+        matches!(last.arguments, PathArguments::None).then_some(&last.ident)
+    })?;
+    if model.helper_structs.iter().any(|h| &h.name == ident) {
+        return Some(Elem::Message(ident.clone()));
+    }
+    let widened = |proto: &'static str, proto_rust: &str| Elem::Widened {
+        proto,
+        proto_rust: Ident::new(proto_rust, Span::call_site()),
+        rust: ident.clone(),
+    };
+    Some(match ident.to_string().as_str() {
+        "String" | "str" => Elem::String,
+        "bool" => Elem::Scalar { proto: "bool" },
+        "i32" => Elem::Scalar { proto: "int32" },
+        "i64" => Elem::Scalar { proto: "int64" },
+        "u32" => Elem::Scalar { proto: "uint32" },
+        "u64" => Elem::Scalar { proto: "uint64" },
+        "f32" => Elem::Scalar { proto: "float" },
+        "f64" => Elem::Scalar { proto: "double" },
+        "i8" | "i16" => widened("int32", "i32"),
+        "u8" | "u16" => widened("uint32", "u32"),
+        "isize" => widened("int64", "i64"),
+        "usize" => widened("uint64", "u64"),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generate, render_proto};
+    use crate::{repository::test_model, testing};
+
+    #[test]
+    fn renders_service_and_messages() {
+        let proto = render_proto(
+            &test_model(
+                r#"
+            #[grpc]
+            MyRepo;
+            function create_user {
+                input: {
+                    name: &str,
+                    age: u8,
+                    nickname: Option<String>,
+                    tags: Vec<String>,
+                    avatar: Vec<u8>,
+                    address: { zip: String },
+                }
+                output: u64
+            }
+            function ping { input: None output: None }
+            "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert!(proto.starts_with("syntax = \"proto3\";\n\npackage my_repo;\n"));
+        assert!(proto.contains(
+            "service MyRepo {\n  rpc CreateUser(CreateUserInput) returns (CreateUserOutput);\n  \
+             rpc Ping(PingInput) returns (PingOutput);\n}\n"
+        ));
+        assert!(proto.contains(
+            "message CreateUserInput {\n  string name = 1;\n  uint32 age = 2;\n  \
+             optional string nickname = 3;\n  repeated string tags = 4;\n  bytes avatar = 5;\n  \
+             CreateUserAddress address = 6;\n}\n"
+        ));
+        assert!(proto.contains("message CreateUserOutput {\n  uint64 output = 1;\n}\n"));
+        assert!(proto.contains("message PingInput {}\n"));
+        assert!(proto.contains("message CreateUserAddress {\n  string zip = 1;\n}\n"));
+    }
+
+    #[test]
+    fn converts_widened_optional_and_message_fields() {
+        let items = testing::items(generate(
+            &test_model(
+                r#"
+            #[grpc]
+            MyRepo;
+            function update_user {
+                input: {
+                    age: u8,
+                    limits: Option<u16>,
+                    address: { zip: String },
+                }
+                output: Vec<i16>
+            }
+            "#,
+            )
+            .unwrap(),
+        ));
+
+        // Widened scalars are range-checked when decoded and cast back.
+        let method = testing::fn_of(&items, Some("MyRepo"), "update_user");
+        assert_eq!(method.param_names(), ["request"]);
+        assert!(method.body_contains(
+            "< u8 as :: std :: convert :: TryFrom < _ > > :: try_from (__message . age)"
+        ));
+        assert!(method.body_contains(
+            "__message . limits . map (| __value | < u16 as :: std :: convert :: TryFrom < _ > > :: try_from (__value)"
+        ));
+        assert!(method.body_contains(
+            "__result . into_iter () . map (| __value | (__value) as i32) . collect ()"
+        ));
+        // Singular messages are `Option`s in `prost`.
+        assert!(method.body_contains(
+            "__message . address . ok_or_else (| | :: tonic :: Status :: invalid_argument (concat ! (\"missing field `\" , \"address\" , \"`\")))"
+        ));
+        assert!(method.body_contains(
+            ". and_then (| __value | __my_repo_grpc_from_update_user_address (__value))"
+        ));
+        let to_message = testing::fn_of(&items, None, "__my_repo_grpc_to_update_user_address");
+        assert_eq!(to_message.param_names(), ["__value"]);
+        assert_eq!(to_message.output(), "-> proto :: UpdateUserAddress");
+    }
+
+    #[test]
+    fn rejects_types_protobuf_cannot_represent() {
+        for ty in [
+            "HashMap<String, u64>",
+            "Vec<Vec<u64>>",
+            "Vec<Option<u64>>",
+            "(u8, u8)",
+        ] {
+            let err = render_proto(
+                &test_model(&format!(
+                    "#[grpc] MyRepo; function f {{ input: {} output: None }}",
+                    ty
+                ))
+                .unwrap(),
+            )
+            .unwrap_err();
+            assert!(
+                err.to_string().contains("`#[grpc]` cannot represent"),
+                "{}: {}",
+                ty,
+                err
+            );
+        }
+    }
+}
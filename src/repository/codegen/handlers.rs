@@ -1,10 +1,18 @@
 use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
-use syn::Type;
+use syn::{PathArguments, Type};
 
+use super::repository::{
+    dyn_adapter_ident_for, field_error_ident_for, has_validated_structs, timeout_error_ident_for,
+    validation_failure_ident_for,
+};
 use crate::{
     helpers::{to_pascal_case, to_snake_case},
-    repository::model::{ConfigModel, FieldSpec, ValueModel},
+    instrument::{OperationSpan, instrument},
+    repository::model::{
+        ConfigModel, FieldSpec, FunctionModel, Validation, ValueModel, anchored_pattern, is_string,
+        single_generic_argument, strip_reference,
+    },
 };
 
 pub fn generate(model: &ConfigModel) -> TokenStream {
@@ -15,6 +23,14 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
         repo_name.span(),
     );
 
+    // Trait object the handlers call through (`native_async` traits are not
+    // `dyn`-compatible themselves).
+    let repo_dyn = if model.native_async {
+        dyn_adapter_ident_for(repo_name)
+    } else {
+        repo_name.clone()
+    };
+
     let per_fn_handlers: Vec<TokenStream> = model
         .functions
         .iter()
@@ -27,7 +43,10 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
             let output_ident: Ident = format_ident!("{}Output", base_pascal);
 
             // Handler signature inputs and call-time argument expressions.
-            let (handler_params_sig, call_args_ts) = build_handler_inputs(&f.input, &input_ident);
+            // Retried calls need their arguments for every attempt, so owned
+            // arguments are cloned.
+            let (handler_params_sig, call_args_ts) =
+                build_handler_inputs(&f.input, &input_ident, f.retry.is_some());
 
             // Return type for the handler.
             let (handler_ret_ty, map_ok_expr) =
@@ -39,11 +58,6 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
             } else {
                 quote! { async }
             };
-            let maybe_await = if f.is_blocking {
-                quote! {}
-            } else {
-                quote! { .await }
-            };
 
             // Deprecation attribute, if any.
             let maybe_deprecated_attr = if f.is_deprecated {
@@ -56,10 +70,37 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
                 quote! {}
             };
 
+            // Inputs are validated before the repository is called (unless the
+            // handler has no way to report the failure).
+            let validate_input = if f.is_direct {
+                quote! {}
+            } else {
+                match &f.input {
+                    ValueModel::None => quote! {},
+                    ValueModel::SingleType { ty_tokens } => match nested_shape(ty_tokens, model) {
+                        Some(NestedShape::Direct) => quote! { input.validate()?; },
+                        Some(NestedShape::Option) => quote! {
+                            if let ::std::option::Option::Some(__input) = &input {
+                                __input.validate()?;
+                            }
+                        },
+                        Some(NestedShape::Vec) => quote! {
+                            for __input in input.iter() {
+                                __input.validate()?;
+                            }
+                        },
+                        None => quote! {},
+                    },
+                    ValueModel::Struct { .. } => quote! { input.validate()?; },
+                }
+            };
+
             // Compose call site and mapping.
+            let call = build_policy_call(f, repo_name, quote! { __repo.#fn_ident(#call_args_ts) });
             let call_invoke = quote! {
-                let __repo: Arc<dyn #repo_name> = { __repo_init!() };
-                let __result = __repo.#fn_ident(#call_args_ts) #maybe_await;
+                #validate_input
+                let __repo: Arc<dyn #repo_dyn> = { __repo_init!() };
+                let __result = #call;
             };
 
             let body_ts = if f.is_direct {
@@ -117,6 +158,18 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
                 }
             };
 
+            let body_ts = instrument(
+                OperationSpan::new(fn_name.clone()).operation(if f.is_blocking {
+                    "blocking"
+                } else {
+                    "function"
+                }),
+                body_ts,
+                handler_ret_ty.clone(),
+                !f.is_blocking,
+                !f.is_direct,
+            );
+
             quote! {
                 #maybe_deprecated_attr
                 pub #maybe_async fn #handler_ident(#handler_params_sig) -> #handler_ret_ty {
@@ -126,17 +179,59 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
         })
         .collect();
 
+    // Delay before the first retry of a failed call; doubled for each further
+    // attempt, up to the maximum delay. Named after the repository, since the
+    // handlers of base repositories are generated alongside.
+    let retry_delay_const = if model.functions.iter().any(|f| f.retry.is_some()) {
+        let const_ident = retry_delay_const_ident_for(repo_name);
+        let max_const_ident = retry_max_delay_const_ident_for(repo_name);
+        quote! {
+            const #const_ident: u64 = 100;
+            const #max_const_ident: u64 = 10_000;
+        }
+    } else {
+        quote! {}
+    };
+
+    // Handlers for the functions of base repositories, generated by their own
+    // handlers macros. The repository initialized here is passed to them as
+    // is, so their calls dispatch through it (upcast to the base trait).
+    let inherited_handlers = model.supertraits.iter().map(|supertrait| {
+        let mut macro_path = supertrait.clone();
+        if let Some(last) = macro_path.segments.last_mut() {
+            last.ident = Ident::new(
+                &format!(
+                    "generate_{}_handlers",
+                    to_snake_case(&last.ident.to_string())
+                ),
+                last.ident.span(),
+            );
+            last.arguments = PathArguments::None;
+        }
+        quote! {
+            #macro_path! { $($repo_init)+ }
+        }
+    });
+
     // The macro accepts a single block/expression that initializes or retrieves
     // the repo. We wrap it in an inner macro so every handler can reuse it
     // without re-parsing.
     let handlers_iter = per_fn_handlers.iter();
+    let validation_impls = generate_validation_impls(model);
+    let (batch_types, batch_handler) = generate_batch_handler(model);
     quote! {
+        #validation_impls
+        #batch_types
+
         #[allow(unused_macros)]
         #[macro_export]
         macro_rules! #macro_name_ident {
             ($($repo_init:tt)+) => {
                 macro_rules! __repo_init { () => { { $($repo_init)+ } } }
+                #retry_delay_const
                 #(#handlers_iter)*
+                #batch_handler
+                #(#inherited_handlers)*
             };
         }
 
@@ -145,16 +240,501 @@ pub fn generate(model: &ConfigModel) -> TokenStream {
     }
 }
 
+/// With `#[batch]`: `{repo}_batch_handler`, running an ordered list of calls
+/// and reporting each call's result separately. Runs of consecutive
+/// `concurrent` calls are awaited together; every other call runs on its own,
+/// in order.
+///
+/// Returns the call / result types (emitted next to the IO structs) and the
+/// handler itself (emitted by the handlers macro, alongside the handlers it
+/// calls). Functions inherited from base repositories are batched by the base
+/// repositories' own batch handlers, if they declare `#[batch]` too. Batches
+/// are limited to `{REPO}_BATCH_MAX_CALLS` calls.
+///
+/// With `concurrent` functions, the generated code refers to `::futures_util`,
+/// so the consuming crate must then depend on `futures-util` itself.
+fn generate_batch_handler(model: &ConfigModel) -> (TokenStream, TokenStream) {
+    if !model.batch {
+        return (TokenStream::new(), TokenStream::new());
+    }
+    let repo_name = &model.repository_name;
+    let repo_name_snake = to_snake_case(&repo_name.to_string());
+    let call_ident = format_ident!("{}BatchCall", repo_name);
+    let output_ident = format_ident!("{}BatchOutput", repo_name);
+    let result_ident = format_ident!("{}BatchResult", repo_name);
+    let handler_ident = format_ident!("{}_batch_handler", repo_name_snake);
+    let call_fn_ident = format_ident!("__{}_batch_call", repo_name_snake);
+    let max_calls_ident = format_ident!("{}_BATCH_MAX_CALLS", repo_name_snake.to_uppercase());
+    let too_large_ident = format_ident!("{}BatchTooLarge", repo_name);
+
+    let mut call_variants = Vec::new();
+    let mut output_variants = Vec::new();
+    let mut call_arms = Vec::new();
+    let mut concurrent_variants = Vec::new();
+    for f in &model.functions {
+        let fn_ident = &f.name;
+        let fn_name = fn_ident.to_string();
+        let base_pascal = to_pascal_case(&fn_name);
+        let variant = format_ident!("{}", base_pascal);
+        let handler = format_ident!("{}_handler", fn_ident);
+
+        // Calls own their input; borrowed single inputs are lent to the
+        // handler.
+        let (call_variant, pattern, args) = match &f.input {
+            ValueModel::None => (
+                quote! { #variant },
+                quote! { #call_ident::#variant },
+                quote! {},
+            ),
+            ValueModel::SingleType { ty_tokens } => match syn::parse2::<Type>(ty_tokens.clone()) {
+                Ok(Type::Reference(r)) => {
+                    let elem = &r.elem;
+                    (
+                        quote! { #variant(<#elem as ::std::borrow::ToOwned>::Owned) },
+                        quote! { #call_ident::#variant(input) },
+                        quote! { ::std::borrow::Borrow::borrow(&input) },
+                    )
+                }
+                _ => (
+                    quote! { #variant(#ty_tokens) },
+                    quote! { #call_ident::#variant(input) },
+                    quote! { input },
+                ),
+            },
+            ValueModel::Struct { .. } => {
+                let input_ident = format_ident!("{}Input", base_pascal);
+                (
+                    quote! { #variant(#input_ident) },
+                    quote! { #call_ident::#variant(input) },
+                    quote! { input },
+                )
+            }
+        };
+        call_variants.push(quote! {
+            #[serde(rename = #fn_name)]
+            #call_variant
+        });
+        if f.concurrent {
+            concurrent_variants.push(match &f.input {
+                ValueModel::None => quote! { #call_ident::#variant },
+                _ => quote! { #call_ident::#variant(..) },
+            });
+        }
+
+        let output_ty = match &f.output {
+            ValueModel::None => quote! { () },
+            ValueModel::SingleType { ty_tokens } => quote! { #ty_tokens },
+            ValueModel::Struct { .. } => {
+                let ident = format_ident!("{}Output", base_pascal);
+                quote! { #ident }
+            }
+        };
+        output_variants.push(quote! { #variant(#output_ty) });
+
+        let call = if f.is_blocking {
+            quote! { #handler(#args) }
+        } else {
+            quote! { #handler(#args).await }
+        };
+        let result = if f.is_direct {
+            quote! { #result_ident::Ok(#output_ident::#variant(#call)) }
+        } else {
+            quote! {
+                match #call {
+                    ::std::result::Result::Ok(__output) => #result_ident::Ok(#output_ident::#variant(__output)),
+                    ::std::result::Result::Err(__error) => #result_ident::Error(__error),
+                }
+            }
+        };
+        call_arms.push(quote! { #pattern => #result });
+    }
+
+    // Only batches with `concurrent` calls need to await several at once.
+    let (is_concurrent_fn, run_calls) = if concurrent_variants.is_empty() {
+        (
+            quote! {},
+            quote! {
+                for __call in calls {
+                    __results.push(#call_fn_ident(__call).await);
+                }
+            },
+        )
+    } else {
+        (
+            quote! {
+                /// Whether the call may run alongside neighbouring
+                /// `concurrent` calls.
+                pub fn is_concurrent(&self) -> bool {
+                    matches!(self, #(#concurrent_variants)|*)
+                }
+            },
+            quote! {
+                let mut __calls = calls.into_iter().peekable();
+                while let ::std::option::Option::Some(__call) = __calls.next() {
+                    if !__call.is_concurrent() {
+                        __results.push(#call_fn_ident(__call).await);
+                        continue;
+                    }
+                    let mut __group = ::std::vec![__call];
+                    while let ::std::option::Option::Some(__call) =
+                        __calls.next_if(#call_ident::is_concurrent)
+                    {
+                        __group.push(__call);
+                    }
+                    __results.extend(
+                        ::futures_util::future::join_all(__group.into_iter().map(#call_fn_ident)).await,
+                    );
+                }
+            },
+        )
+    };
+
+    let types = quote! {
+        /// Maximum number of calls in one batch.
+        pub const #max_calls_ident: usize = 100;
+
+        ::fractic_server_error::define_client_error!(
+            #too_large_ident,
+            "Batch of {calls} calls exceeds the limit of {max_calls}.",
+            { calls: usize, max_calls: usize }
+        );
+
+        /// One call of a batch: `{"function": "<name>", "input": ...}`, with
+        /// `input` omitted for functions without input. Only covers the
+        /// repository's own functions; inherited functions are batched through
+        /// the base repositories' batch handlers.
+        #[derive(::serde::Deserialize)]
+        #[serde(tag = "function", content = "input")]
+        pub enum #call_ident {
+            #(#call_variants),*
+        }
+
+        impl #call_ident {
+            #is_concurrent_fn
+        }
+
+        /// Output of a successful batch call, as returned by its handler.
+        #[derive(::serde::Serialize)]
+        #[serde(untagged)]
+        pub enum #output_ident {
+            #(#output_variants),*
+        }
+
+        /// Result of one batch call: `{"ok": ...}` or `{"error": ...}`.
+        #[derive(::serde::Serialize)]
+        #[serde(rename_all = "snake_case")]
+        pub enum #result_ident {
+            Ok(#output_ident),
+            Error(::fractic_server_error::ServerError),
+        }
+    };
+
+    let handler = quote! {
+        #[allow(deprecated)]
+        async fn #call_fn_ident(call: #call_ident) -> #result_ident {
+            match call {
+                #(#call_arms),*
+            }
+        }
+
+        /// Run the calls and return their results in the same order. A failed
+        /// call does not affect the others; the batch as a whole is only
+        /// rejected if it holds more than the maximum number of calls.
+        pub async fn #handler_ident(
+            calls: ::std::vec::Vec<#call_ident>,
+        ) -> ::std::result::Result<::std::vec::Vec<#result_ident>, ::fractic_server_error::ServerError> {
+            if calls.len() > #max_calls_ident {
+                return ::std::result::Result::Err(#too_large_ident::new(calls.len(), #max_calls_ident));
+            }
+            let mut __results = ::std::vec::Vec::with_capacity(calls.len());
+            #run_calls
+            ::std::result::Result::Ok(__results)
+        }
+    };
+
+    (types, handler)
+}
+
+/// `validate()` impls for every input struct and helper struct, checking the
+/// fields' `#[validate(...)]` rules and recursing into nested helper structs.
+fn generate_validation_impls(model: &ConfigModel) -> TokenStream {
+    if !has_validated_structs(model) {
+        return quote! {};
+    }
+    let field_error_ident = field_error_ident_for(&model.repository_name);
+    let failure_ident = validation_failure_ident_for(&model.repository_name);
+    let input_structs = model.functions.iter().filter_map(|f| match &f.input {
+        ValueModel::Struct { fields } => Some((
+            format_ident!("{}Input", to_pascal_case(&f.name.to_string())),
+            fields.as_slice(),
+        )),
+        _ => None,
+    });
+    let helper_structs = model
+        .helper_structs
+        .iter()
+        .map(|h| (h.name.clone(), h.fields.as_slice()));
+    let impls = input_structs
+        .chain(helper_structs)
+        .map(|(struct_ident, fields)| {
+            let checks = fields
+                .iter()
+                .map(|field| gen_field_checks(field, model, &field_error_ident));
+            quote! {
+                impl #struct_ident {
+                    /// Check the `#[validate(...)]` rules of all fields, including
+                    /// those of nested inline structs, reporting every failure.
+                    pub fn validate(&self) -> ::std::result::Result<(), #failure_ident> {
+                        let mut __errors = ::std::vec::Vec::new();
+                        self.collect_validation_errors("", &mut __errors);
+                        if __errors.is_empty() {
+                            ::std::result::Result::Ok(())
+                        } else {
+                            ::std::result::Result::Err(#failure_ident { errors: __errors })
+                        }
+                    }
+
+                    #[doc(hidden)]
+                    #[allow(unused_variables, unused_comparisons)]
+                    pub fn collect_validation_errors(
+                        &self,
+                        __prefix: &str,
+                        __errors: &mut ::std::vec::Vec<#field_error_ident>,
+                    ) {
+                        #(#checks)*
+                    }
+                }
+            }
+        });
+    quote! { #(#impls)* }
+}
+
+fn gen_field_checks(
+    field: &FieldSpec,
+    model: &ConfigModel,
+    field_error_ident: &Ident,
+) -> TokenStream {
+    let name = &field.name;
+    let name_str = name.to_string();
+    let push_error = |message: TokenStream| {
+        quote! {
+            __errors.push(#field_error_ident {
+                field: format!("{}{}", __prefix, #name_str),
+                message: #message,
+            });
+        }
+    };
+
+    // Rules declared on the field itself.
+    let rules = field.validations.iter().map(|validation| match validation {
+        Validation::Length { min, max } => {
+            // Strings are measured in characters rather than bytes.
+            let len = if is_string(&field.ty_tokens) {
+                quote! { __value.chars().count() }
+            } else {
+                quote! { __value.len() }
+            };
+            let min = min.as_ref().map(|min| {
+                let push = push_error(quote! { format!("length must be at least {}", #min) });
+                quote! { if #len < #min { #push } }
+            });
+            let max = max.as_ref().map(|max| {
+                let push = push_error(quote! { format!("length must be at most {}", #max) });
+                quote! { if #len > #max { #push } }
+            });
+            quote! { #min #max }
+        }
+        Validation::Range { min, max } => {
+            let min = min.as_ref().map(|min| {
+                let push = push_error(quote! { format!("must be at least {}", #min) });
+                quote! { if *__value < (#min as _) { #push } }
+            });
+            let max = max.as_ref().map(|max| {
+                let push = push_error(quote! { format!("must be at most {}", #max) });
+                quote! { if *__value > (#max as _) { #push } }
+            });
+            quote! { #min #max }
+        }
+        Validation::Regex(pattern) => {
+            // The pattern was checked when the model was built, so it compiles.
+            let anchored = anchored_pattern(pattern);
+            let push = push_error(quote! { "does not match the expected format".to_string() });
+            quote! {
+                {
+                    static __PATTERN: ::std::sync::LazyLock<::regex::Regex> =
+                        ::std::sync::LazyLock::new(|| ::regex::Regex::new(#anchored).unwrap());
+                    if !__PATTERN.is_match(__value) {
+                        #push
+                    }
+                }
+            }
+        }
+    });
+    let rules = if field.validations.is_empty() {
+        quote! {}
+    } else if is_option(&field.ty_tokens) {
+        // Absent optional values are not checked.
+        quote! {
+            if let ::std::option::Option::Some(__value) = &self.#name {
+                #(#rules)*
+            }
+        }
+    } else {
+        quote! {
+            {
+                let __value = &self.#name;
+                #(#rules)*
+            }
+        }
+    };
+
+    // Rules of nested inline structs.
+    let nested = match nested_shape(&field.ty_tokens, model) {
+        Some(NestedShape::Direct) => quote! {
+            self.#name.collect_validation_errors(&format!("{}{}.", __prefix, #name_str), __errors);
+        },
+        Some(NestedShape::Option) => quote! {
+            if let ::std::option::Option::Some(__nested) = &self.#name {
+                __nested.collect_validation_errors(&format!("{}{}.", __prefix, #name_str), __errors);
+            }
+        },
+        Some(NestedShape::Vec) => quote! {
+            for (__index, __nested) in self.#name.iter().enumerate() {
+                __nested.collect_validation_errors(&format!("{}{}[{}].", __prefix, #name_str, __index), __errors);
+            }
+        },
+        None => quote! {},
+    };
+
+    quote! {
+        #rules
+        #nested
+    }
+}
+
+/// How a value refers to a helper struct, if it does in a way the generated
+/// validation can follow.
+enum NestedShape {
+    Direct,
+    Option,
+    Vec,
+}
+
+fn nested_shape(ty_tokens: &TokenStream, model: &ConfigModel) -> Option<NestedShape> {
+    let is_helper = |ty: &Type| match ty {
+        Type::Path(p) => p
+            .path
+            .get_ident()
+            .is_some_and(|ident| model.helper_structs.iter().any(|h| &h.name == ident)),
+        _ => false,
+    };
+    let ty = strip_reference(syn::parse2::<Type>(ty_tokens.clone()).ok()?);
+    if is_helper(&ty) {
+        return Some(NestedShape::Direct);
+    }
+    let (container, inner) = single_generic_argument(&ty)?;
+    if !is_helper(inner) {
+        return None;
+    }
+    if container == "Option" {
+        Some(NestedShape::Option)
+    } else if container == "Vec" {
+        Some(NestedShape::Vec)
+    } else {
+        None
+    }
+}
+
+fn is_option(ty_tokens: &TokenStream) -> bool {
+    syn::parse2::<Type>(ty_tokens.clone())
+        .ok()
+        .map(strip_reference)
+        .as_ref()
+        .and_then(single_generic_argument)
+        .is_some_and(|(container, _)| container == "Option")
+}
+
+fn retry_delay_const_ident_for(repo_name: &Ident) -> Ident {
+    format_ident!(
+        "__{}_RETRY_BASE_DELAY_MS",
+        to_snake_case(&repo_name.to_string()).to_uppercase()
+    )
+}
+
+fn retry_max_delay_const_ident_for(repo_name: &Ident) -> Ident {
+    format_ident!(
+        "__{}_RETRY_MAX_DELAY_MS",
+        to_snake_case(&repo_name.to_string()).to_uppercase()
+    )
+}
+
+/// Expression evaluating to the result of calling the repository function,
+/// applying its call policies: each attempt is limited to `timeout_ms`, and
+/// failed attempts are retried up to `retry` times with exponential backoff.
+/// Async calls time out and sleep with `tokio`, which the consuming crate must
+/// then depend on.
+fn build_policy_call(f: &FunctionModel, repo_name: &Ident, call: TokenStream) -> TokenStream {
+    let delay_const = retry_delay_const_ident_for(repo_name);
+    let max_delay_const = retry_max_delay_const_ident_for(repo_name);
+    let attempt = match f.timeout_ms {
+        Some(timeout_ms) => {
+            let fn_name = f.name.to_string();
+            let error_ident = timeout_error_ident_for(repo_name);
+            quote! {
+                match ::tokio::time::timeout(::std::time::Duration::from_millis(#timeout_ms), #call).await {
+                    ::std::result::Result::Ok(__result) => __result,
+                    ::std::result::Result::Err(_) => ::std::result::Result::Err(#error_ident::new(#fn_name, #timeout_ms)),
+                }
+            }
+        }
+        None if f.is_blocking => call,
+        None => quote! { #call.await },
+    };
+    let Some(retry) = f.retry else {
+        return attempt;
+    };
+    let sleep = if f.is_blocking {
+        quote! { ::std::thread::sleep(__delay) }
+    } else {
+        quote! { ::tokio::time::sleep(__delay).await }
+    };
+    quote! {
+        {
+            let mut __attempt: u32 = 0;
+            loop {
+                match #attempt {
+                    ::std::result::Result::Err(_) if __attempt < #retry => {
+                        let __delay = ::std::time::Duration::from_millis(
+                            #delay_const
+                                .saturating_mul(1u64.checked_shl(__attempt).unwrap_or(u64::MAX))
+                                .min(#max_delay_const),
+                        );
+                        #sleep;
+                        __attempt += 1;
+                    }
+                    __result => break __result,
+                }
+            }
+        }
+    }
+}
+
 fn build_handler_inputs(
     input: &ValueModel,
     input_struct_ident: &Ident,
+    clone_args: bool,
 ) -> (TokenStream, TokenStream) {
     match input {
         ValueModel::None => (quote! {}, quote! {}),
         ValueModel::SingleType { ty_tokens } => {
             // Accept and forward the single type directly.
             let sig = quote! { input: #ty_tokens };
-            (sig, quote! { input })
+            let needs_ref_mode = argument_needs_reference(ty_tokens.clone());
+            if clone_args && !needs_ref_mode.requires_ref {
+                (sig, quote! { input.clone() })
+            } else {
+                (sig, quote! { input })
+            }
         }
         ValueModel::Struct { fields, .. } => {
             let sig = quote! { input: #input_struct_ident };
@@ -167,6 +747,8 @@ fn build_handler_inputs(
                     } else {
                         quote! { &input.#name }
                     }
+                } else if clone_args {
+                    quote! { input.#name.clone() }
                 } else {
                     quote! { input.#name }
                 }
@@ -238,3 +820,200 @@ fn argument_needs_reference(tokens: TokenStream) -> RefMode {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::generate;
+    use crate::{repository::test_model, testing};
+
+    fn generated(dsl: &str) -> Vec<syn::Item> {
+        testing::items(generate(&test_model(dsl).unwrap()))
+    }
+
+    #[test]
+    fn validates_string_lengths_in_characters() {
+        let items = generated(
+            r#"
+            MyRepo;
+            function create_user {
+                input: {
+                    #[validate(length(min = 1))]
+                    name: String,
+                    #[validate(length(max = 3))]
+                    nickname: Option<String>,
+                    #[validate(length(max = 5))]
+                    tags: Vec<String>,
+                }
+                output: None
+            }
+            "#,
+        );
+
+        let checks = testing::fn_of(&items, Some("CreateUserInput"), "collect_validation_errors");
+        assert!(checks.body_contains("if __value . chars () . count () < 1 {"));
+        assert!(checks.body_contains("if __value . chars () . count () > 3 {"));
+        assert!(checks.body_contains("if __value . len () > 5 {"));
+    }
+
+    #[test]
+    fn casts_range_bounds_to_the_field_type() {
+        let items = generated(
+            r#"
+            MyRepo;
+            function create_user {
+                input: {
+                    #[validate(range(min = 1, max = 120))]
+                    age: u8,
+                    #[validate(range(min = -1.5))]
+                    score: Option<f32>,
+                }
+                output: None
+            }
+            "#,
+        );
+
+        let checks = testing::fn_of(&items, Some("CreateUserInput"), "collect_validation_errors");
+        assert!(checks.body_contains("if * __value < (1 as _) {"));
+        assert!(checks.body_contains("if * __value > (120 as _) {"));
+        assert!(checks.body_contains("if * __value < (- 1.5 as _) {"));
+    }
+
+    #[test]
+    fn reports_structured_field_errors() {
+        let items = generated(
+            r#"
+            MyRepo;
+            function create_user {
+                input: {
+                    #[validate(regex = "[a-z]+")]
+                    name: String,
+                }
+                output: None
+            }
+            "#,
+        );
+
+        let validate = testing::fn_of(&items, Some("CreateUserInput"), "validate");
+        assert_eq!(
+            validate.output(),
+            "-> :: std :: result :: Result < () , MyRepoValidationFailure >"
+        );
+        let checks = testing::fn_of(&items, Some("CreateUserInput"), "collect_validation_errors");
+        assert_eq!(checks.param_names(), ["__prefix", "__errors"]);
+        assert_eq!(
+            checks
+                .sig
+                .inputs
+                .last()
+                .map(|arg| quote::quote!(#arg).to_string()),
+            Some("__errors : & mut :: std :: vec :: Vec < MyRepoFieldError >".to_string())
+        );
+        assert!(checks.body_contains(
+            "__errors . push (MyRepoFieldError { field : format ! (\"{}{}\" , __prefix , \"name\")"
+        ));
+        assert!(checks.body_contains(":: regex :: Regex :: new (\"^(?:[a-z]+)$\") . unwrap ()"));
+        assert!(!checks.body_contains("join ("));
+    }
+
+    #[test]
+    fn groups_consecutive_concurrent_batch_calls() {
+        let items = generated(
+            r#"
+            #[batch]
+            MyRepo;
+            function get_user { input: String output: User concurrent }
+            function ping { input: None output: None concurrent }
+            function save_user { input: User output: None }
+            "#,
+        );
+
+        let is_concurrent = testing::fn_of(&items, Some("MyRepoBatchCall"), "is_concurrent");
+        assert_eq!(is_concurrent.output(), "-> bool");
+        assert!(is_concurrent.body_contains(
+            "matches ! (self , MyRepoBatchCall :: GetUser (..) | MyRepoBatchCall :: Ping)"
+        ));
+        let handler = testing::fn_of(&items, None, "my_repo_batch_handler");
+        assert!(handler.body_contains("__calls . next_if (MyRepoBatchCall :: is_concurrent)"));
+        assert!(handler.body_contains(
+            ":: futures_util :: future :: join_all (__group . into_iter () . map (__my_repo_batch_call)) . await"
+        ));
+    }
+
+    #[test]
+    fn runs_batches_without_concurrent_calls_in_order() {
+        let items = generated(
+            r#"
+            #[batch]
+            MyRepo;
+            function save_user { input: User output: None }
+            blocking compact { input: None output: None }
+            "#,
+        );
+
+        assert!(testing::fns(&items, "is_concurrent").is_empty());
+        let handler = testing::fn_of(&items, None, "my_repo_batch_handler");
+        assert!(!handler.body_contains("futures_util"));
+        assert!(handler.body_contains(
+            "for __call in calls { __results . push (__my_repo_batch_call (__call) . await) ; }"
+        ));
+        let call = testing::fn_of(&items, None, "__my_repo_batch_call");
+        assert!(call.body_contains("MyRepoBatchCall :: Compact => match compact_handler () {"));
+    }
+
+    #[test]
+    fn limits_batch_size() {
+        let items = generated("#[batch] MyRepo; function ping { input: None output: None }");
+
+        assert!(testing::type_names(&items).contains(&"MY_REPO_BATCH_MAX_CALLS".to_string()));
+        let handler = testing::fn_of(&items, None, "my_repo_batch_handler");
+        assert_eq!(handler.param_names(), ["calls"]);
+        assert!(handler.body_contains(
+            "if calls . len () > MY_REPO_BATCH_MAX_CALLS { return :: std :: result :: Result :: Err (MyRepoBatchTooLarge :: new (calls . len () , MY_REPO_BATCH_MAX_CALLS)) ; }"
+        ));
+    }
+
+    #[test]
+    fn owns_borrowed_batch_inputs() {
+        let items = generated(
+            r#"
+            #[batch]
+            MyRepo;
+            function count { input: &'a str output: u64 }
+            function tag { input: &[String] output: None }
+            "#,
+        );
+
+        let Some(syn::Item::Enum(calls)) = items
+            .iter()
+            .find(|item| matches!(item, syn::Item::Enum(e) if e.ident == "MyRepoBatchCall"))
+        else {
+            panic!("expected `MyRepoBatchCall`");
+        };
+        let fields = calls
+            .variants
+            .iter()
+            .map(|variant| quote::ToTokens::to_token_stream(&variant.fields).to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            [
+                "(< str as :: std :: borrow :: ToOwned > :: Owned)",
+                "(< [String] as :: std :: borrow :: ToOwned > :: Owned)",
+            ]
+        );
+    }
+
+    #[test]
+    fn omits_batch_handler_without_option() {
+        let items = generated("MyRepo; function ping { input: None output: None concurrent }");
+
+        let types = testing::type_names(&items);
+        assert!(
+            !types.iter().any(|name| name.contains("Batch")),
+            "{:?}",
+            types
+        );
+        assert!(testing::fns(&items, "my_repo_batch_handler").is_empty());
+        assert_eq!(testing::fns(&items, "ping_handler").len(), 1);
+    }
+}
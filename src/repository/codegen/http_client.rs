@@ -0,0 +1,268 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use syn::{PathArguments, Type};
+
+use super::repository::{build_method_inputs, build_method_output};
+use crate::{
+    helpers::{to_pascal_case, to_snake_case},
+    repository::model::{ConfigModel, FunctionModel, ValueModel},
+};
+
+/// `{Repo}HttpClient`, if the repository declares `#[http_client]`: implements
+/// the repository trait by POSTing each call's input to
+/// `{base_url}/{function}` and deserializing the output (or `ServerError`)
+/// from the response.
+///
+/// The trait impl itself is generated by an `impl_{repo}_http_client!` macro,
+/// so that clients of derived repositories can reuse it for the inherited
+/// functions. Base repositories must therefore declare `#[http_client]` too.
+///
+/// The generated code refers to `::reqwest`, `::serde` and `::serde_json`, so
+/// the consuming crate must depend on them itself.
+pub fn generate(model: &ConfigModel) -> TokenStream {
+    if !model.http_client {
+        return TokenStream::new();
+    }
+
+    let repo_name = &model.repository_name;
+    let client_ident = format_ident!("{}HttpClient", repo_name);
+    let error_ident = format_ident!("{}HttpError", repo_name);
+    let macro_name_ident = impl_macro_ident_for(repo_name);
+
+    let methods = model.functions.iter().map(generate_method);
+    let maybe_async_trait = if model.native_async {
+        quote! {}
+    } else {
+        quote! { #[::async_trait::async_trait] }
+    };
+
+    // Trait impls of base repositories, generated by their own macros for this
+    // client type.
+    let inherited_impls = model.supertraits.iter().map(|supertrait| {
+        let mut macro_path = supertrait.clone();
+        if let Some(last) = macro_path.segments.last_mut() {
+            last.ident = impl_macro_ident_for(&last.ident);
+            last.arguments = PathArguments::None;
+        }
+        quote! {
+            #macro_path! { $client }
+        }
+    });
+
+    quote! {
+        ::fractic_server_error::define_internal_error!(
+            #error_ident,
+            "Remote call to {function} failed: {details}.",
+            { function: &str, details: &str }
+        );
+
+        /// Implements the repository trait by calling the functions' handlers
+        /// over HTTP.
+        #[derive(::core::clone::Clone, ::core::fmt::Debug)]
+        pub struct #client_ident {
+            base_url: ::std::string::String,
+            client: ::reqwest::Client,
+        }
+
+        impl #client_ident {
+            /// Client for handlers served at `{base_url}/{function}`.
+            pub fn new(base_url: impl ::std::convert::Into<::std::string::String>) -> Self {
+                Self::with_client(base_url, ::reqwest::Client::new())
+            }
+
+            /// Like `new`, reusing an existing `reqwest` client (ex. one
+            /// configured with timeouts or default headers).
+            pub fn with_client(
+                base_url: impl ::std::convert::Into<::std::string::String>,
+                client: ::reqwest::Client,
+            ) -> Self {
+                let base_url: ::std::string::String = base_url.into();
+                Self {
+                    base_url: base_url.trim_end_matches('/').to_string(),
+                    client,
+                }
+            }
+
+            #[doc(hidden)]
+            pub async fn __call<I, O>(
+                &self,
+                function: &str,
+                input: &I,
+            ) -> ::std::result::Result<O, ::fractic_server_error::ServerError>
+            where
+                I: ::serde::Serialize + ?::std::marker::Sized,
+                O: ::serde::de::DeserializeOwned,
+            {
+                let __error = |details: ::std::string::String| #error_ident::new(function, &details);
+                let __body = ::serde_json::to_vec(input)
+                    .map_err(|e| __error(format!("invalid input: {}", e)))?;
+                let __response = self
+                    .client
+                    .post(format!("{}/{}", self.base_url, function))
+                    .header(::reqwest::header::CONTENT_TYPE, "application/json")
+                    .body(__body)
+                    .send()
+                    .await
+                    .map_err(|e| __error(e.to_string()))?;
+                let __status = __response.status();
+                let __text = __response.text().await.map_err(|e| __error(e.to_string()))?;
+                // Handlers without output may respond with an empty body.
+                let __json = if __text.trim().is_empty() { "null" } else { __text.as_str() };
+                if __status.is_success() {
+                    ::serde_json::from_str(__json)
+                        .map_err(|e| __error(format!("invalid response: {}", e)))
+                } else {
+                    ::std::result::Result::Err(
+                        match ::serde_json::from_str::<::fractic_server_error::ServerError>(__json) {
+                            ::std::result::Result::Ok(__server_error) => __server_error,
+                            ::std::result::Result::Err(_) => {
+                                __error(format!("status {}: {}", __status, __text))
+                            }
+                        },
+                    )
+                }
+            }
+        }
+
+        #[allow(unused_macros)]
+        #[macro_export]
+        macro_rules! #macro_name_ident {
+            ($client:ty) => {
+                #maybe_async_trait
+                impl #repo_name for $client {
+                    #(#methods)*
+                }
+                #(#inherited_impls)*
+            };
+        }
+
+        #[allow(unused_imports)]
+        pub(crate) use #macro_name_ident;
+
+        #macro_name_ident! { #client_ident }
+    }
+}
+
+fn impl_macro_ident_for(repo_name: &Ident) -> Ident {
+    Ident::new(
+        &format!("impl_{}_http_client", to_snake_case(&repo_name.to_string())),
+        repo_name.span(),
+    )
+}
+
+fn generate_method(f: &FunctionModel) -> TokenStream {
+    let fn_ident = &f.name;
+    let fn_name = fn_ident.to_string();
+    let base_pascal = to_pascal_case(&fn_name);
+    let input_ident = format_ident!("{}Input", base_pascal);
+    let output_ident = format_ident!("{}Output", base_pascal);
+
+    // Same signature as the trait method.
+    let (inputs_ts, needs_a_lifetime) = build_method_inputs(&f.input);
+    let output_ts = build_method_output(&f.output, &output_ident, f.is_direct);
+    let maybe_generics = if needs_a_lifetime {
+        quote! { <'a> }
+    } else {
+        quote! {}
+    };
+
+    // Request body, as received by the handler. Borrowed arguments (which the
+    // input struct holds by value) are cloned.
+    let request = match &f.input {
+        ValueModel::None => quote! { &() },
+        ValueModel::SingleType { .. } => quote! { &input },
+        ValueModel::Struct { fields } => {
+            let field_inits = fields.iter().map(|field| {
+                let name = &field.name;
+                match syn::parse2::<Type>(field.ty_tokens.clone()) {
+                    Ok(Type::Reference(_)) => {
+                        quote! { #name: ::std::borrow::ToOwned::to_owned(#name) }
+                    }
+                    _ => quote! { #name },
+                }
+            });
+            quote! { &#input_ident { #(#field_inits),* } }
+        }
+    };
+
+    // Response body, as returned by the handler, and how to unwrap it into
+    // the method's output.
+    let (response_ty, unwrap) = match &f.output {
+        ValueModel::None => (quote! { () }, quote! { __response }),
+        ValueModel::SingleType { ty_tokens } => (quote! { #ty_tokens }, quote! { __response }),
+        ValueModel::Struct { fields } if fields.len() == 1 => {
+            let field_name = &fields[0].name;
+            (quote! { #output_ident }, quote! { __response.#field_name })
+        }
+        ValueModel::Struct { .. } => (quote! { #output_ident }, quote! { __response }),
+    };
+
+    quote! {
+        async fn #fn_ident #maybe_generics (&self #inputs_ts) -> #output_ts {
+            let __response: #response_ty = self.__call(#fn_name, #request).await?;
+            ::std::result::Result::Ok(#unwrap)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::generate;
+    use crate::{repository::test_model, testing};
+
+    fn generated(dsl: &str) -> Vec<syn::Item> {
+        testing::items(generate(&test_model(dsl).unwrap()))
+    }
+
+    #[test]
+    fn maps_inputs_to_request_bodies() {
+        let items = generated(
+            r#"
+            #[http_client]
+            MyRepo;
+            function ping { input: None output: None }
+            function count { input: &str output: u64 }
+            function rename { input: { id: u64, name: &str } output: None }
+            "#,
+        );
+
+        let ping = testing::fn_of(&items, Some("MyRepo"), "ping");
+        assert!(ping.sig.asyncness.is_some());
+        assert!(ping.param_names().is_empty());
+        assert!(ping.body_contains("self . __call (\"ping\" , & ()) . await ?"));
+        let count = testing::fn_of(&items, Some("MyRepo"), "count");
+        assert_eq!(count.param_names(), ["input"]);
+        assert!(count.body_contains("self . __call (\"count\" , & input) . await ?"));
+        let rename = testing::fn_of(&items, Some("MyRepo"), "rename");
+        assert_eq!(rename.param_names(), ["id", "name"]);
+        assert!(rename.body_contains(
+            "& RenameInput { id , name : :: std :: borrow :: ToOwned :: to_owned (name) }"
+        ));
+    }
+
+    #[test]
+    fn unwraps_single_field_outputs() {
+        let items = generated(
+            r#"
+            #[http_client]
+            MyRepo;
+            function total { input: None output: { sum: u64 } }
+            function stats { input: None output: { sum: u64, count: u64 } }
+            "#,
+        );
+
+        let total = testing::fn_of(&items, Some("MyRepo"), "total");
+        assert!(total.body_contains(
+            "let __response : TotalOutput = self . __call (\"total\" , & ()) . await ?"
+        ));
+        assert!(total.body_contains(":: std :: result :: Result :: Ok (__response . sum)"));
+        let stats = testing::fn_of(&items, Some("MyRepo"), "stats");
+        assert!(stats.body_contains(":: std :: result :: Result :: Ok (__response)"));
+    }
+
+    #[test]
+    fn omits_client_without_option() {
+        let items = generated("MyRepo; function ping { input: None output: None }");
+        assert!(items.is_empty());
+    }
+}
@@ -0,0 +1,764 @@
+use std::collections::HashMap;
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Attribute, Expr, Lit, LitStr, Token, Type, UnOp};
+
+use crate::{
+    helpers::{to_pascal_case, to_snake_case},
+    repository::model::{
+        ConfigModel, FieldSpec, HelperStruct, Validation, ValueModel, anchored_pattern,
+    },
+    schema::Json,
+};
+
+const DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// `{repo}_schemas()`, if the repository declares `#[json_schema]`: JSON
+/// Schemas of the generated `*Input`, `*Output` and helper structs, keyed by
+/// struct name, for validating payloads before they reach the handlers.
+///
+/// Schemas follow the structs' serde representation (`rename`, `skip`,
+/// `default`, `flatten`, ...) in the direction the payload travels: inputs are
+/// deserialized, outputs serialized. Validation rules on input fields become
+/// the equivalent schema keywords. Types the macro cannot see into (ex. user
+/// structs) accept any value. Functions inherited from base repositories are
+/// described by the base repositories' own `_schemas()`.
+pub fn generate(model: &ConfigModel) -> TokenStream {
+    if !model.json_schema {
+        return TokenStream::new();
+    }
+
+    // Direction each helper is first used in, for its standalone schema.
+    let mut helper_directions = HashMap::new();
+    let mut schemas = Vec::new();
+    for f in &model.functions {
+        let base_pascal = to_pascal_case(&f.name.to_string());
+        for (value, suffix, direction) in [
+            (&f.input, "Input", Direction::Deserialize),
+            (&f.output, "Output", Direction::Serialize),
+        ] {
+            let ValueModel::Struct { fields } = value else {
+                continue;
+            };
+            let title = format!("{}{}", base_pascal, suffix);
+            let mut builder = SchemaBuilder::new(model, direction);
+            let document = builder.document(&title, fields);
+            for helper in builder.defined {
+                helper_directions.entry(helper).or_insert(direction);
+            }
+            schemas.push((title, document));
+        }
+    }
+    for helper in &model.helper_structs {
+        let direction = helper_directions
+            .get(&helper.name.to_string())
+            .copied()
+            .unwrap_or(Direction::Deserialize);
+        let title = helper.name.to_string();
+        let document = SchemaBuilder::new(model, direction).document(&title, &helper.fields);
+        schemas.push((title, document));
+    }
+
+    let fn_ident = format_ident!(
+        "{}_schemas",
+        to_snake_case(&model.repository_name.to_string())
+    );
+    let doc = format!(
+        " JSON Schemas ({}) of the payloads of `{}`, keyed by generated struct \
+         name.",
+        DIALECT, model.repository_name
+    );
+    let entries = schemas.iter().map(|(title, document)| {
+        let document = document.render();
+        quote! { (#title, #document) }
+    });
+    quote! {
+        #[doc = #doc]
+        pub fn #fn_ident() -> &'static ::std::collections::BTreeMap<&'static str, ::serde_json::Value> {
+            // Parsed on first use only.
+            static __SCHEMAS: ::std::sync::LazyLock<
+                ::std::collections::BTreeMap<&'static str, ::serde_json::Value>,
+            > = ::std::sync::LazyLock::new(|| {
+                [#(#entries),*]
+                    .into_iter()
+                    .map(|(name, schema)| {
+                        let schema = ::serde_json::from_str(schema)
+                            .expect("generated JSON Schema should be valid JSON");
+                        (name, schema)
+                    })
+                    .collect()
+            });
+            &__SCHEMAS
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Serialize,
+    Deserialize,
+}
+
+/// Builds the schema of one struct, collecting the helper structs it refers
+/// to (transitively) under `$defs`.
+struct SchemaBuilder<'a> {
+    model: &'a ConfigModel,
+    direction: Direction,
+    /// Helpers referenced so far, in order of first reference.
+    referenced: Vec<&'a HelperStruct>,
+    /// Names of the helpers rendered under `$defs`.
+    defined: Vec<String>,
+}
+
+impl<'a> SchemaBuilder<'a> {
+    fn new(model: &'a ConfigModel, direction: Direction) -> Self {
+        Self {
+            model,
+            direction,
+            referenced: Vec::new(),
+            defined: Vec::new(),
+        }
+    }
+
+    fn document(&mut self, title: &str, fields: &[FieldSpec]) -> Json {
+        let Json::Object(body) = self.object_schema(fields) else {
+            unreachable!("object schemas are JSON objects");
+        };
+        let mut document = vec![
+            ("$schema", Json::string(DIALECT)),
+            ("title", Json::string(title)),
+        ];
+        document.extend(body);
+
+        // Rendering a helper may reference further helpers.
+        let mut defs = Vec::new();
+        let mut next = 0;
+        while let Some(helper) = self.referenced.get(next).copied() {
+            next += 1;
+            defs.push((helper.name.to_string(), self.object_schema(&helper.fields)));
+        }
+        self.defined = defs.iter().map(|(name, _)| name.clone()).collect();
+        if !defs.is_empty() {
+            document.push(("$defs", Json::Map(defs)));
+        }
+        Json::Object(document)
+    }
+
+    fn object_schema(&mut self, fields: &[FieldSpec]) -> Json {
+        let mut properties = Vec::new();
+        let mut required = Vec::new();
+        self.collect_properties(fields, true, &mut properties, &mut required);
+        let mut schema = vec![
+            ("type", Json::string("object")),
+            ("properties", Json::Map(properties)),
+        ];
+        if !required.is_empty() {
+            schema.push(("required", Json::strings(required)));
+        }
+        Json::Object(schema)
+    }
+
+    fn collect_properties(
+        &mut self,
+        fields: &[FieldSpec],
+        may_require: bool,
+        properties: &mut Vec<(String, Json)>,
+        required: &mut Vec<String>,
+    ) {
+        for field in fields {
+            let serde = SerdeField::from_attrs(&field.attrs);
+            if serde.is_skipped(self.direction) {
+                continue;
+            }
+            let ty = syn::parse2::<Type>(field.ty_tokens.clone()).ok();
+            let ty = ty.as_ref().map(strip_wrappers);
+            let option_inner = ty.and_then(|ty| generic_argument(ty, "Option"));
+
+            // Flattened helpers contribute their own properties. Other
+            // flattened types (ex. maps) cannot be described.
+            if serde.flatten {
+                let inner = option_inner.or(ty).map(strip_wrappers);
+                if let Some(helper) = inner.and_then(|ty| self.helper(ty)) {
+                    let may_require = may_require && option_inner.is_none();
+                    self.collect_properties(&helper.fields, may_require, properties, required);
+                }
+                continue;
+            }
+
+            let name = serde
+                .name(self.direction)
+                .unwrap_or_else(|| field.name.to_string().trim_start_matches("r#").to_string());
+            let schema = match ty {
+                _ if serde.has_custom_codec(self.direction) => any(),
+                Some(ty) => match option_inner {
+                    Some(inner) => Json::Object(vec![(
+                        "anyOf",
+                        Json::Array(vec![
+                            self.validated_schema(inner, &field.validations),
+                            json_type("null"),
+                        ]),
+                    )]),
+                    None => self.validated_schema(ty, &field.validations),
+                },
+                None => any(),
+            };
+            let is_required = match self.direction {
+                // Missing `Option`s deserialize as `None`.
+                Direction::Deserialize => !serde.default && option_inner.is_none(),
+                Direction::Serialize => !serde.skip_serializing_if,
+            };
+            if may_require && is_required {
+                required.push(name.clone());
+            }
+            properties.push((name, schema));
+        }
+    }
+
+    fn validated_schema(&mut self, ty: &Type, validations: &[Validation]) -> Json {
+        let schema = self.type_schema(ty);
+        if validations.is_empty() {
+            return schema;
+        }
+        let Json::Object(mut schema) = schema else {
+            return schema;
+        };
+        let is_string = schema.iter().any(|(key, value)| {
+            *key == "type" && matches!(value, Json::String(t) if t == "string")
+        });
+        for validation in validations {
+            match validation {
+                Validation::Length { min, max } => {
+                    let (min_key, max_key) = if is_string {
+                        ("minLength", "maxLength")
+                    } else {
+                        ("minItems", "maxItems")
+                    };
+                    if let Some(min) = min {
+                        tighten(&mut schema, min_key, min.base10_digits(), Bound::Lower);
+                    }
+                    if let Some(max) = max {
+                        tighten(&mut schema, max_key, max.base10_digits(), Bound::Upper);
+                    }
+                }
+                Validation::Range { min, max } => {
+                    if let Some(min) = min.as_ref().and_then(number_literal) {
+                        tighten(&mut schema, "minimum", &min, Bound::Lower);
+                    }
+                    if let Some(max) = max.as_ref().and_then(number_literal) {
+                        tighten(&mut schema, "maximum", &max, Bound::Upper);
+                    }
+                }
+                Validation::Regex(pattern) => {
+                    let pattern = Json::String(anchored_pattern(pattern));
+                    // Keywords must be unique, so further patterns go under
+                    // `allOf`.
+                    if !schema.iter().any(|(key, _)| *key == "pattern") {
+                        schema.push(("pattern", pattern));
+                    } else {
+                        let pattern = Json::Object(vec![("pattern", pattern)]);
+                        match schema.iter_mut().find(|(key, _)| *key == "allOf") {
+                            Some((_, Json::Array(all_of))) => all_of.push(pattern),
+                            _ => schema.push(("allOf", Json::Array(vec![pattern]))),
+                        }
+                    }
+                }
+            }
+        }
+        Json::Object(schema)
+    }
+
+    fn type_schema(&mut self, ty: &Type) -> Json {
+        let ty = strip_wrappers(ty);
+        match ty {
+            Type::Slice(slice) => array(self.type_schema(&slice.elem)),
+            Type::Array(array_ty) => array(self.type_schema(&array_ty.elem)),
+            Type::Tuple(tuple) if tuple.elems.is_empty() => json_type("null"),
+            Type::Tuple(tuple) => {
+                let items = tuple
+                    .elems
+                    .iter()
+                    .map(|elem| self.type_schema(elem))
+                    .collect();
+                let len = tuple.elems.len().to_string();
+                Json::Object(vec![
+                    ("type", Json::string("array")),
+                    ("prefixItems", Json::Array(items)),
+                    ("items", Json::Bool(false)),
+                    ("minItems", Json::Number(len.clone())),
+                    ("maxItems", Json::Number(len)),
+                ])
+            }
+            Type::Path(_) => self.path_schema(ty),
+            _ => any(),
+        }
+    }
+
+    fn path_schema(&mut self, ty: &Type) -> Json {
+        if let Some(helper) = self.helper(ty) {
+            let reference = format!("#/$defs/{}", helper.name);
+            if !self.referenced.iter().any(|h| h.name == helper.name) {
+                self.referenced.push(helper);
+            }
+            return Json::Object(vec![("$ref", Json::String(reference))]);
+        }
+        let Type::Path(p) = ty else {
+            return any();
+        };
+        let Some(segment) = p.path.segments.last() else {
+            return any();
+        };
+        let args = type_arguments(segment);
+        match (segment.ident.to_string().as_str(), args.as_slice()) {
+            ("bool", []) => json_type("boolean"),
+            ("u8" | "u16" | "u32" | "u64" | "u128" | "usize", []) => Json::Object(vec![
+                ("type", Json::string("integer")),
+                ("minimum", Json::Number("0".to_string())),
+            ]),
+            ("i8" | "i16" | "i32" | "i64" | "i128" | "isize", []) => json_type("integer"),
+            ("f32" | "f64", []) => json_type("number"),
+            ("String" | "str", []) => json_type("string"),
+            ("char", []) => Json::Object(vec![
+                ("type", Json::string("string")),
+                ("minLength", Json::Number("1".to_string())),
+                ("maxLength", Json::Number("1".to_string())),
+            ]),
+            ("Option", [inner]) => Json::Object(vec![(
+                "anyOf",
+                Json::Array(vec![self.type_schema(inner), json_type("null")]),
+            )]),
+            ("Vec" | "VecDeque" | "LinkedList", [item]) => array(self.type_schema(item)),
+            ("HashSet" | "BTreeSet", [item]) => {
+                let Json::Object(mut schema) = array(self.type_schema(item)) else {
+                    unreachable!("array schemas are JSON objects");
+                };
+                schema.push(("uniqueItems", Json::Bool(true)));
+                Json::Object(schema)
+            }
+            ("HashMap" | "BTreeMap", [_, value]) => Json::Object(vec![
+                ("type", Json::string("object")),
+                ("additionalProperties", self.type_schema(value)),
+            ]),
+            ("Box" | "Arc" | "Rc" | "Cow", [.., inner]) => self.type_schema(inner),
+            _ => any(),
+        }
+    }
+
+    fn helper(&self, ty: &Type) -> Option<&'a HelperStruct> {
+        let Type::Path(p) = ty else {
+            return None;
+        };
+        let ident = p.path.get_ident()?;
+        self.model.helper_structs.iter().find(|h| &h.name == ident)
+    }
+}
+
+/// Serde field attributes that affect the representation.
+#[derive(Default)]
+struct SerdeField {
+    rename_serialize: Option<String>,
+    rename_deserialize: Option<String>,
+    skip_serializing: bool,
+    skip_deserializing: bool,
+    skip_serializing_if: bool,
+    default: bool,
+    flatten: bool,
+    serialize_with: bool,
+    deserialize_with: bool,
+}
+
+impl SerdeField {
+    /// Malformed attributes are ignored here; serde reports them.
+    fn from_attrs(attrs: &[Attribute]) -> Self {
+        let mut field = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            let _ = attr.parse_nested_meta(|meta| {
+                let path = &meta.path;
+                if path.is_ident("rename") {
+                    if meta.input.peek(Token![=]) {
+                        let name = meta.value()?.parse::<LitStr>()?.value();
+                        field.rename_serialize = Some(name.clone());
+                        field.rename_deserialize = Some(name);
+                    } else {
+                        meta.parse_nested_meta(|inner| {
+                            let name = inner.value()?.parse::<LitStr>()?.value();
+                            if inner.path.is_ident("serialize") {
+                                field.rename_serialize = Some(name);
+                            } else if inner.path.is_ident("deserialize") {
+                                field.rename_deserialize = Some(name);
+                            }
+                            Ok(())
+                        })?;
+                    }
+                    return Ok(());
+                }
+                if path.is_ident("skip") {
+                    field.skip_serializing = true;
+                    field.skip_deserializing = true;
+                } else if path.is_ident("skip_serializing") {
+                    field.skip_serializing = true;
+                } else if path.is_ident("skip_deserializing") {
+                    field.skip_deserializing = true;
+                } else if path.is_ident("skip_serializing_if") {
+                    field.skip_serializing_if = true;
+                } else if path.is_ident("default") {
+                    field.default = true;
+                } else if path.is_ident("flatten") {
+                    field.flatten = true;
+                } else if path.is_ident("with") {
+                    field.serialize_with = true;
+                    field.deserialize_with = true;
+                } else if path.is_ident("serialize_with") {
+                    field.serialize_with = true;
+                } else if path.is_ident("deserialize_with") {
+                    field.deserialize_with = true;
+                }
+                // Skip the argument of any option, known or not.
+                if meta.input.peek(Token![=]) {
+                    meta.value()?.parse::<Expr>()?;
+                } else if meta.input.peek(syn::token::Paren) {
+                    meta.parse_nested_meta(|inner| {
+                        if inner.input.peek(Token![=]) {
+                            inner.value()?.parse::<Expr>()?;
+                        }
+                        Ok(())
+                    })?;
+                }
+                Ok(())
+            });
+        }
+        field
+    }
+
+    fn name(&self, direction: Direction) -> Option<String> {
+        match direction {
+            Direction::Serialize => self.rename_serialize.clone(),
+            Direction::Deserialize => self.rename_deserialize.clone(),
+        }
+    }
+
+    fn is_skipped(&self, direction: Direction) -> bool {
+        match direction {
+            Direction::Serialize => self.skip_serializing,
+            Direction::Deserialize => self.skip_deserializing,
+        }
+    }
+
+    fn has_custom_codec(&self, direction: Direction) -> bool {
+        match direction {
+            Direction::Serialize => self.serialize_with,
+            Direction::Deserialize => self.deserialize_with,
+        }
+    }
+}
+
+/// Schema accepting any value.
+fn any() -> Json {
+    Json::Object(Vec::new())
+}
+
+fn json_type(name: &'static str) -> Json {
+    Json::Object(vec![("type", Json::string(name))])
+}
+
+fn array(items: Json) -> Json {
+    Json::Object(vec![("type", Json::string("array")), ("items", items)])
+}
+
+/// References, parentheses and invisible groups serialize as their contents.
+fn strip_wrappers(ty: &Type) -> &Type {
+    match ty {
+        Type::Reference(r) => strip_wrappers(&r.elem),
+        Type::Paren(p) => strip_wrappers(&p.elem),
+        Type::Group(g) => strip_wrappers(&g.elem),
+        ty => ty,
+    }
+}
+
+fn type_arguments(segment: &syn::PathSegment) -> Vec<&Type> {
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return Vec::new();
+    };
+    args.args
+        .iter()
+        .filter_map(|arg| match arg {
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        })
+        .collect()
+}
+
+/// `T` if `ty` is `{container}<T>`.
+fn generic_argument<'t>(ty: &'t Type, container: &str) -> Option<&'t Type> {
+    let Type::Path(p) = ty else {
+        return None;
+    };
+    let segment = p.path.segments.last()?;
+    match type_arguments(segment).as_slice() {
+        [inner] if segment.ident == container => Some(inner),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Bound {
+    Lower,
+    Upper,
+}
+
+/// Set the bound keyword `key` to `value`, keeping an existing bound (ex. the
+/// `minimum` of unsigned integers) if it is already stricter.
+fn tighten(schema: &mut Vec<(&'static str, Json)>, key: &'static str, value: &str, bound: Bound) {
+    let Some((_, existing)) = schema.iter_mut().find(|(k, _)| *k == key) else {
+        schema.push((key, Json::Number(value.to_string())));
+        return;
+    };
+    let stricter = match existing {
+        Json::Number(current) => match compare_numbers(value, current) {
+            Some(ordering) => match bound {
+                Bound::Lower => ordering.is_gt(),
+                Bound::Upper => ordering.is_lt(),
+            },
+            None => false,
+        },
+        _ => true,
+    };
+    if stricter {
+        *existing = Json::Number(value.to_string());
+    }
+}
+
+/// Order of two number literals, exact for integers.
+fn compare_numbers(a: &str, b: &str) -> Option<std::cmp::Ordering> {
+    match (a.parse::<i128>(), b.parse::<i128>()) {
+        (Ok(a), Ok(b)) => Some(a.cmp(&b)),
+        _ => a.parse::<f64>().ok()?.partial_cmp(&b.parse::<f64>().ok()?),
+    }
+}
+
+/// JSON rendering of a numeric literal bound (ex. `10`, `-0.5`). Other
+/// expressions (ex. constants) have no schema equivalent.
+fn number_literal(tokens: &TokenStream) -> Option<String> {
+    match syn::parse2::<Expr>(tokens.clone()).ok()? {
+        Expr::Lit(lit) => lit_digits(&lit.lit),
+        Expr::Unary(unary) if matches!(unary.op, UnOp::Neg(_)) => match *unary.expr {
+            Expr::Lit(lit) => lit_digits(&lit.lit).map(|digits| format!("-{}", digits)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn lit_digits(lit: &Lit) -> Option<String> {
+    match lit {
+        Lit::Int(int) => Some(int.base10_digits().to_string()),
+        Lit::Float(float) => Some(float.base10_digits().to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::ToTokens as _;
+
+    use super::{Direction, SchemaBuilder, generate};
+    use crate::{
+        repository::{model::ValueModel, test_model},
+        testing,
+    };
+
+    /// Rendered schema of the first function's input or output struct.
+    fn schema(dsl: &str, direction: Direction) -> String {
+        let model = test_model(dsl).unwrap();
+        let value = match direction {
+            Direction::Deserialize => &model.functions[0].input,
+            Direction::Serialize => &model.functions[0].output,
+        };
+        let ValueModel::Struct { fields } = value else {
+            panic!("expected a struct");
+        };
+        SchemaBuilder::new(&model, direction)
+            .document("Test", fields)
+            .render()
+    }
+
+    #[test]
+    fn follows_serde_attributes_in_payload_direction() {
+        let dsl = r#"
+            MyRepo;
+            function f {
+                input: {
+                    #[serde(rename = "userName")]
+                    name: String,
+                    #[serde(rename(serialize = "out", deserialize = "in"))]
+                    both: u8,
+                    #[serde(skip)]
+                    secret: String,
+                    #[serde(skip_deserializing)]
+                    server_only: bool,
+                    #[serde(default)]
+                    limit: u32,
+                    #[serde(with = "my_codec")]
+                    when: Timestamp,
+                    note: Option<String>,
+                }
+                output: {
+                    #[serde(skip_serializing_if = "Option::is_none")]
+                    next: Option<String>,
+                    total: u64,
+                }
+            }
+        "#;
+
+        let input = schema(dsl, Direction::Deserialize);
+        assert!(input.contains(r#""userName":{"type":"string"}"#));
+        assert!(input.contains(r#""in":{"type":"integer","minimum":0}"#));
+        assert!(!input.contains("secret"));
+        assert!(!input.contains("server_only"));
+        assert!(input.contains(r#""when":{}"#));
+        assert!(input.contains(r#""note":{"anyOf":[{"type":"string"},{"type":"null"}]}"#));
+        assert!(input.contains(r#""required":["userName","in","when"]"#));
+
+        let output = schema(dsl, Direction::Serialize);
+        assert!(output.contains(r#""required":["total"]"#));
+    }
+
+    #[test]
+    fn flattens_helper_properties() {
+        let input = schema(
+            r#"
+            MyRepo;
+            function f {
+                input: {
+                    id: String,
+                    #[serde(flatten)]
+                    paging: { offset: u32, limit: u32 },
+                    #[serde(flatten)]
+                    filter: Option<{ tag: String }>,
+                }
+                output: None
+            }
+            "#,
+            Direction::Deserialize,
+        );
+
+        assert!(input.contains(r#""offset":{"type":"integer","minimum":0}"#));
+        assert!(input.contains(r#""tag":{"type":"string"}"#));
+        assert!(!input.contains("paging"));
+        // Properties of an optional flattened helper are never required.
+        assert!(input.contains(r#""required":["id","offset","limit"]"#));
+    }
+
+    #[test]
+    fn maps_validation_rules_without_duplicate_keywords() {
+        let input = schema(
+            r#"
+            MyRepo;
+            function f {
+                input: {
+                    #[validate(length(min = 1, max = 64), regex = "[a-z]+")]
+                    name: String,
+                    #[validate(range(min = 18, max = 130))]
+                    age: u8,
+                    #[validate(range(min = 0, max = 10))]
+                    offset: u32,
+                    #[validate(length(min = 0, max = 4))]
+                    initial: char,
+                    #[validate(length(max = 3))]
+                    tags: Vec<String>,
+                    #[validate(regex = "a.*")]
+                    #[validate(regex = ".*z")]
+                    code: String,
+                }
+                output: None
+            }
+            "#,
+            Direction::Deserialize,
+        );
+
+        assert!(input.contains(
+            r#""name":{"type":"string","minLength":1,"maxLength":64,"pattern":"^(?:[a-z]+)$"}"#
+        ));
+        assert!(input.contains(r#""age":{"type":"integer","minimum":18,"maximum":130}"#));
+        // Bounds matching the type's own are not repeated.
+        assert!(input.contains(r#""offset":{"type":"integer","minimum":0,"maximum":10}"#));
+        assert!(input.contains(r#""initial":{"type":"string","minLength":1,"maxLength":1}"#));
+        assert!(
+            input.contains(r#""tags":{"type":"array","items":{"type":"string"},"maxItems":3}"#)
+        );
+        assert!(input.contains(
+            r#""code":{"type":"string","pattern":"^(?:a.*)$","allOf":[{"pattern":"^(?:.*z)$"}]}"#
+        ));
+    }
+
+    #[test]
+    fn collects_nested_helpers_under_defs() {
+        let input = schema(
+            r#"
+            MyRepo;
+            function f {
+                input: {
+                    address: { zip: String, geo: Option<{ lat: f64 }> },
+                    others: Vec<{ name: String }>,
+                }
+                output: None
+            }
+            "#,
+            Direction::Deserialize,
+        );
+
+        assert!(input.starts_with(
+            r#"{"$schema":"https://json-schema.org/draft/2020-12/schema","title":"Test","#
+        ));
+        assert!(input.contains(r##""address":{"$ref":"#/$defs/F_Address"}"##));
+        assert!(input.contains(r##""items":{"$ref":"#/$defs/F_Others"}"##));
+        // Helpers referenced by helpers are collected transitively.
+        assert!(
+            input.contains(
+                r##""geo":{"anyOf":[{"$ref":"#/$defs/F_Address_Geo"},{"type":"null"}]}"##
+            )
+        );
+        assert!(input.contains(r#""$defs":{"F_Address":{"type":"object","#));
+        assert!(input.contains(r#""F_Others":{"type":"object","#));
+        assert!(input.contains(
+            r#""F_Address_Geo":{"type":"object","properties":{"lat":{"type":"number"}}"#
+        ));
+    }
+
+    #[test]
+    fn parses_schemas_once() {
+        let items = testing::items(generate(
+            &test_model("#[json_schema] MyRepo; function f { input: { id: String } output: None }")
+                .unwrap(),
+        ));
+
+        let schemas = testing::fn_of(&items, None, "my_repo_schemas");
+        assert_eq!(
+            schemas.output(),
+            "-> & 'static :: std :: collections :: BTreeMap < & 'static str , :: serde_json :: Value >"
+        );
+        // The map is built in a `LazyLock` static, which the function borrows.
+        let Some(syn::Item::Fn(item)) = items
+            .iter()
+            .find(|item| matches!(item, syn::Item::Fn(f) if f.sig.ident == "my_repo_schemas"))
+        else {
+            panic!("expected `my_repo_schemas`");
+        };
+        let [
+            syn::Stmt::Item(syn::Item::Static(cache)),
+            syn::Stmt::Expr(returned, None),
+        ] = item.block.stmts.as_slice()
+        else {
+            panic!("expected a static and the returned reference");
+        };
+        assert_eq!(cache.ident, "__SCHEMAS");
+        assert!(
+            cache
+                .ty
+                .to_token_stream()
+                .to_string()
+                .starts_with(":: std :: sync :: LazyLock <")
+        );
+        assert_eq!(returned.to_token_stream().to_string(), "& __SCHEMAS");
+    }
+}
@@ -4,23 +4,222 @@ use syn::{GenericArgument, Lifetime, PathArguments, Type, TypeParamBound};
 
 use crate::{
     helpers::to_pascal_case,
-    repository::model::{ConfigModel, FieldSpec, ValueModel},
+    repository::model::{ConfigModel, FieldSpec, FunctionModel, ValueModel},
 };
 
 pub fn generate(model: &ConfigModel) -> TokenStream {
     let helper_structs = generate_helper_structs(model);
     let (io_structs, trait_methods) = generate_functions_and_trait_methods(model);
     let repo_name = &model.repository_name;
+    let supertraits = &model.supertraits;
+
+    // Error returned by handlers when a call to a function with `timeout_ms`
+    // does not complete in time.
+    let timeout_error = if model.functions.iter().any(|f| f.timeout_ms.is_some()) {
+        let error_ident = timeout_error_ident_for(repo_name);
+        quote! {
+            ::fractic_server_error::define_internal_error!(
+                #error_ident,
+                "Call to {function} timed out after {timeout_ms}ms.",
+                { function: &str, timeout_ms: u64 }
+            );
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    // Error returned by handlers when an input fails its `#[validate(...)]`
+    // rules, listing every failing field. `validate()` itself returns the
+    // structured per-field list, which converts into this error.
+    let validation_error = if has_validated_structs(model) {
+        let error_ident = validation_error_ident_for(repo_name);
+        let field_error_ident = field_error_ident_for(repo_name);
+        let failure_ident = validation_failure_ident_for(repo_name);
+        quote! {
+            ::fractic_server_error::define_client_error!(
+                #error_ident,
+                "Invalid input: {details}.",
+                { details: &str }
+            );
+
+            /// Failed `#[validate(...)]` rule of a single field.
+            #[derive(Debug, Clone, PartialEq, Eq)]
+            pub struct #field_error_ident {
+                /// Path of the field within the validated value, such as
+                /// `address.zip` or `items[2].name`.
+                pub field: ::std::string::String,
+                pub message: ::std::string::String,
+            }
+
+            /// Every failed `#[validate(...)]` rule of a value, in field order.
+            #[derive(Debug, Clone, PartialEq, Eq)]
+            pub struct #failure_ident {
+                pub errors: ::std::vec::Vec<#field_error_ident>,
+            }
+
+            impl ::std::fmt::Display for #failure_ident {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    for (__index, __error) in self.errors.iter().enumerate() {
+                        if __index > 0 {
+                            f.write_str("; ")?;
+                        }
+                        write!(f, "{}: {}", __error.field, __error.message)?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+
+            impl ::std::convert::From<#failure_ident> for ::fractic_server_error::ServerError {
+                fn from(failure: #failure_ident) -> Self {
+                    #error_ident::new(&failure.to_string())
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    let repository_trait = if model.native_async {
+        let dyn_adapter = generate_dyn_adapter(model);
+        quote! {
+            pub trait #repo_name: #(#supertraits +)* ::std::marker::Send + ::std::marker::Sync {
+                #(#trait_methods)*
+            }
+
+            #dyn_adapter
+        }
+    } else {
+        quote! {
+            #[::async_trait::async_trait]
+            pub trait #repo_name: #(#supertraits +)* ::std::marker::Send + ::std::marker::Sync {
+                #(#trait_methods)*
+            }
+        }
+    };
 
     quote! {
+        #timeout_error
+        #validation_error
         #helper_structs
         #io_structs
+        #repository_trait
+    }
+}
 
-        #[::async_trait::async_trait]
-        pub trait #repo_name: ::std::marker::Send + ::std::marker::Sync {
-            #(#trait_methods)*
+/// Name of the `dyn`-compatible counterpart of a `native_async` repository.
+pub(crate) fn dyn_adapter_ident_for(repo_name: &Ident) -> Ident {
+    format_ident!("{}Dyn", repo_name)
+}
+
+/// `{Repo}Dyn`: the same methods as a `native_async` repository trait, with
+/// async methods returning boxed futures so that the trait can be used as
+/// `dyn {Repo}Dyn` (as the generated handlers do). Implemented for every
+/// implementor of the repository trait. Base repositories must be
+/// `native_async` too, since the adapter extends their adapters.
+fn generate_dyn_adapter(model: &ConfigModel) -> TokenStream {
+    let repo_name = &model.repository_name;
+    let adapter_ident = dyn_adapter_ident_for(repo_name);
+    let supertraits = model.supertraits.iter().map(|supertrait| {
+        let mut supertrait = supertrait.clone();
+        if let Some(last) = supertrait.segments.last_mut() {
+            last.ident = dyn_adapter_ident_for(&last.ident);
+        }
+        supertrait
+    });
+
+    let mut adapter_methods = Vec::new();
+    let mut forwarding_methods = Vec::new();
+    for f in &model.functions {
+        let fn_ident = &f.name;
+        let output_ident = format_ident!("{}Output", to_pascal_case(&fn_ident.to_string()));
+        let output_ts = build_method_output(&f.output, &output_ident, f.is_direct);
+        let deprecated_attr = deprecated_attr(f);
+        let args = method_arg_idents(&f.input);
+        if f.is_blocking {
+            let (inputs_ts, needs_a_lifetime) = build_method_inputs(&f.input);
+            let generics = if needs_a_lifetime {
+                quote! { <'a> }
+            } else {
+                quote! {}
+            };
+            let signature = quote! { fn #fn_ident #generics (&self #inputs_ts) -> #output_ts };
+            adapter_methods.push(quote! {
+                #deprecated_attr
+                #signature;
+            });
+            forwarding_methods.push(quote! {
+                #signature {
+                    #repo_name::#fn_ident(self, #(#args),*)
+                }
+            });
+        } else {
+            // Every borrow, including `self`, must outlive the boxed future.
+            let inputs_ts = build_boxed_method_inputs(&f.input);
+            let signature = quote! {
+                fn #fn_ident<'a>(&'a self #inputs_ts) -> ::std::pin::Pin<::std::boxed::Box<
+                    dyn ::std::future::Future<Output = #output_ts> + ::std::marker::Send + 'a
+                >>
+            };
+            adapter_methods.push(quote! {
+                #deprecated_attr
+                #signature;
+            });
+            forwarding_methods.push(quote! {
+                #signature {
+                    ::std::boxed::Box::pin(#repo_name::#fn_ident(self, #(#args),*))
+                }
+            });
         }
     }
+
+    quote! {
+        /// `dyn`-compatible form of the repository trait, implemented for
+        /// every implementor of it.
+        pub trait #adapter_ident: #(#supertraits +)* ::std::marker::Send + ::std::marker::Sync {
+            #(#adapter_methods)*
+        }
+
+        #[allow(deprecated)]
+        impl<T: #repo_name + ?::std::marker::Sized> #adapter_ident for T {
+            #(#forwarding_methods)*
+        }
+    }
+}
+
+fn deprecated_attr(f: &FunctionModel) -> TokenStream {
+    if !f.is_deprecated {
+        return quote! {};
+    }
+    match &f.deprecated_note {
+        Some(note) => quote! { #[deprecated(note = #note)] },
+        None => quote! { #[deprecated] },
+    }
+}
+
+pub(crate) fn timeout_error_ident_for(repo_name: &Ident) -> Ident {
+    format_ident!("{}Timeout", repo_name)
+}
+
+pub(crate) fn validation_error_ident_for(repo_name: &Ident) -> Ident {
+    format_ident!("{}ValidationError", repo_name)
+}
+
+pub(crate) fn field_error_ident_for(repo_name: &Ident) -> Ident {
+    format_ident!("{}FieldError", repo_name)
+}
+
+pub(crate) fn validation_failure_ident_for(repo_name: &Ident) -> Ident {
+    format_ident!("{}ValidationFailure", repo_name)
+}
+
+/// Whether any generated struct gets a `validate()` impl (every input struct
+/// and helper struct does).
+pub(crate) fn has_validated_structs(model: &ConfigModel) -> bool {
+    !model.helper_structs.is_empty()
+        || model
+            .functions
+            .iter()
+            .any(|f| matches!(f.input, ValueModel::Struct { .. }))
 }
 
 fn generate_helper_structs(model: &ConfigModel) -> TokenStream {
@@ -49,11 +248,22 @@ fn generate_functions_and_trait_methods(model: &ConfigModel) -> (TokenStream, Ve
         let input_ident: Ident = format_ident!("{}Input", base_pascal);
         let output_ident: Ident = format_ident!("{}Output", base_pascal);
 
+        // The HTTP client sends inputs and receives outputs, so these also
+        // need the opposite serde direction.
+        let (maybe_serialize, maybe_deserialize) = if model.http_client {
+            (
+                quote! { , ::serde::Serialize },
+                quote! { , ::serde::Deserialize },
+            )
+        } else {
+            (quote! {}, quote! {})
+        };
+
         // Define input struct if needed.
         if let ValueModel::Struct { fields } = &f.input {
             let fields_ts = generate_struct_fields(fields);
             io_structs_accum.push(quote! {
-                #[derive(::core::clone::Clone, ::core::fmt::Debug, ::serde::Deserialize)]
+                #[derive(::core::clone::Clone, ::core::fmt::Debug, ::serde::Deserialize #maybe_serialize)]
                 pub struct #input_ident {
                     #(#fields_ts),*
                 }
@@ -64,7 +274,7 @@ fn generate_functions_and_trait_methods(model: &ConfigModel) -> (TokenStream, Ve
         if let ValueModel::Struct { fields } = &f.output {
             let fields_ts = generate_struct_fields(fields);
             io_structs_accum.push(quote! {
-                #[derive(::core::clone::Clone, ::core::fmt::Debug, ::serde::Serialize)]
+                #[derive(::core::clone::Clone, ::core::fmt::Debug, ::serde::Serialize #maybe_deserialize)]
                 pub struct #output_ident {
                     #(#fields_ts),*
                 }
@@ -83,20 +293,19 @@ fn generate_functions_and_trait_methods(model: &ConfigModel) -> (TokenStream, Ve
         } else {
             quote! {}
         };
-        let maybe_deprecated_attr = if f.is_deprecated {
-            if let Some(note) = &f.deprecated_note {
-                quote! { #[deprecated(note = #note)] }
-            } else {
-                quote! { #[deprecated] }
-            }
-        } else {
-            quote! {}
-        };
+        let maybe_deprecated_attr = deprecated_attr(f);
         if f.is_blocking {
             trait_methods.push(quote! {
                 #maybe_deprecated_attr
                 fn #fn_ident #maybe_generics (&self #inputs_ts) -> #output_ts;
             });
+        } else if model.native_async {
+            // Implementations may still be written as `async fn`.
+            trait_methods.push(quote! {
+                #maybe_deprecated_attr
+                fn #fn_ident #maybe_generics (&self #inputs_ts)
+                    -> impl ::std::future::Future<Output = #output_ts> + ::std::marker::Send;
+            });
         } else {
             trait_methods.push(quote! {
                 #maybe_deprecated_attr
@@ -111,7 +320,7 @@ fn generate_functions_and_trait_methods(model: &ConfigModel) -> (TokenStream, Ve
 /// Build the trait method inputs and report whether the method must be generic
 /// over a lifetime `'a` (i.e., any argument contained an explicit `'a`/`'_'` that
 /// was normalized to `'a`).
-fn build_method_inputs(input: &ValueModel) -> (TokenStream, bool) {
+pub(crate) fn build_method_inputs(input: &ValueModel) -> (TokenStream, bool) {
     match input {
         ValueModel::None => (quote! {}, false),
         ValueModel::SingleType { ty_tokens } => {
@@ -133,7 +342,44 @@ fn build_method_inputs(input: &ValueModel) -> (TokenStream, bool) {
     }
 }
 
-fn build_method_output(
+/// Like `build_method_inputs`, but with every reference (including those
+/// without an explicit lifetime) borrowed for `'a`.
+fn build_boxed_method_inputs(input: &ValueModel) -> TokenStream {
+    let boxed = |ty_tokens: &TokenStream| match syn::parse2::<Type>(ty_tokens.clone()) {
+        Ok(mut ty) => {
+            let mut _unused = false;
+            rewrite_lifetimes_in_type(&mut ty, LifetimeTarget::BoxedMethodArg, &mut _unused);
+            quote! { #ty }
+        }
+        Err(_) => ty_tokens.clone(),
+    };
+    match input {
+        ValueModel::None => quote! {},
+        ValueModel::SingleType { ty_tokens } => {
+            let ty = boxed(ty_tokens);
+            quote! { , input: #ty }
+        }
+        ValueModel::Struct { fields } => {
+            let params = fields.iter().map(|f| {
+                let name = &f.name;
+                let ty = boxed(&f.ty_tokens);
+                quote! { #name: #ty }
+            });
+            quote! { , #(#params),* }
+        }
+    }
+}
+
+/// Names of the trait method parameters, as declared by `build_method_inputs`.
+fn method_arg_idents(input: &ValueModel) -> Vec<Ident> {
+    match input {
+        ValueModel::None => Vec::new(),
+        ValueModel::SingleType { .. } => vec![format_ident!("input")],
+        ValueModel::Struct { fields } => fields.iter().map(|f| f.name.clone()).collect(),
+    }
+}
+
+pub(crate) fn build_method_output(
     output: &ValueModel,
     output_struct_ident: &Ident,
     is_direct: bool,
@@ -229,6 +475,9 @@ enum LifetimeTarget {
     MethodArg,
     /// Serde struct field position: rewrite explicit `'a`/`'_' to `'static`.
     SerdeStructField,
+    /// Argument of a method returning a boxed future: as `MethodArg`, but
+    /// references without a lifetime also get `'a`.
+    BoxedMethodArg,
 }
 
 /// Single traversal that rewrites lifetimes across a `syn::Type` according to
@@ -248,6 +497,14 @@ fn rewrite_lifetimes_in_type(ty: &mut Type, target: LifetimeTarget, needs_a: &mu
                     }
                     None => {}
                 },
+                LifetimeTarget::BoxedMethodArg => match &mut r.lifetime {
+                    Some(l) => {
+                        if is_lifetime_a_or_underscore(l) {
+                            *l = lifetime_named("'a");
+                        }
+                    }
+                    None => r.lifetime = Some(lifetime_named("'a")),
+                },
                 LifetimeTarget::SerdeStructField => match &mut r.lifetime {
                     Some(l) => {
                         if is_lifetime_a_or_underscore(l) {
@@ -287,7 +544,8 @@ fn rewrite_lifetimes_in_type(ty: &mut Type, target: LifetimeTarget, needs_a: &mu
                             GenericArgument::Lifetime(l) => {
                                 if is_lifetime_a_or_underscore(l) {
                                     match target {
-                                        LifetimeTarget::MethodArg => {
+                                        LifetimeTarget::MethodArg
+                                        | LifetimeTarget::BoxedMethodArg => {
                                             *l = lifetime_named("'a");
                                             *needs_a = true;
                                         }
@@ -309,7 +567,7 @@ fn rewrite_lifetimes_in_type(ty: &mut Type, target: LifetimeTarget, needs_a: &mu
                 if let TypeParamBound::Lifetime(l) = b {
                     if is_lifetime_a_or_underscore(l) {
                         match target {
-                            LifetimeTarget::MethodArg => {
+                            LifetimeTarget::MethodArg | LifetimeTarget::BoxedMethodArg => {
                                 *l = lifetime_named("'a");
                                 *needs_a = true;
                             }
@@ -326,7 +584,7 @@ fn rewrite_lifetimes_in_type(ty: &mut Type, target: LifetimeTarget, needs_a: &mu
                 if let TypeParamBound::Lifetime(l) = b {
                     if is_lifetime_a_or_underscore(l) {
                         match target {
-                            LifetimeTarget::MethodArg => {
+                            LifetimeTarget::MethodArg | LifetimeTarget::BoxedMethodArg => {
                                 *l = lifetime_named("'a");
                                 *needs_a = true;
                             }
@@ -341,3 +599,114 @@ fn rewrite_lifetimes_in_type(ty: &mut Type, target: LifetimeTarget, needs_a: &mu
         _ => {}
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use quote::{ToTokens as _, quote};
+
+    use super::generate;
+    use crate::{
+        repository::test_model,
+        testing::{self, GeneratedFn},
+    };
+
+    fn generated(dsl: &str) -> Vec<syn::Item> {
+        testing::items(generate(&test_model(dsl).unwrap()))
+    }
+
+    /// The `{Repo}Dyn` method declaration and its blanket implementation.
+    fn dyn_method(items: &[syn::Item], name: &str) -> (GeneratedFn, GeneratedFn) {
+        let (declared, implemented): (Vec<_>, Vec<_>) = testing::fns(items, name)
+            .into_iter()
+            .filter(|f| f.owner.as_deref() == Some("MyRepoDyn"))
+            .partition(|f| f.body.is_none());
+        let [declared] = <[_; 1]>::try_from(declared).ok().unwrap();
+        let [implemented] = <[_; 1]>::try_from(implemented).ok().unwrap();
+        (declared, implemented)
+    }
+
+    #[test]
+    fn boxes_borrowed_arguments_in_dyn_adapter() {
+        let items = generated(
+            r#"
+            #[native_async]
+            MyRepo: BaseRepo;
+            function find_users {
+                input: { name: &str, ids: &[u64], tag: Option<&'a str> }
+                output: Vec<User>
+            }
+            function count {
+                input: &str
+                output: u64
+            }
+            blocking_direct flush {
+                input: &[u8]
+                output: None
+            }
+            "#,
+        );
+
+        let adapter = items
+            .iter()
+            .find_map(|item| match item {
+                syn::Item::Trait(t) if t.ident == "MyRepoDyn" => Some(t),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(
+            adapter.supertraits.to_token_stream().to_string(),
+            quote! { BaseRepoDyn + ::std::marker::Send + ::std::marker::Sync }.to_string()
+        );
+
+        let (find_users, find_users_impl) = dyn_method(&items, "find_users");
+        let expected: syn::Signature = syn::parse_quote! {
+            fn find_users<'a>(&'a self, name: &'a str, ids: &'a [u64], tag: Option<&'a str>)
+                -> ::std::pin::Pin<::std::boxed::Box<dyn ::std::future::Future<Output =
+                    ::std::result::Result<Vec<User>, ::fractic_server_error::ServerError>>
+                    + ::std::marker::Send + 'a>>
+        };
+        assert_eq!(find_users.sig, expected);
+        assert!(find_users_impl.body_contains(
+            ":: std :: boxed :: Box :: pin (MyRepo :: find_users (self , name , ids , tag))"
+        ));
+
+        let (count, _) = dyn_method(&items, "count");
+        assert_eq!(count.param_names(), ["input"]);
+        assert!(count.output().starts_with("-> :: std :: pin :: Pin <"));
+
+        // Blocking methods are already `dyn`-compatible and keep their own
+        // signature.
+        let (flush, flush_impl) = dyn_method(&items, "flush");
+        assert!(flush.sig.generics.params.is_empty());
+        assert_eq!(flush.output(), "-> ()");
+        assert!(flush_impl.body_contains("MyRepo :: flush (self , input)"));
+
+        assert!(items.iter().any(|item| matches!(
+            item,
+            syn::Item::Impl(i) if i.trait_.as_ref().is_some_and(|(_, path, _)| path.is_ident("MyRepoDyn"))
+                && i.generics.to_token_stream().to_string()
+                    == quote! { <T: MyRepo + ?::std::marker::Sized> }.to_string()
+        )));
+    }
+
+    #[test]
+    fn omits_dyn_adapter_without_native_async() {
+        let items = generated("MyRepo; function count { input: &str output: u64 }");
+
+        assert!(
+            !testing::type_names(&items)
+                .iter()
+                .any(|name| name.contains("Dyn"))
+        );
+        let repository = items
+            .iter()
+            .find_map(|item| match item {
+                syn::Item::Trait(t) if t.ident == "MyRepo" => Some(t),
+                _ => None,
+            })
+            .unwrap();
+        assert!(repository.attrs.iter().any(
+            |attr| attr.path().to_token_stream().to_string() == ":: async_trait :: async_trait"
+        ));
+    }
+}
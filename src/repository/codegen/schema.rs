@@ -0,0 +1,92 @@
+use proc_macro2::TokenStream;
+use quote::ToTokens as _;
+
+use crate::{
+    repository::model::{ConfigModel, FieldSpec, FunctionModel, ValueModel},
+    schema::{Json, schema_const, type_string},
+};
+
+/// `{REPO}_SCHEMA`, if the repository declares `#[schema]`. Field types refer
+/// to the generated helper structs by name, so these are described too.
+/// Functions inherited from base repositories are described in their own
+/// schemas.
+pub fn generate(model: &ConfigModel) -> TokenStream {
+    if !model.schema {
+        return TokenStream::new();
+    }
+
+    let supertraits = model
+        .supertraits
+        .iter()
+        .map(|path| Json::string(type_string(&path.to_token_stream())))
+        .collect();
+    let functions = model.functions.iter().map(function_json).collect();
+    let helpers = model
+        .helper_structs
+        .iter()
+        .map(|helper| {
+            Json::Object(vec![
+                ("name", Json::string(&helper.name)),
+                ("fields", fields_json(&helper.fields)),
+            ])
+        })
+        .collect();
+
+    let document = Json::Object(vec![
+        ("repository", Json::string(&model.repository_name)),
+        ("supertraits", Json::Array(supertraits)),
+        ("functions", Json::Array(functions)),
+        ("helpers", Json::Array(helpers)),
+    ]);
+    schema_const(&model.repository_name, document, "repository_scaffolding")
+}
+
+fn function_json(f: &FunctionModel) -> Json {
+    let kind = match (f.is_blocking, f.is_direct) {
+        (false, false) => "function",
+        (false, true) => "function_direct",
+        (true, false) => "blocking",
+        (true, true) => "blocking_direct",
+    };
+    Json::Object(vec![
+        ("name", Json::string(&f.name)),
+        ("kind", Json::string(kind)),
+        ("direct", Json::Bool(f.is_direct)),
+        ("blocking", Json::Bool(f.is_blocking)),
+        ("deprecated", Json::Bool(f.is_deprecated)),
+        (
+            "deprecated_note",
+            f.deprecated_note
+                .as_ref()
+                .map_or(Json::Null, |note| Json::string(note.value())),
+        ),
+        ("input", value_json(&f.input)),
+        ("output", value_json(&f.output)),
+    ])
+}
+
+/// `null` for no value, `{"type": ..}` for a single type, or `{"fields": ..}`
+/// for an inline struct.
+fn value_json(value: &ValueModel) -> Json {
+    match value {
+        ValueModel::None => Json::Null,
+        ValueModel::SingleType { ty_tokens } => {
+            Json::Object(vec![("type", Json::string(type_string(ty_tokens)))])
+        }
+        ValueModel::Struct { fields } => Json::Object(vec![("fields", fields_json(fields))]),
+    }
+}
+
+fn fields_json(fields: &[FieldSpec]) -> Json {
+    Json::Array(
+        fields
+            .iter()
+            .map(|field| {
+                Json::Object(vec![
+                    ("name", Json::string(&field.name)),
+                    ("type", Json::string(type_string(&field.ty_tokens))),
+                ])
+            })
+            .collect(),
+    )
+}
@@ -3,19 +3,37 @@ use quote::quote;
 
 mod ast;
 mod codegen {
+    pub mod grpc;
     pub mod handlers;
+    pub mod http_client;
+    pub mod json_schema;
     pub mod repository;
+    pub mod schema;
 }
 mod model;
 
 pub use ast::ConfigAst;
 pub use model::ConfigModel;
 
+/// Model of a DSL snippet, for the codegen tests.
+#[cfg(test)]
+pub(crate) fn test_model(dsl: &str) -> syn::Result<ConfigModel> {
+    ConfigModel::try_from(syn::parse_str::<ConfigAst>(dsl)?)
+}
+
 pub fn generate(model: &ConfigModel) -> TokenStream {
     let repository_trait = codegen::repository::generate(model);
     let handlers_macro = codegen::handlers::generate(model);
+    let http_client = codegen::http_client::generate(model);
+    let grpc = codegen::grpc::generate(model);
+    let schema = codegen::schema::generate(model);
+    let json_schema = codegen::json_schema::generate(model);
     quote! {
         #repository_trait
         #handlers_macro
+        #http_client
+        #grpc
+        #schema
+        #json_schema
     }
 }
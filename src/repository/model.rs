@@ -1,11 +1,41 @@
 use proc_macro2::{Delimiter, Group, Ident, Span, TokenStream as TokenStream2, TokenTree};
-use syn::{Attribute, Error, LitStr, Result};
+use quote::quote;
+use syn::{
+    Attribute, Error, GenericArgument, Lit, LitInt, LitStr, Path, PathArguments, Result, Token,
+    Type,
+};
 
 use crate::{helpers::to_pascal_case, repository::ast};
 
 #[derive(Debug)]
 pub struct ConfigModel {
     pub repository_name: Ident,
+    /// Whether to emit a `{REPO}_SCHEMA` JSON description of the functions.
+    pub schema: bool,
+    /// Whether trait methods are native `async` (returning `impl Future`)
+    /// rather than boxed by `async_trait`. Handlers then go through the
+    /// generated `{Repo}Dyn` adapter.
+    pub native_async: bool,
+    /// Whether to emit a `{Repo}HttpClient` implementing the trait by calling
+    /// the functions' handlers over HTTP. The generated client refers to
+    /// `::reqwest`, `::serde` and `::serde_json`, so the consuming crate must
+    /// then depend on those itself.
+    pub http_client: bool,
+    /// Whether to emit a `{REPO}_PROTO` gRPC service definition and a
+    /// `{Repo}GrpcAdapter` serving it through the `tonic` server generated
+    /// from that definition.
+    pub grpc: bool,
+    /// Whether to emit `{repo}_schemas()`, JSON Schemas of the generated
+    /// input, output and helper structs.
+    pub json_schema: bool,
+    /// Whether to emit `{repo}_batch_handler`, running a list of calls in one
+    /// request. Calls are deserialized from their inputs and results
+    /// serialized from their outputs, so every input must implement
+    /// `Deserialize` and every output `Serialize`.
+    pub batch: bool,
+    /// Repositories (generated by other `repository_scaffolding!`
+    /// invocations) whose functions this one inherits.
+    pub supertraits: Vec<Path>,
     pub helper_structs: Vec<HelperStruct>,
     pub functions: Vec<FunctionModel>,
 }
@@ -19,6 +49,17 @@ pub struct FunctionModel {
     pub is_direct: bool,
     pub is_deprecated: bool,
     pub deprecated_note: Option<LitStr>,
+    /// Number of times a failed call is retried (with exponential backoff)
+    /// before giving up, at most `MAX_RETRY`. Only allowed on idempotent
+    /// functions. Async handlers wait between attempts with `tokio`, so the
+    /// consuming crate must then depend on `tokio` (with its `time` feature).
+    pub retry: Option<u32>,
+    /// Limit on the duration of each call attempt, enforced with `tokio`'s
+    /// `time` feature (see `retry`).
+    pub timeout_ms: Option<u64>,
+    /// Whether batch handlers may run this call alongside other
+    /// `concurrent` calls rather than strictly in order.
+    pub concurrent: bool,
 }
 
 #[derive(Debug)]
@@ -44,9 +85,34 @@ pub struct HelperStruct {
 
 #[derive(Debug, Clone)]
 pub struct FieldSpec {
+    /// Attributes to emit on the field (`#[validate(...)]` attributes are
+    /// consumed into `validations` instead).
     pub attrs: Vec<Attribute>,
     pub name: Ident,
     pub ty_tokens: TokenStream2,
+    pub validations: Vec<Validation>,
+}
+
+/// Rule declared on an inline struct field with `#[validate(...)]`, checked by
+/// the generated `validate()` before the repository is called.
+#[derive(Debug, Clone)]
+pub enum Validation {
+    /// `length(min = .., max = ..)`, checked against the value's `len()`, or
+    /// its number of characters for strings.
+    Length {
+        min: Option<LitInt>,
+        max: Option<LitInt>,
+    },
+    /// `range(min = .., max = ..)`, with inclusive numeric bounds, cast to the
+    /// field's type (so they must fit primitive integer fields).
+    Range {
+        min: Option<TokenStream2>,
+        max: Option<TokenStream2>,
+    },
+    /// `regex = "..."`, on string fields, which the whole value must match. The
+    /// pattern is checked when the macro expands; the generated check uses
+    /// `::regex`, so the consuming crate must then depend on `regex` itself.
+    Regex(LitStr),
 }
 
 impl TryFrom<ast::ConfigAst> for ConfigModel {
@@ -59,9 +125,22 @@ impl TryFrom<ast::ConfigAst> for ConfigModel {
             .into_iter()
             .map(|f| build_function_model(&value.repository_name, f, &mut helper_structs))
             .collect::<Result<Vec<_>>>()?;
+        if value.options.http_client {
+            functions.iter().try_for_each(check_http_client_support)?;
+        }
+        if value.options.batch {
+            functions.iter().try_for_each(check_batch_support)?;
+        }
 
         Ok(Self {
             repository_name: value.repository_name,
+            schema: value.options.schema,
+            native_async: value.options.native_async,
+            http_client: value.options.http_client,
+            grpc: value.options.grpc,
+            json_schema: value.options.json_schema,
+            batch: value.options.batch,
+            supertraits: value.supertraits,
             helper_structs,
             functions,
         })
@@ -74,8 +153,19 @@ fn build_function_model(
     helper_structs: &mut Vec<HelperStruct>,
 ) -> Result<FunctionModel> {
     let fn_name = func.name.clone();
+    let first_input_helper = helper_structs.len();
     let input = build_value_model(&fn_name, None, func.input, helper_structs)?;
+    let first_output_helper = helper_structs.len();
     let output = build_value_model(&fn_name, None, func.output, helper_structs)?;
+
+    // Validation rules only make sense where the handler can reject the call
+    // before it is made.
+    if let Some(field) = first_validated_field(&output, &helper_structs[first_output_helper..]) {
+        return Err(Error::new(
+            field.name.span(),
+            "`#[validate(...)]` is only supported on input fields",
+        ));
+    }
     let (is_blocking, is_direct) = match func.kind {
         ast::FunctionKindAst::Async => (false, false),
         ast::FunctionKindAst::AsyncDirect => (false, true),
@@ -87,6 +177,67 @@ fn build_function_model(
         Some(ast::DeprecatedAst::Note(lit)) => (true, Some(lit)),
         None => (false, None),
     };
+
+    // Call policies.
+    let retry = func
+        .retry
+        .as_ref()
+        .map(|lit| parse_positive::<u32>(lit, "retry"))
+        .transpose()?;
+    let timeout_ms = func
+        .timeout_ms
+        .as_ref()
+        .map(|lit| parse_positive::<u64>(lit, "timeout_ms"))
+        .transpose()?;
+    if let (Some(lit), Some(retry)) = (&func.retry, retry)
+        && retry > MAX_RETRY
+    {
+        return Err(Error::new(
+            lit.span(),
+            format!("`retry` must be at most {}", MAX_RETRY),
+        ));
+    }
+    if let (Some(lit), false) = (&func.retry, func.idempotent) {
+        return Err(Error::new(
+            lit.span(),
+            "`retry` is only allowed on functions marked `idempotent`, since a failed call \
+             may still have taken effect",
+        ));
+    }
+    if let (Some(lit), true) = (func.retry.as_ref().or(func.timeout_ms.as_ref()), is_direct) {
+        return Err(Error::new(
+            lit.span(),
+            "`retry` and `timeout_ms` are not supported on direct functions, which cannot \
+             report errors",
+        ));
+    }
+    if let (Some(field), true) = (
+        first_validated_field(
+            &input,
+            &helper_structs[first_input_helper..first_output_helper],
+        ),
+        is_direct,
+    ) {
+        return Err(Error::new(
+            field.name.span(),
+            "`#[validate(...)]` is not supported on direct functions, which cannot report errors",
+        ));
+    }
+    if func.concurrent && is_blocking {
+        return Err(Error::new(
+            fn_name.span(),
+            "`concurrent` is only supported on async functions; blocking calls would hold up \
+             the calls running alongside them",
+        ));
+    }
+    if let (Some(lit), true) = (&func.timeout_ms, is_blocking) {
+        return Err(Error::new(
+            lit.span(),
+            "`timeout_ms` is only supported on async functions; blocking calls cannot be \
+             interrupted",
+        ));
+    }
+
     Ok(FunctionModel {
         name: fn_name,
         input,
@@ -95,9 +246,113 @@ fn build_function_model(
         is_direct,
         is_deprecated,
         deprecated_note,
+        retry,
+        timeout_ms,
+        concurrent: func.concurrent,
     })
 }
 
+/// The HTTP client reports failed requests as errors and builds each request
+/// from an owned `*Input` struct, which rules out direct and blocking functions
+/// and input fields that borrow for a named lifetime.
+fn check_http_client_support(f: &FunctionModel) -> Result<()> {
+    if f.is_blocking || f.is_direct {
+        return Err(Error::new(
+            f.name.span(),
+            "`#[http_client]` only supports `function`s; direct functions cannot report failed \
+             requests, and blocking functions cannot await them",
+        ));
+    }
+    if let ValueModel::Struct { fields } = &f.input
+        && let Some(field) = fields
+            .iter()
+            .find(|field| has_named_lifetime(&field.ty_tokens))
+    {
+        return Err(Error::new(
+            field.name.span(),
+            "`#[http_client]` does not support input fields borrowing for a lifetime, since \
+             the request is built from an owned input struct",
+        ));
+    }
+    Ok(())
+}
+
+/// Batch calls own their input, lending it to the handler. A single input
+/// behind a top-level reference is owned through `ToOwned`; other borrows
+/// (ex. `Option<&'a str>`) cannot be owned generically.
+fn check_batch_support(f: &FunctionModel) -> Result<()> {
+    let ValueModel::SingleType { ty_tokens } = &f.input else {
+        return Ok(());
+    };
+    let owned = match syn::parse2::<Type>(ty_tokens.clone()) {
+        Ok(Type::Reference(r)) => {
+            let elem = r.elem;
+            quote! { #elem }
+        }
+        _ => ty_tokens.clone(),
+    };
+    if has_named_lifetime(&owned) {
+        return Err(Error::new_spanned(
+            ty_tokens,
+            "`#[batch]` does not support inputs borrowing for a lifetime below the top-level \
+             reference, since batch calls own their input; use an inline struct input instead",
+        ));
+    }
+    Ok(())
+}
+
+/// Whether the type tokens contain a lifetime other than `'static`.
+fn has_named_lifetime(tokens: &TokenStream2) -> bool {
+    let mut iter = tokens.clone().into_iter().peekable();
+    while let Some(tt) = iter.next() {
+        match tt {
+            TokenTree::Punct(p) if p.as_char() == '\'' => {
+                if matches!(iter.peek(), Some(TokenTree::Ident(ident)) if ident != "static") {
+                    return true;
+                }
+            }
+            TokenTree::Group(g) if has_named_lifetime(&g.stream()) => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Upper bound on `retry`. Delays double with every attempt, so more retries
+/// would only add attempts at the maximum delay.
+pub const MAX_RETRY: u32 = 10;
+
+fn parse_positive<N>(lit: &LitInt, property: &str) -> Result<N>
+where
+    N: std::str::FromStr + Default + PartialEq,
+    N::Err: std::fmt::Display,
+{
+    let value: N = lit.base10_parse()?;
+    if value == N::default() {
+        return Err(Error::new(
+            lit.span(),
+            format!("`{}` must be a positive number", property),
+        ));
+    }
+    Ok(value)
+}
+
+/// First field with validation rules in a value or the helper structs created
+/// for it, if any.
+fn first_validated_field<'a>(
+    value: &'a ValueModel,
+    helpers: &'a [HelperStruct],
+) -> Option<&'a FieldSpec> {
+    let fields = match value {
+        ValueModel::Struct { fields } => fields.as_slice(),
+        _ => &[],
+    };
+    fields
+        .iter()
+        .chain(helpers.iter().flat_map(|helper| helper.fields.iter()))
+        .find(|field| !field.validations.is_empty())
+}
+
 fn build_value_model(
     fn_name: &Ident,
     chain: Option<Vec<Ident>>,
@@ -142,10 +397,25 @@ fn resolve_inline_struct_fields(
             field.ty_tokens.clone(),
             helper_structs,
         )?;
+        let (validate_attrs, attrs): (Vec<_>, Vec<_>) = field
+            .attrs
+            .into_iter()
+            .partition(|attr| attr.path().is_ident("validate"));
+        let validations = validate_attrs
+            .iter()
+            .map(parse_validations)
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        validations
+            .iter()
+            .try_for_each(|validation| check_validation_type(&ty_tokens, validation))?;
         out_fields.push(FieldSpec {
-            attrs: field.attrs,
+            attrs,
             name: field.name,
             ty_tokens,
+            validations,
         });
     }
     Ok(out_fields)
@@ -198,6 +468,187 @@ fn replace_inline_structs_in_tokens(
     Ok(out)
 }
 
+/// Parse the rules of one `#[validate(...)]` attribute.
+fn parse_validations(attr: &Attribute) -> Result<Vec<Validation>> {
+    let mut out = Vec::new();
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("length") {
+            let (mut min, mut max) = (None, None);
+            meta.parse_nested_meta(|bound| {
+                if bound.path.is_ident("min") {
+                    min = Some(bound.value()?.parse::<LitInt>()?);
+                } else if bound.path.is_ident("max") {
+                    max = Some(bound.value()?.parse::<LitInt>()?);
+                } else {
+                    return Err(bound.error("expected `min` or `max`"));
+                }
+                Ok(())
+            })?;
+            if min.is_none() && max.is_none() {
+                return Err(meta.error("`length` requires `min` and/or `max`"));
+            }
+            out.push(Validation::Length { min, max });
+        } else if meta.path.is_ident("range") {
+            let (mut min, mut max) = (None, None);
+            meta.parse_nested_meta(|bound| {
+                // Numeric literal, optionally negated.
+                let value = bound.value()?;
+                let negate = value.peek(Token![-]);
+                if negate {
+                    let _minus: Token![-] = value.parse()?;
+                }
+                let lit: Lit = value.parse()?;
+                if !matches!(lit, Lit::Int(_) | Lit::Float(_)) {
+                    return Err(Error::new(lit.span(), "expected a numeric literal"));
+                }
+                let tokens = if negate {
+                    quote! { -#lit }
+                } else {
+                    quote! { #lit }
+                };
+                if bound.path.is_ident("min") {
+                    min = Some(tokens);
+                } else if bound.path.is_ident("max") {
+                    max = Some(tokens);
+                } else {
+                    return Err(bound.error("expected `min` or `max`"));
+                }
+                Ok(())
+            })?;
+            if min.is_none() && max.is_none() {
+                return Err(meta.error("`range` requires `min` and/or `max`"));
+            }
+            out.push(Validation::Range { min, max });
+        } else if meta.path.is_ident("regex") {
+            let pattern = meta.value()?.parse::<LitStr>()?;
+            if let Err(e) = regex_syntax::Parser::new().parse(&anchored_pattern(&pattern)) {
+                return Err(Error::new(
+                    pattern.span(),
+                    format!("invalid `regex` pattern: {}", e),
+                ));
+            }
+            out.push(Validation::Regex(pattern));
+        } else {
+            return Err(meta.error("unknown validation; expected `length`, `range`, or `regex`"));
+        }
+        Ok(())
+    })?;
+    Ok(out)
+}
+
+/// Reject rules the field's type cannot be checked against: `regex` on
+/// anything but strings, and `range` bounds a primitive integer field cannot
+/// hold (the generated check casts the bounds to the field's type). Bounds of
+/// other types are left for the compiler to check.
+fn check_validation_type(ty_tokens: &TokenStream2, validation: &Validation) -> Result<()> {
+    match validation {
+        Validation::Regex(pattern) if !is_string(ty_tokens) => Err(Error::new(
+            pattern.span(),
+            "`regex` is only supported on `String` and `str` fields",
+        )),
+        Validation::Range { min, max } => {
+            let Some((ty, type_min, type_max)) = integer_bounds(ty_tokens) else {
+                return Ok(());
+            };
+            for bound in [min, max].into_iter().flatten() {
+                let mut tokens = bound.clone().into_iter().peekable();
+                let negative = tokens
+                    .next_if(|tt| matches!(tt, TokenTree::Punct(_)))
+                    .is_some();
+                let Some(TokenTree::Literal(literal)) = tokens.next() else {
+                    continue;
+                };
+                let Lit::Int(int) = Lit::new(literal.clone()) else {
+                    return Err(Error::new(
+                        literal.span(),
+                        format!("`range` bounds of `{}` fields must be integers", ty),
+                    ));
+                };
+                let value = int
+                    .base10_parse::<i128>()
+                    .ok()
+                    .map(|value| if negative { -value } else { value });
+                if !value.is_some_and(|value| (type_min..=type_max).contains(&value)) {
+                    return Err(Error::new(
+                        literal.span(),
+                        format!("`range` bound out of range for `{}`", ty),
+                    ));
+                }
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Whether the value is a `String` or `str` (possibly behind a reference or
+/// `Option`), whose length is counted in characters.
+pub(crate) fn is_string(ty_tokens: &TokenStream2) -> bool {
+    scalar_type_ident(ty_tokens).is_some_and(|ident| ident == "String" || ident == "str")
+}
+
+/// For primitive integer values (possibly behind a reference or `Option`),
+/// the type and its bounds. `u128` is capped at `i128::MAX`, which is enough
+/// for any bound written in the DSL.
+fn integer_bounds(ty_tokens: &TokenStream2) -> Option<(Ident, i128, i128)> {
+    let ty = scalar_type_ident(ty_tokens)?;
+    let (min, max) = match ty.to_string().as_str() {
+        "i8" => (i8::MIN.into(), i8::MAX.into()),
+        "i16" => (i16::MIN.into(), i16::MAX.into()),
+        "i32" => (i32::MIN.into(), i32::MAX.into()),
+        "i64" | "isize" => (i64::MIN.into(), i64::MAX.into()),
+        "i128" => (i128::MIN, i128::MAX),
+        "u8" => (0, u8::MAX.into()),
+        "u16" => (0, u16::MAX.into()),
+        "u32" => (0, u32::MAX.into()),
+        "u64" | "usize" => (0, u64::MAX.into()),
+        "u128" => (0, i128::MAX),
+        _ => return None,
+    };
+    Some((ty, min, max))
+}
+
+/// Last path segment of the value's type, looking through a reference and
+/// `Option`.
+fn scalar_type_ident(ty_tokens: &TokenStream2) -> Option<Ident> {
+    let ty = strip_reference(syn::parse2::<Type>(ty_tokens.clone()).ok()?);
+    let ty = match single_generic_argument(&ty) {
+        Some((container, inner)) if container == "Option" => strip_reference(inner.clone()),
+        _ => ty,
+    };
+    match &ty {
+        Type::Path(p) => p.path.segments.last().map(|segment| segment.ident.clone()),
+        _ => None,
+    }
+}
+
+pub(crate) fn strip_reference(ty: Type) -> Type {
+    match ty {
+        Type::Reference(r) => *r.elem,
+        ty => ty,
+    }
+}
+
+/// Last path segment and its type argument, for types like `Option<T>`.
+pub(crate) fn single_generic_argument(ty: &Type) -> Option<(&Ident, &Type)> {
+    let Type::Path(p) = ty else {
+        return None;
+    };
+    let segment = p.path.segments.last()?;
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(inner) if args.args.len() == 1 => Some((&segment.ident, inner)),
+        _ => None,
+    }
+}
+
+/// `regex` pattern anchored at both ends, so the whole value has to match.
+pub(crate) fn anchored_pattern(pattern: &LitStr) -> String {
+    format!("^(?:{})$", pattern.value())
+}
+
 fn build_helper_ident(fn_name: &Ident, chain: &[Ident]) -> Ident {
     let mut parts: Vec<String> = Vec::with_capacity(1 + chain.len());
     parts.push(to_pascal_case(&fn_name.to_string()));
@@ -207,3 +658,268 @@ fn build_helper_ident(fn_name: &Ident, chain: &[Ident]) -> Ident {
     let name = parts.join("_");
     Ident::new(&name, Span::call_site())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Validation, ValueModel, has_named_lifetime};
+    use crate::repository::test_model as model;
+
+    fn model_err(dsl: &str) -> String {
+        model(dsl).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn carries_call_policies_into_semantic_model() {
+        let model = model(
+            r#"
+            MyRepo;
+            function fetch_user {
+                input: String
+                output: User
+                idempotent
+                retry: 10
+                timeout_ms: 500
+            }
+            blocking compact {
+                input: None
+                output: None
+                idempotent
+                retry: 2
+            }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(model.functions[0].retry, Some(10));
+        assert_eq!(model.functions[0].timeout_ms, Some(500));
+        assert_eq!(model.functions[1].retry, Some(2));
+        assert_eq!(model.functions[1].timeout_ms, None);
+    }
+
+    #[test]
+    fn rejects_invalid_call_policies() {
+        assert!(
+            model_err("MyRepo; function f { input: None output: None retry: 2 }")
+                .contains("only allowed on functions marked `idempotent`")
+        );
+        assert!(
+            model_err("MyRepo; function f { input: None output: None idempotent retry: 0 }")
+                .contains("`retry` must be a positive number")
+        );
+        assert!(
+            model_err("MyRepo; function f { input: None output: None idempotent retry: 11 }")
+                .contains("`retry` must be at most 10")
+        );
+        assert!(
+            model_err("MyRepo; function f { input: None output: None timeout_ms: 0 }")
+                .contains("`timeout_ms` must be a positive number")
+        );
+        assert!(
+            model_err("MyRepo; function_direct f { input: None output: None idempotent retry: 1 }")
+                .contains("not supported on direct functions")
+        );
+        assert!(
+            model_err("MyRepo; blocking_direct f { input: None output: None timeout_ms: 5 }")
+                .contains("not supported on direct functions")
+        );
+        assert!(
+            model_err("MyRepo; blocking f { input: None output: None timeout_ms: 5 }")
+                .contains("`timeout_ms` is only supported on async functions")
+        );
+    }
+
+    #[test]
+    fn parses_validation_rules() {
+        let model = model(
+            r#"
+            MyRepo;
+            function create_user {
+                input: {
+                    #[validate(length(min = 1, max = 64), regex = "[a-z]+")]
+                    name: String,
+                    #[validate(range(min = -1.5))]
+                    score: f64,
+                    address: {
+                        #[validate(length(max = 10))]
+                        zip: String,
+                    },
+                }
+                output: None
+            }
+            "#,
+        )
+        .unwrap();
+
+        let ValueModel::Struct { fields } = &model.functions[0].input else {
+            panic!("expected a struct input");
+        };
+        match fields[0].validations.as_slice() {
+            [Validation::Length { min, max }, Validation::Regex(pattern)] => {
+                assert_eq!(min.as_ref().unwrap().base10_digits(), "1");
+                assert_eq!(max.as_ref().unwrap().base10_digits(), "64");
+                assert_eq!(pattern.value(), "[a-z]+");
+            }
+            other => panic!("unexpected rules: {:?}", other),
+        }
+        match fields[1].validations.as_slice() {
+            [Validation::Range { min, max }] => {
+                assert_eq!(min.as_ref().unwrap().to_string(), "- 1.5");
+                assert!(max.is_none());
+            }
+            other => panic!("unexpected rules: {:?}", other),
+        }
+        assert!(fields[2].validations.is_empty());
+        assert!(matches!(
+            model.helper_structs[0].fields[0].validations.as_slice(),
+            [Validation::Length {
+                min: None,
+                max: Some(_)
+            }]
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_validation_rules() {
+        let err = |rule: &str| {
+            model_err(&format!(
+                "MyRepo; function f {{ input: {{ #[validate({})] name: String }} output: None }}",
+                rule
+            ))
+        };
+        assert!(err("length(min = 1, exact = 2)").contains("expected `min` or `max`"));
+        assert!(err("range(min = \"a\")").contains("expected a numeric literal"));
+        assert!(err("regex = \"[a-z\"").contains("invalid `regex` pattern"));
+        assert!(err("format = \"email\"").contains("unknown validation"));
+    }
+
+    #[test]
+    fn checks_validation_rules_against_field_types() {
+        let err = |rule: &str, ty: &str| {
+            model(&format!(
+                "MyRepo; function f {{ input: {{ #[validate({})] value: {} }} output: None }}",
+                rule, ty
+            ))
+            .err()
+            .map(|e| e.to_string())
+        };
+        assert!(err("regex = \"[a-z]+\"", "Option<&str>").is_none());
+        assert!(
+            err("regex = \"[a-z]+\"", "u32")
+                .unwrap()
+                .contains("only supported on `String`")
+        );
+        assert!(err("regex = \"[a-z]+\"", "Vec<String>").is_some());
+        assert!(err("range(min = -1, max = 10)", "Option<i64>").is_none());
+        assert!(err("range(min = -1.5)", "f32").is_none());
+        assert!(
+            err("range(min = 1.5)", "u8")
+                .unwrap()
+                .contains("of `u8` fields must be integers")
+        );
+        assert!(
+            err("range(min = -1)", "&u64")
+                .unwrap()
+                .contains("out of range for `u64`")
+        );
+        assert!(
+            err("range(max = 300)", "Option<u8>")
+                .unwrap()
+                .contains("out of range for `u8`")
+        );
+        assert!(err("range(min = -128, max = 127)", "i8").is_none());
+        // Bounds of other types are left to the compiler.
+        assert!(err("range(min = -1)", "Decimal").is_none());
+    }
+
+    #[test]
+    fn rejects_validation_on_outputs_and_direct_functions() {
+        assert!(
+            model_err(
+                "MyRepo; function f { input: None output: { #[validate(length(min = 1))] name: String } }"
+            )
+            .contains("only supported on input fields")
+        );
+        assert!(
+            model_err(
+                "MyRepo; function f { input: None output: { inner: { #[validate(range(max = 1))] n: u8 } } }"
+            )
+            .contains("only supported on input fields")
+        );
+        assert!(
+            model_err(
+                "MyRepo; function_direct f { input: { #[validate(length(min = 1))] name: String } output: None }"
+            )
+            .contains("not supported on direct functions")
+        );
+    }
+
+    #[test]
+    fn rejects_batch_inputs_borrowing_for_a_lifetime() {
+        assert!(
+            model_err("#[batch] MyRepo; function f { input: Option<&'a str> output: None }")
+                .contains("`#[batch]` does not support inputs borrowing for a lifetime")
+        );
+        assert!(
+            model_err("#[batch] MyRepo; function f { input: &'a [Cow<'a, str>] output: None }")
+                .contains("`#[batch]` does not support inputs borrowing for a lifetime")
+        );
+        // Top-level references are owned, and struct fields are owned already.
+        assert!(model("#[batch] MyRepo; function f { input: &'a str output: None }").is_ok());
+        assert!(
+            model("#[batch] MyRepo; function f { input: { tag: Option<&'a str> } output: None }")
+                .is_ok()
+        );
+        // Without `#[batch]`, nothing needs to be owned.
+        assert!(model("MyRepo; function f { input: Option<&'a str> output: None }").is_ok());
+    }
+
+    #[test]
+    fn detects_named_lifetimes() {
+        let named = |ty: &str| has_named_lifetime(&syn::parse_str(ty).unwrap());
+        assert!(named("&'a str"));
+        assert!(named("Vec<Cow<'a, str>>"));
+        assert!(named("Option<(u8, &'b [u8])>"));
+        assert!(!named("&'static str"));
+        assert!(!named("&str"));
+        assert!(!named("HashMap<String, Vec<u8>>"));
+    }
+
+    #[test]
+    fn rejects_unsupported_http_client_functions() {
+        assert!(
+            model_err("#[http_client] MyRepo; blocking f { input: None output: None }")
+                .contains("`#[http_client]` only supports `function`s")
+        );
+        assert!(
+            model_err("#[http_client] MyRepo; function_direct f { input: None output: None }")
+                .contains("`#[http_client]` only supports `function`s")
+        );
+        assert!(
+            model_err(
+                "#[http_client] MyRepo; function f { input: { name: Cow<'a, str> } output: None }"
+            )
+            .contains("does not support input fields borrowing for a lifetime")
+        );
+        // Elided and `'static` borrows are cloned into the owned input struct.
+        assert!(
+            model(
+                "#[http_client] MyRepo; function f { input: { name: &str, tag: &'static str } output: None }"
+            )
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn rejects_concurrent_blocking_functions() {
+        assert!(
+            model_err("MyRepo; blocking f { input: None output: None concurrent }")
+                .contains("`concurrent` is only supported on async functions")
+        );
+        assert!(
+            model_err("MyRepo; blocking_direct f { input: None output: None concurrent }")
+                .contains("`concurrent` is only supported on async functions")
+        );
+        let model = model("MyRepo; function_direct f { input: None output: None concurrent }");
+        assert!(model.unwrap().functions[0].concurrent);
+    }
+}
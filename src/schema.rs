@@ -0,0 +1,183 @@
+//! Shared pieces of the optional `{REPO}_SCHEMA` constants: a JSON document
+//! describing the macro input, for tooling that cannot parse it (admin UIs,
+//! docs, migration scripts).
+
+use proc_macro2::{Delimiter, Ident, TokenStream, TokenTree};
+use quote::quote;
+
+use crate::helpers::to_snake_case;
+
+/// Minimal JSON value, rendered without whitespace. Object keys keep their
+/// insertion order, so the output is stable across builds.
+pub enum Json {
+    Null,
+    Bool(bool),
+    /// Number literal, rendered verbatim (ex. `0`, `-1.5`).
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(&'static str, Json)>),
+    /// Object whose keys are only known at expansion time (ex. field names).
+    Map(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn string(value: impl ToString) -> Self {
+        Json::String(value.to_string())
+    }
+
+    pub fn strings<T: ToString>(values: impl IntoIterator<Item = T>) -> Self {
+        Json::Array(values.into_iter().map(Json::string).collect())
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        self.render_into(&mut out);
+        out
+    }
+
+    fn render_into(&self, out: &mut String) {
+        match self {
+            Json::Null => out.push_str("null"),
+            Json::Bool(value) => out.push_str(if *value { "true" } else { "false" }),
+            Json::Number(value) => out.push_str(value),
+            Json::String(value) => render_string(value, out),
+            Json::Array(items) => {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    item.render_into(out);
+                }
+                out.push(']');
+            }
+            Json::Object(fields) => render_object(fields.iter().map(|(k, v)| (*k, v)), out),
+            Json::Map(fields) => render_object(fields.iter().map(|(k, v)| (k.as_str(), v)), out),
+        }
+    }
+}
+
+fn render_object<'a>(fields: impl Iterator<Item = (&'a str, &'a Json)>, out: &mut String) {
+    out.push('{');
+    for (i, (key, value)) in fields.enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        render_string(key, out);
+        out.push(':');
+        value.render_into(out);
+    }
+    out.push('}');
+}
+
+fn render_string(value: &str, out: &mut String) {
+    out.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if (ch as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+}
+
+/// Render a type as it would usually be written (ex. `Vec<Option<String>>`),
+/// rather than with the spacing of `TokenStream::to_string`.
+pub fn type_string(ty: &TokenStream) -> String {
+    let mut out = String::new();
+    write_tokens(ty.clone(), &mut out);
+    out.replace("->", " -> ")
+}
+
+fn write_tokens(tokens: TokenStream, out: &mut String) {
+    let mut prev_is_word = false;
+    for token in tokens {
+        match token {
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::None => ("", ""),
+                };
+                out.push_str(open);
+                write_tokens(group.stream(), out);
+                out.push_str(close);
+                prev_is_word = false;
+            }
+            TokenTree::Punct(punct) => {
+                out.push(punct.as_char());
+                if matches!(punct.as_char(), ',' | ';') {
+                    out.push(' ');
+                }
+                prev_is_word = false;
+            }
+            TokenTree::Ident(_) | TokenTree::Literal(_) => {
+                if prev_is_word {
+                    out.push(' ');
+                }
+                out.push_str(&token.to_string());
+                prev_is_word = true;
+            }
+        }
+    }
+}
+
+/// `pub const {REPO}_SCHEMA: &str` holding the given document.
+pub fn schema_const(repository_name: &Ident, document: Json, macro_name: &str) -> TokenStream {
+    let const_ident = Ident::new(
+        &format!(
+            "{}_SCHEMA",
+            to_snake_case(&repository_name.to_string()).to_uppercase()
+        ),
+        repository_name.span(),
+    );
+    let doc = format!(
+        " JSON description of `{}`, as declared in `{}!`.",
+        repository_name, macro_name
+    );
+    let document = document.render();
+    quote! {
+        #[doc = #doc]
+        pub const #const_ident: &str = #document;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+
+    use super::{Json, type_string};
+
+    #[test]
+    fn renders_escaped_json_and_compact_type_strings() {
+        let json = Json::Object(vec![
+            ("name", Json::string("say \"hi\"\n")),
+            ("tags", Json::strings(["a", "b"])),
+            ("note", Json::Null),
+            ("direct", Json::Bool(true)),
+            ("limit", Json::Number("-1.5".to_string())),
+            (
+                "fields",
+                Json::Map(vec![("user_id".to_string(), Json::Null)]),
+            ),
+        ]);
+        assert_eq!(
+            json.render(),
+            r#"{"name":"say \"hi\"\n","tags":["a","b"],"note":null,"direct":true,"limit":-1.5,"fields":{"user_id":null}}"#
+        );
+
+        assert_eq!(
+            type_string(&quote! { Vec<Option<HashMap<String, u32>>> }),
+            "Vec<Option<HashMap<String, u32>>>"
+        );
+        assert_eq!(type_string(&quote! { &'static str }), "&'static str");
+        assert_eq!(type_string(&quote! { [u8; 4] }), "[u8; 4]");
+    }
+}
@@ -0,0 +1,194 @@
+//! Helpers for inspecting generated code in unit tests.
+
+use proc_macro2::{Delimiter, Group, TokenStream, TokenTree};
+use quote::ToTokens;
+
+/// A function found in the generated code.
+pub(crate) struct GeneratedFn {
+    // Trait, or last segment of the impl's self type, defining the function;
+    // `None` for free functions.
+    pub owner: Option<String>,
+    pub sig: syn::Signature,
+    // Body tokens, or `None` for trait methods without a default.
+    pub body: Option<String>,
+}
+
+impl GeneratedFn {
+    pub fn param_names(&self) -> Vec<String> {
+        self.sig
+            .inputs
+            .iter()
+            .filter_map(|input| match input {
+                syn::FnArg::Typed(pat) => Some(pat.pat.to_token_stream().to_string()),
+                syn::FnArg::Receiver(_) => None,
+            })
+            .collect()
+    }
+
+    pub fn output(&self) -> String {
+        self.sig.output.to_token_stream().to_string()
+    }
+
+    pub fn body_contains(&self, needle: &str) -> bool {
+        self.body.as_ref().is_some_and(|body| body.contains(needle))
+    }
+}
+
+/// Items of the generated code, including those inside the bodies of the
+/// `macro_rules!` it defines (with `$` dropped from metavariables, so
+/// `$ctx_repo_accessor` parses as a plain identifier, and repetitions
+/// expanded once).
+pub(crate) fn items(tokens: TokenStream) -> Vec<syn::Item> {
+    let file: syn::File = syn::parse2(strip_dollars(tokens)).expect("generated code parses");
+    let mut items = Vec::new();
+    collect_items(file, &mut items);
+    items
+}
+
+fn collect_items(file: syn::File, out: &mut Vec<syn::Item>) {
+    for item in file.items {
+        if let syn::Item::Macro(item_macro) = &item
+            && item_macro.mac.path.is_ident("macro_rules")
+        {
+            // Helper macros expand to expressions rather than items.
+            for body in macro_rules_bodies(&item_macro.mac.tokens) {
+                if let Ok(file) = syn::parse2(body) {
+                    collect_items(file, out);
+                }
+            }
+        }
+        out.push(item);
+    }
+}
+
+/// All functions with the given name, in impls, traits or at the top level.
+pub(crate) fn fns(items: &[syn::Item], name: &str) -> Vec<GeneratedFn> {
+    let mut found = Vec::new();
+    for item in items {
+        match item {
+            syn::Item::Fn(f) if f.sig.ident == name => found.push(GeneratedFn {
+                owner: None,
+                sig: f.sig.clone(),
+                body: Some(f.block.to_token_stream().to_string()),
+            }),
+            syn::Item::Impl(item_impl) => {
+                let owner = match &item_impl.trait_ {
+                    Some((_, path, _)) => path.segments.last().map(|s| s.ident.to_string()),
+                    None => match &*item_impl.self_ty {
+                        syn::Type::Path(ty) => ty.path.segments.last().map(|s| s.ident.to_string()),
+                        other => Some(other.to_token_stream().to_string()),
+                    },
+                };
+                found.extend(
+                    item_impl
+                        .items
+                        .iter()
+                        .filter_map(|impl_item| match impl_item {
+                            syn::ImplItem::Fn(f) if f.sig.ident == name => Some(GeneratedFn {
+                                owner: owner.clone(),
+                                sig: f.sig.clone(),
+                                body: Some(f.block.to_token_stream().to_string()),
+                            }),
+                            _ => None,
+                        }),
+                );
+            }
+            syn::Item::Trait(item_trait) => {
+                found.extend(
+                    item_trait
+                        .items
+                        .iter()
+                        .filter_map(|trait_item| match trait_item {
+                            syn::TraitItem::Fn(f) if f.sig.ident == name => Some(GeneratedFn {
+                                owner: Some(item_trait.ident.to_string()),
+                                sig: f.sig.clone(),
+                                body: f.default.as_ref().map(|b| b.to_token_stream().to_string()),
+                            }),
+                            _ => None,
+                        }),
+                );
+            }
+            _ => {}
+        }
+    }
+    found
+}
+
+/// The single function with the given name defined by `owner` (see
+/// `GeneratedFn::owner`).
+pub(crate) fn fn_of(items: &[syn::Item], owner: Option<&str>, name: &str) -> GeneratedFn {
+    let mut found = fns(items, name)
+        .into_iter()
+        .filter(|f| f.owner.as_deref() == owner)
+        .collect::<Vec<_>>();
+    assert_eq!(found.len(), 1, "expected one `{}` in {:?}", name, owner);
+    found.remove(0)
+}
+
+/// Names of the structs, enums, traits, consts, statics and type aliases
+/// defined at the top level.
+pub(crate) fn type_names(items: &[syn::Item]) -> Vec<String> {
+    items
+        .iter()
+        .filter_map(|item| match item {
+            syn::Item::Struct(item) => Some(item.ident.to_string()),
+            syn::Item::Enum(item) => Some(item.ident.to_string()),
+            syn::Item::Trait(item) => Some(item.ident.to_string()),
+            syn::Item::Const(item) => Some(item.ident.to_string()),
+            syn::Item::Static(item) => Some(item.ident.to_string()),
+            syn::Item::Type(item) => Some(item.ident.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn strip_dollars(tokens: TokenStream) -> TokenStream {
+    let trees = tokens.into_iter().collect::<Vec<_>>();
+    let is_punct = |i: usize, chars: &str| matches!(trees.get(i), Some(TokenTree::Punct(p)) if chars.contains(p.as_char()));
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < trees.len() {
+        match &trees[i] {
+            TokenTree::Punct(punct) if punct.as_char() == '$' => {
+                // Repetitions (ex. `$($proto)+`) are kept as a single instance.
+                if let Some(TokenTree::Group(group)) = trees.get(i + 1)
+                    && group.delimiter() == Delimiter::Parenthesis
+                {
+                    out.extend(strip_dollars(group.stream()));
+                    i += 2;
+                    if is_punct(i, "*+?") {
+                        i += 1;
+                    } else if is_punct(i + 1, "*+?") {
+                        i += 2;
+                    }
+                    continue;
+                }
+            }
+            TokenTree::Group(group) => {
+                let mut stripped = Group::new(group.delimiter(), strip_dollars(group.stream()));
+                stripped.set_span(group.span());
+                out.push(TokenTree::Group(stripped));
+            }
+            other => out.push(other.clone()),
+        }
+        i += 1;
+    }
+    out.into_iter().collect()
+}
+
+// Expansions of each `(pattern) => { body }` rule.
+fn macro_rules_bodies(tokens: &TokenStream) -> Vec<TokenStream> {
+    let trees = tokens.clone().into_iter().collect::<Vec<_>>();
+    let mut bodies = Vec::new();
+    for (i, tree) in trees.iter().enumerate() {
+        if let TokenTree::Punct(punct) = tree
+            && punct.as_char() == '>'
+            && i > 0
+            && matches!(&trees[i - 1], TokenTree::Punct(p) if p.as_char() == '=')
+            && let Some(TokenTree::Group(body)) = trees.get(i + 1)
+        {
+            bodies.push(body.stream());
+        }
+    }
+    bodies
+}